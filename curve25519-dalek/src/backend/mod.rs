@@ -34,6 +34,8 @@
 //! The [`vector`] backend is selected by the `simd_backend` cargo
 //! feature; it uses the [`serial`] backend for non-vectorized operations.
 
+use crate::backend::serial::curve_models::ProjectivePoint;
+use crate::errors::StepError;
 use crate::EdwardsPoint;
 use crate::Scalar;
 
@@ -96,6 +98,40 @@ where
     }
 }
 
+/// Like [`pippenger_optional_multiscalar_mul`], but with the digit width
+/// `w` given rather than chosen from the number of terms.
+#[cfg(feature = "alloc")]
+pub fn pippenger_optional_multiscalar_mul_with_width<I, J>(
+    w: usize,
+    scalars: I,
+    points: J,
+) -> Option<EdwardsPoint>
+where
+    I: IntoIterator,
+    I::Item: core::borrow::Borrow<Scalar>,
+    J: IntoIterator<Item = Option<EdwardsPoint>>,
+{
+    match get_selected_backend() {
+        #[cfg(curve25519_dalek_backend = "simd")]
+        BackendKind::Avx2 => {
+            vector::scalar_mul::pippenger::spec_avx2::Pippenger::optional_multiscalar_mul_with_width(
+                w, scalars, points,
+            )
+        }
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
+        BackendKind::Avx512 => {
+            vector::scalar_mul::pippenger::spec_avx512ifma_avx512vl::Pippenger::optional_multiscalar_mul_with_width(
+                w, scalars, points,
+            )
+        }
+        BackendKind::Serial => {
+            serial::scalar_mul::pippenger::Pippenger::optional_multiscalar_mul_with_width(
+                w, scalars, points,
+            )
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub(crate) enum VartimePrecomputedStraus {
     #[cfg(curve25519_dalek_backend = "simd")]
@@ -276,37 +312,204 @@ pub fn vartime_double_base_mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> Edwa
     }
 }
 
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint,
+/// with the digits of \\(a\\) in a width-`w` NAF, or `None` unless
+/// \\(4 \leq w \leq 8\\).
+#[allow(non_snake_case)]
+pub fn vartime_double_base_mul_with_naf_width(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    w: usize,
+) -> Option<EdwardsPoint> {
+    match get_selected_backend() {
+        #[cfg(curve25519_dalek_backend = "simd")]
+        BackendKind::Avx2 => {
+            vector::scalar_mul::vartime_double_base::spec_avx2::mul_with_naf_width(a, A, b, w)
+        }
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
+        BackendKind::Avx512 => {
+            vector::scalar_mul::vartime_double_base::spec_avx512ifma_avx512vl::mul_with_naf_width(
+                a, A, b, w,
+            )
+        }
+        BackendKind::Serial => {
+            serial::scalar_mul::vartime_double_base::mul_with_naf_width(a, A, b, w)
+        }
+    }
+}
+
+/// Compute \\(aA + bB + cC\\) in variable time, where \\(C\\) is the Ed25519 basepoint.
+#[allow(non_snake_case)]
+pub fn vartime_triple_base_mul(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    B: &EdwardsPoint,
+    c: &Scalar,
+) -> EdwardsPoint {
+    match get_selected_backend() {
+        #[cfg(curve25519_dalek_backend = "simd")]
+        BackendKind::Avx2 => vector::scalar_mul::vartime_triple_base::spec_avx2::mul(a, A, b, B, c),
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
+        BackendKind::Avx512 => {
+            vector::scalar_mul::vartime_triple_base::spec_avx512ifma_avx512vl::mul(a, A, b, B, c)
+        }
+        BackendKind::Serial => serial::scalar_mul::vartime_triple_base::mul(a, A, b, B, c),
+    }
+}
+
 /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
+///
+/// Every backend checkpoints the accumulator in the serial backend's
+/// projective form, so a checkpoint written on one backend resumes on any
+/// other, and every backend rejects the same out-of-range indices.
 #[allow(non_snake_case)]
-pub fn step_vartime_double_base_mul<F: Fn(usize, [u64; 15]) -> ()>(
+pub fn step_vartime_double_base_mul<F: FnMut(usize, [u64; 15])>(
     a: &Scalar,
     A: &EdwardsPoint,
     b: &Scalar,
     progress_saving_handle: F,
     i: usize,
     projective_point: [u64; 15],
-) -> (EdwardsPoint, u8) {
+) -> Result<(EdwardsPoint, u8), StepError> {
+    #[allow(unused_imports)]
+    use serial::scalar_mul::vartime_double_base::{deserialize_r_from_backup, DEFAULT_STEP_BUDGET};
+
     match get_selected_backend() {
         #[cfg(curve25519_dalek_backend = "simd")]
-        BackendKind::Avx2 => {
-            (
-                vector::scalar_mul::vartime_double_base::spec_avx2::mul(a, A, b),
-                0u8,
+        BackendKind::Avx2 => vector::scalar_mul::vartime_double_base::spec_avx2::step_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            deserialize_r_from_backup(projective_point),
+            DEFAULT_STEP_BUDGET,
+        )
+        .map(|(point, status, _)| (point, status)),
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
+        BackendKind::Avx512 => {
+            vector::scalar_mul::vartime_double_base::spec_avx512ifma_avx512vl::step_mul(
+                a,
+                A,
+                b,
+                progress_saving_handle,
+                i,
+                deserialize_r_from_backup(projective_point),
+                DEFAULT_STEP_BUDGET,
             )
+            .map(|(point, status, _)| (point, status))
         }
-        #[cfg(all(curve25519_dalek_backend = "simd", nightly))]
+        BackendKind::Serial => serial::scalar_mul::vartime_double_base::step_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            projective_point,
+        ),
+    }
+}
+
+/// Like [`step_vartime_double_base_mul`], but performing at most `budget`
+/// loop iterations per call.
+#[allow(non_snake_case)]
+pub fn step_vartime_double_base_mul_with_budget<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    progress_saving_handle: F,
+    i: usize,
+    projective_point: [u64; 15],
+    budget: usize,
+) -> Result<(EdwardsPoint, u8), StepError> {
+    #[allow(unused_imports)]
+    use serial::scalar_mul::vartime_double_base::deserialize_r_from_backup;
+
+    match get_selected_backend() {
+        #[cfg(curve25519_dalek_backend = "simd")]
+        BackendKind::Avx2 => vector::scalar_mul::vartime_double_base::spec_avx2::step_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            deserialize_r_from_backup(projective_point),
+            budget,
+        )
+        .map(|(point, status, _)| (point, status)),
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
         BackendKind::Avx512 => {
-            vector::scalar_mul::vartime_double_base::spec_avx512ifma_avx512vl::mul(a, A, b)
+            vector::scalar_mul::vartime_double_base::spec_avx512ifma_avx512vl::step_mul(
+                a,
+                A,
+                b,
+                progress_saving_handle,
+                i,
+                deserialize_r_from_backup(projective_point),
+                budget,
+            )
+            .map(|(point, status, _)| (point, status))
         }
-        BackendKind::Serial => {
-            serial::scalar_mul::vartime_double_base::step_mul(
+        BackendKind::Serial => serial::scalar_mul::vartime_double_base::step_mul_with_budget(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            projective_point,
+            budget,
+        ),
+    }
+}
+
+/// Like [`step_vartime_double_base_mul`], but resuming from an accumulator
+/// already reconstructed as a `ProjectivePoint`.
+#[allow(non_snake_case)]
+pub fn step_vartime_double_base_mul_from_point<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    progress_saving_handle: F,
+    i: usize,
+    r: ProjectivePoint,
+) -> Result<(EdwardsPoint, u8), StepError> {
+    #[allow(unused_imports)]
+    use serial::scalar_mul::vartime_double_base::DEFAULT_STEP_BUDGET;
+
+    match get_selected_backend() {
+        #[cfg(curve25519_dalek_backend = "simd")]
+        BackendKind::Avx2 => vector::scalar_mul::vartime_double_base::spec_avx2::step_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            r,
+            DEFAULT_STEP_BUDGET,
+        )
+        .map(|(point, status, _)| (point, status)),
+        #[cfg(all(curve25519_dalek_backend = "unstable_avx512", nightly))]
+        BackendKind::Avx512 => {
+            vector::scalar_mul::vartime_double_base::spec_avx512ifma_avx512vl::step_mul(
                 a,
                 A,
                 b,
                 progress_saving_handle,
                 i,
-                projective_point,
+                r,
+                DEFAULT_STEP_BUDGET,
             )
+            .map(|(point, status, _)| (point, status))
         }
+        BackendKind::Serial => serial::scalar_mul::vartime_double_base::step_mul_from_point(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            r,
+        ),
     }
 }
@@ -99,6 +99,7 @@ impl<'a, 'b> Sub<&'b FieldElement51> for &'a FieldElement51 {
 
 impl<'b> MulAssign<&'b FieldElement51> for FieldElement51 {
     fn mul_assign(&mut self, rhs: &'b FieldElement51) {
+        count_ops!(FieldMul);
         let mut self_loose = fiat_25519_loose_field_element([0; 5]);
         fiat_25519_relax(&mut self_loose, &self.0);
         let mut rhs_loose = fiat_25519_loose_field_element([0; 5]);
@@ -110,6 +111,7 @@ impl<'b> MulAssign<&'b FieldElement51> for FieldElement51 {
 impl<'a, 'b> Mul<&'b FieldElement51> for &'a FieldElement51 {
     type Output = FieldElement51;
     fn mul(self, rhs: &'b FieldElement51) -> FieldElement51 {
+        count_ops!(FieldMul);
         let mut self_loose = fiat_25519_loose_field_element([0; 5]);
         fiat_25519_relax(&mut self_loose, &self.0);
         let mut rhs_loose = fiat_25519_loose_field_element([0; 5]);
@@ -226,6 +228,7 @@ impl FieldElement51 {
 
     /// Given `k > 0`, return `self^(2^k)`.
     pub fn pow2k(&self, mut k: u32) -> FieldElement51 {
+        count_ops!(FieldSquare, k);
         let mut output = *self;
         loop {
             let mut input = fiat_25519_loose_field_element([0; 5]);
@@ -240,6 +243,7 @@ impl FieldElement51 {
 
     /// Returns the square of this field element.
     pub fn square(&self) -> FieldElement51 {
+        count_ops!(FieldSquare);
         let mut self_loose = fiat_25519_loose_field_element([0; 5]);
         fiat_25519_relax(&mut self_loose, &self.0);
         let mut output = FieldElement51::ZERO;
@@ -249,6 +253,7 @@ impl FieldElement51 {
 
     /// Returns 2 times the square of this field element.
     pub fn square2(&self) -> FieldElement51 {
+        count_ops!(FieldSquare);
         let mut self_loose = fiat_25519_loose_field_element([0; 5]);
         fiat_25519_relax(&mut self_loose, &self.0);
         let mut square = fiat_25519_tight_field_element([0; 5]);
@@ -0,0 +1,94 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Straus' method over a number of terms fixed at compile time.
+//!
+//! These are the algorithms of the [`straus`](super::straus) module, with
+//! the per-term lookup tables and scalar digits held in arrays of length
+//! `N` on the stack rather than in `Vec`s, so that they work without
+//! `alloc`.  Each term costs about 1.3KB of stack.
+
+#![allow(non_snake_case)]
+
+use core::cmp::Ordering;
+
+use crate::backend::serial::curve_models::{CompletedPoint, ProjectiveNielsPoint, ProjectivePoint};
+use crate::edwards::EdwardsPoint;
+use crate::scalar::Scalar;
+use crate::traits::Identity;
+use crate::window::{LookupTable, NafLookupTable5};
+
+/// Straus' method for a number of terms fixed at compile time.
+pub struct FixedStraus {}
+
+impl FixedStraus {
+    /// Compute \\(\sum\_i s\_i P\_i\\) in constant time, with radix-16
+    /// digits and one [`LookupTable`] per term.
+    pub fn multiscalar_mul<const N: usize>(
+        scalars: &[Scalar; N],
+        points: &[EdwardsPoint; N],
+    ) -> EdwardsPoint {
+        let mut lookup_tables = [LookupTable::<ProjectiveNielsPoint>::default(); N];
+        for (table, point) in lookup_tables.iter_mut().zip(points.iter()) {
+            *table = LookupTable::from(point);
+        }
+
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut scalar_digits = [[0i8; 64]; N];
+        for (digits, scalar) in scalar_digits.iter_mut().zip(scalars.iter()) {
+            *digits = scalar.as_radix_16();
+        }
+
+        let mut Q = EdwardsPoint::identity();
+        for j in (0..64).rev() {
+            Q = Q.mul_by_pow_2(4);
+            for (s_i, lookup_table_i) in scalar_digits.iter().zip(lookup_tables.iter()) {
+                let R_i = lookup_table_i.select(s_i[j]);
+                Q = (&Q + &R_i).as_extended();
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut scalar_digits);
+
+        Q
+    }
+
+    /// Compute \\(\sum\_i s\_i P\_i\\) in variable time, with width-5 NAFs
+    /// and one [`NafLookupTable5`] per term.
+    pub fn vartime_multiscalar_mul<const N: usize>(
+        scalars: &[Scalar; N],
+        points: &[EdwardsPoint; N],
+    ) -> EdwardsPoint {
+        let mut nafs = [[0i8; 256]; N];
+        for (naf, scalar) in nafs.iter_mut().zip(scalars.iter()) {
+            *naf = scalar.non_adjacent_form(5);
+        }
+
+        let mut lookup_tables = [NafLookupTable5([ProjectiveNielsPoint::identity(); 8]); N];
+        for (table, point) in lookup_tables.iter_mut().zip(points.iter()) {
+            *table = NafLookupTable5::from(point);
+        }
+
+        let mut r = ProjectivePoint::identity();
+        for i in (0..256).rev() {
+            let mut t: CompletedPoint = r.double();
+
+            for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
+                match naf[i].cmp(&0) {
+                    Ordering::Greater => {
+                        t = &t.as_extended() + &lookup_table.select(naf[i] as usize)
+                    }
+                    Ordering::Less => t = &t.as_extended() - &lookup_table.select(-naf[i] as usize),
+                    Ordering::Equal => {}
+                }
+            }
+
+            r = t.as_projective();
+        }
+
+        r.as_extended()
+    }
+}
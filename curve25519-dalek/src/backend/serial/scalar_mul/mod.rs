@@ -23,9 +23,14 @@ pub mod variable_base;
 #[allow(missing_docs)]
 pub mod vartime_double_base;
 
+#[allow(missing_docs)]
+pub mod vartime_triple_base;
+
 #[cfg(feature = "alloc")]
 pub mod straus;
 
+pub mod fixed_straus;
+
 #[cfg(feature = "alloc")]
 pub mod precomputed_straus;
 
@@ -18,7 +18,12 @@ use alloc::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 
+use crate::backend::serial::curve_models::ProjectivePoint;
+use crate::backend::serial::scalar_mul::vartime_double_base::{
+    check_step_call, serialize_r_for_backup, DEFAULT_STEP_BUDGET,
+};
 use crate::edwards::EdwardsPoint;
+use crate::errors::MultiscalarError;
 use crate::scalar::Scalar;
 use crate::traits::MultiscalarMul;
 use crate::traits::VartimeMultiscalarMul;
@@ -162,9 +167,7 @@ impl VartimeMultiscalarMul for Straus {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<EdwardsPoint>>,
     {
-        use crate::backend::serial::curve_models::{
-            CompletedPoint, ProjectiveNielsPoint, ProjectivePoint,
-        };
+        use crate::backend::serial::curve_models::{CompletedPoint, ProjectiveNielsPoint};
         use crate::traits::Identity;
         use crate::window::NafLookupTable5;
 
@@ -199,3 +202,87 @@ impl VartimeMultiscalarMul for Straus {
         Some(r.as_extended())
     }
 }
+
+impl Straus {
+    /// Variable-time Straus, like [`VartimeMultiscalarMul::optional_multiscalar_mul`]
+    /// but performing a bounded number of iterations per call, like
+    /// [`step_mul_from_point`](super::vartime_double_base::step_mul_from_point).
+    ///
+    /// `i_bu` is 300 to start from scratch, or the index of the next NAF
+    /// digit to process when resuming from the accumulator `r_bu`.  If
+    /// digits remain once the budget is spent, the checkpoint is handed to
+    /// `progress_saving_handle` and the status is 1; otherwise the result
+    /// is returned with status 2.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the point and the status;
+    /// - `Err(MultiscalarError::LengthMismatch)` if `scalars` and `points`
+    ///   have different lengths;
+    /// - `Err(MultiscalarError::Step)` if `i_bu` is neither below 256 nor
+    ///   the fresh-start value 300.
+    pub fn step_multiscalar_mul<F: FnMut(usize, [u64; 15])>(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+        mut progress_saving_handle: F,
+        i_bu: usize,
+        r_bu: ProjectivePoint,
+    ) -> Result<(EdwardsPoint, u8), MultiscalarError> {
+        use crate::backend::serial::curve_models::ProjectiveNielsPoint;
+        use crate::traits::Identity;
+        use crate::window::NafLookupTable5;
+
+        if scalars.len() != points.len() {
+            return Err(MultiscalarError::LengthMismatch {
+                scalars: scalars.len(),
+                points: points.len(),
+            });
+        }
+        let resume = check_step_call(i_bu, DEFAULT_STEP_BUDGET).map_err(MultiscalarError::Step)?;
+
+        let nafs: Vec<_> = scalars.iter().map(|c| c.non_adjacent_form(5)).collect();
+        let lookup_tables: Vec<_> = points
+            .iter()
+            .map(NafLookupTable5::<ProjectiveNielsPoint>::from)
+            .collect();
+
+        let (mut i, mut r) = match resume {
+            None => {
+                let start = (0..256)
+                    .rev()
+                    .find(|&i| nafs.iter().any(|naf| naf[i] != 0))
+                    .unwrap_or(0);
+                (start as i64, ProjectivePoint::identity())
+            }
+            Some(i_bu) => (i_bu as i64, r_bu),
+        };
+
+        let mut j = 0;
+        while i >= 0 && j < DEFAULT_STEP_BUDGET {
+            let digit = i as usize;
+            let mut t = r.double();
+            for (naf, lookup_table) in nafs.iter().zip(lookup_tables.iter()) {
+                match naf[digit].cmp(&0) {
+                    Ordering::Greater => {
+                        t = &t.as_extended() + &lookup_table.select(naf[digit] as usize)
+                    }
+                    Ordering::Less => {
+                        t = &t.as_extended() - &lookup_table.select(-naf[digit] as usize)
+                    }
+                    Ordering::Equal => {}
+                }
+            }
+            r = t.as_projective();
+
+            i -= 1;
+            j += 1;
+        }
+
+        if i < 0 {
+            return Ok((r.as_extended(), 2));
+        }
+
+        progress_saving_handle(i as usize, serialize_r_for_backup(&r));
+        Ok((EdwardsPoint::default(), 1))
+    }
+}
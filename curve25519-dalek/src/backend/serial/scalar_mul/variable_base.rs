@@ -1,11 +1,14 @@
 #![allow(non_snake_case)]
 
-use crate::backend::serial::curve_models::ProjectiveNielsPoint;
+use crate::backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
 use crate::edwards::EdwardsPoint;
 use crate::scalar::Scalar;
 use crate::traits::Identity;
 use crate::window::LookupTable;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// Perform constant-time, variable-base scalar multiplication.
 #[rustfmt::skip] // keep alignment of explanatory comments
 pub(crate) fn mul(point: &EdwardsPoint, scalar: &Scalar) -> EdwardsPoint {
@@ -46,3 +49,92 @@ pub(crate) fn mul(point: &EdwardsPoint, scalar: &Scalar) -> EdwardsPoint {
     }
     tmp1.as_extended()
 }
+
+/// Perform constant-time, variable-base scalar multiplication by a scalar
+/// read from `chunks`, as [`stream_scalar_bytes`] does, without holding more
+/// than one chunk and one radix-16 digit of it at a time.
+///
+/// [`mul`] needs the signed radix-16 digits of the whole scalar up front,
+/// since computing them carries from the least significant digit up.  Here
+/// the unsigned digits \\(d_i\\) arrive from the most significant, so each
+/// is recoded as
+///
+///    s_i = d_i - 16*[d_i >= 8] + [d_{i-1} >= 8],
+///
+/// which lies in `[-8, 8]` and needs only the next digit down.  The sum of
+/// the \\(s_i 16^i\\) over a leading zero digit \\(d_{64}\\) and the 64
+/// digits of the scalar telescopes to the scalar, so any 256-bit value is
+/// multiplied exactly, at the cost of 65 rather than 64 iterations.
+///
+/// [`stream_scalar_bytes`]: crate::scalar::stream_scalar_bytes
+pub(crate) fn mul_streaming<const N: usize, E, F>(
+    point: &EdwardsPoint,
+    chunks: F,
+) -> Result<EdwardsPoint, E>
+where
+    F: FnMut(core::ops::Range<usize>, &mut [u8; N]) -> Result<(), E>,
+{
+    let lookup_table = LookupTable::<ProjectiveNielsPoint>::from(point);
+    let mut r = ProjectivePoint::identity();
+    // The digit awaiting the next one down, starting from the leading zero
+    let mut pending = 0u8;
+
+    let mut process = |next: u8| {
+        let digit = pending as i8 - (((pending >> 3) & 1) << 4) as i8 + ((next >> 3) & 1) as i8;
+        let mut t = r.double();
+        for _ in 0..3 {
+            t = t.as_projective().double();
+        }
+        t = &t.as_extended() + &lookup_table.select(digit);
+        r = t.as_projective();
+        pending = next;
+    };
+    let streamed = crate::scalar::stream_scalar_bytes(chunks, |byte| {
+        process(byte >> 4);
+        process(byte & 0x0f);
+    });
+    if streamed.is_ok() {
+        // The least significant digit has no digit below it to carry from
+        process(0);
+    }
+    let result = streamed.map(|()| r.as_extended());
+
+    #[cfg(feature = "zeroize")]
+    {
+        pending.zeroize();
+        r.zeroize();
+    }
+    result
+}
+
+/// Process up to `budget` of the radix-16 digits `scalar_digits` of a
+/// scalar, from the most significant, in the same way as [`mul`].
+///
+/// `lookup_table` and `scalar_digits` are what [`mul`] builds from the
+/// point and the scalar; callers build them once and keep them between
+/// calls.  `remaining` is the number of digits still to process, 64 to
+/// start from scratch, and `r` the accumulator so far, the identity to
+/// start from scratch.  Returns the new number of digits remaining and the
+/// new accumulator.
+///
+/// Every digit costs four doublings, a constant-time table lookup, and one
+/// addition, so the work done by a call depends only on `budget` and
+/// `remaining`, not on the scalar.
+pub(crate) fn step_mul(
+    lookup_table: &LookupTable<ProjectiveNielsPoint>,
+    scalar_digits: &[i8; 64],
+    mut remaining: usize,
+    mut r: ProjectivePoint,
+    budget: usize,
+) -> (usize, ProjectivePoint) {
+    for _ in 0..budget.min(remaining) {
+        remaining -= 1;
+        let mut t = r.double();
+        for _ in 0..3 {
+            t = t.as_projective().double();
+        }
+        t = &t.as_extended() + &lookup_table.select(scalar_digits[remaining]);
+        r = t.as_projective();
+    }
+    (remaining, r)
+}
@@ -11,48 +11,150 @@
 #![allow(non_snake_case)]
 
 use core::cmp::Ordering;
-extern crate std;
 
 use crate::backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
-use crate::backend::serial::u64::field::FieldElement51;
 use crate::constants;
-use crate::edwards::EdwardsPoint;
+use crate::edwards::{EdwardsPoint, VartimeNafTable};
+use crate::errors::StepError;
+use crate::field::FieldElement;
+use crate::resumable::WorkDone;
 use crate::scalar::Scalar;
-use crate::traits::Identity;
+use crate::traits::{Identity, ValidityCheck};
 use crate::window::NafLookupTable5;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// Per-thread counts of the lookup tables set up by [`mul`], so that tests
+/// can check the fast paths really skip them.
+#[cfg(test)]
+pub(crate) mod metrics {
+    use core::cell::Cell;
+
+    std::thread_local! {
+        /// Number of times a table of multiples of `A` was built.
+        pub(crate) static TABLE_A: Cell<usize> = const { Cell::new(0) };
+        /// Number of times a basepoint table was set up.
+        pub(crate) static TABLE_B: Cell<usize> = const { Cell::new(0) };
+    }
+}
+
 /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
+///
+/// When \\(b = 0\\) this is just \\(aA\\), so the basepoint table and the
+/// \\(b\\) digits are skipped and the variable-base multiply is used instead.
+/// Symmetrically, when \\(a = 0\\) this is just \\(bB\\), so building the
+/// table of multiples of \\(A\\) is skipped in favour of the fixed-base
+/// multiply.
 pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
-    let a_naf = a.non_adjacent_form(5);
+    if b == &Scalar::ZERO {
+        return super::variable_base::mul(A, a);
+    }
+    if a == &Scalar::ZERO {
+        return EdwardsPoint::mul_base(b);
+    }
+
+    let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
+    #[cfg(test)]
+    metrics::TABLE_A.with(|n| n.set(n.get() + 1));
+
+    mul_with_table(a, &table_A, b)
+}
+
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint
+/// and `table_A` was built from \\(A\\) ahead of time.
+///
+/// Unlike [`mul`], this never rebuilds the table of multiples of \\(A\\), so
+/// a table shared across many calls is paid for once.
+pub fn mul_with_precomputed(a: &Scalar, table_A: &VartimeNafTable, b: &Scalar) -> EdwardsPoint {
+    mul_with_table(a, table_A.lookup_table(), b)
+}
+
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint
+/// and `table_A` holds the odd multiples of \\(A\\).
+///
+/// This lets callers who multiply the same \\(A\\) many times build its
+/// table once.
+pub(crate) fn mul_with_table(
+    a: &Scalar,
+    table_A: &NafLookupTable5<ProjectiveNielsPoint>,
+    b: &Scalar,
+) -> EdwardsPoint {
+    mul_with_odd_multiples(a, &table_A.0, b)
+}
+
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+/// basepoint, with the digits of \\(a\\) in a width-`w` NAF.
+///
+/// [`mul`] uses \\(w = 5\\).  A wider NAF has fewer nonzero digits, about
+/// \\(256 / (w + 1)\\), so the loop makes fewer additions, but its table
+/// of the \\(2\^{w-2}\\) odd multiples of \\(A\\) takes as many additions
+/// to build and 160 bytes of stack per entry.  For one multiplication
+/// the two balance around \\(w = 5\\): width 4 costs about the same with
+/// half the table, while each wider step is some 5% slower.
+///
+/// Returns `None` unless \\(4 \leq w \leq 8\\).
+pub fn mul_with_naf_width(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    w: usize,
+) -> Option<EdwardsPoint> {
+    Some(match w {
+        4 => mul_with_odd_multiples(a, &odd_multiples::<4>(A), b),
+        5 => mul_with_odd_multiples(a, &odd_multiples::<8>(A), b),
+        6 => mul_with_odd_multiples(a, &odd_multiples::<16>(A), b),
+        7 => mul_with_odd_multiples(a, &odd_multiples::<32>(A), b),
+        8 => mul_with_odd_multiples(a, &odd_multiples::<64>(A), b),
+        _ => return None,
+    })
+}
+
+/// The odd multiples \\(A, 3A, \ldots, (2N - 1)A\\).
+fn odd_multiples<const N: usize>(A: &EdwardsPoint) -> [ProjectiveNielsPoint; N] {
+    #[cfg(test)]
+    metrics::TABLE_A.with(|n| n.set(n.get() + 1));
+
+    let mut Ai = [A.as_projective_niels(); N];
+    let A2 = A.double();
+    for i in 1..N {
+        Ai[i] = (&A2 + &Ai[i - 1]).as_extended().as_projective_niels();
+    }
+    Ai
+}
+
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint
+/// and `odd_multiples_A` holds the \\(N = 2\^{w-2}\\) odd multiples of \\(A\\)
+/// for a width-\\(w\\) NAF of \\(a\\).
+fn mul_with_odd_multiples<const N: usize>(
+    a: &Scalar,
+    odd_multiples_A: &[ProjectiveNielsPoint; N],
+    b: &Scalar,
+) -> EdwardsPoint {
+    let a_naf = a.non_adjacent_form(N.trailing_zeros() as usize + 2);
 
     #[cfg(feature = "precomputed-tables")]
     let b_naf = b.non_adjacent_form(8);
     #[cfg(not(feature = "precomputed-tables"))]
     let b_naf = b.non_adjacent_form(5);
 
-    // Find starting index
-    let mut i: usize = 255;
-    for j in (0..256).rev() {
-        i = j;
-        if a_naf[i] != 0 || b_naf[i] != 0 {
-            break;
-        }
-    }
+    let mut i = start_index(&a_naf, &b_naf);
 
-    let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
     #[cfg(feature = "precomputed-tables")]
     let table_B = &constants::AFFINE_ODD_MULTIPLES_OF_BASEPOINT;
     #[cfg(not(feature = "precomputed-tables"))]
     let table_B =
         &NafLookupTable5::<ProjectiveNielsPoint>::from(&constants::ED25519_BASEPOINT_POINT);
+    #[cfg(test)]
+    metrics::TABLE_B.with(|n| n.set(n.get() + 1));
 
     let mut r = ProjectivePoint::identity();
     loop {
         let mut t = r.double();
 
         match a_naf[i].cmp(&0) {
-            Ordering::Greater => t = &t.as_extended() + &table_A.select(a_naf[i] as usize),
-            Ordering::Less => t = &t.as_extended() - &table_A.select(-a_naf[i] as usize),
+            Ordering::Greater => t = &t.as_extended() + &odd_multiples_A[a_naf[i] as usize / 2],
+            Ordering::Less => t = &t.as_extended() - &odd_multiples_A[-a_naf[i] as usize / 2],
             Ordering::Equal => {}
         }
 
@@ -73,18 +175,72 @@ pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
     r.as_extended()
 }
 
-pub fn deserialize_r_from_backup(projective_point_bu: [u64; 15]) -> ProjectivePoint {
+/// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint,
+/// in a single call, handing the accumulator to `progress_handle` after
+/// every `every_n_doublings` doublings.
+///
+/// The callback receives the same `(index, limbs)` pair [`step_mul`] saves
+/// when it suspends, so it can be persisted or shown as progress, but the
+/// multiplication never suspends.  No callback is made once the last digit
+/// has been processed.
+///
+/// # Panics
+///
+/// Panics if `every_n_doublings` is zero.
+pub fn mul_with_progress<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    every_n_doublings: usize,
+    mut progress_handle: F,
+) -> EdwardsPoint {
+    assert!(every_n_doublings > 0, "every_n_doublings must be nonzero");
+
+    // Run the step-wise loop in chunks of `every_n_doublings` iterations, so
+    // that each callback sees exactly the checkpoint a suspension saves.  A
+    // chunk of `MAX_STEP_BUDGET` iterations already covers every digit.
+    let context = StepMulContext::new(a, A, b);
+    let budget = every_n_doublings.min(MAX_STEP_BUDGET);
+    let mut i = 300;
+    let mut r = ProjectivePoint::identity();
+    loop {
+        let mut saved = None;
+        let (point, status, _) = step_mul_with_context(
+            &context,
+            |index, limbs| saved = Some((index, limbs)),
+            i,
+            r,
+            budget,
+        )
+        .expect("the budget and saved indices are in range");
+        match (status, saved) {
+            (1, Some((index, limbs))) => {
+                progress_handle(index, limbs);
+                i = index;
+                r = deserialize_r_from_backup(limbs);
+            }
+            _ => return point,
+        }
+    }
+}
+
+/// Rebuild the accumulator from the limbs handed to a progress callback.
+///
+/// This does not check the limbs at all, so it is only for limbs the
+/// step-wise loop itself produced; anything loaded from outside the crate
+/// goes through [`try_deserialize_r_from_backup`].
+pub(crate) fn deserialize_r_from_backup(projective_point_bu: [u64; 15]) -> ProjectivePoint {
     let mut array: [u64; 5] = [0u64; 5];
     array.clone_from_slice(&projective_point_bu[0..5]);
-    let x_field_element = FieldElement51(array);
+    let x_field_element = coordinate_from_limbs(array);
 
     let mut array: [u64; 5] = [0u64; 5];
     array.clone_from_slice(&projective_point_bu[5..10]);
-    let y_field_element = FieldElement51(array);
+    let y_field_element = coordinate_from_limbs(array);
 
     let mut array: [u64; 5] = [0u64; 5];
     array.clone_from_slice(&projective_point_bu[10..15]);
-    let z_field_element = FieldElement51(array);
+    let z_field_element = coordinate_from_limbs(array);
 
     ProjectivePoint {
         X: x_field_element,
@@ -93,39 +249,369 @@ pub fn deserialize_r_from_backup(projective_point_bu: [u64; 15]) -> ProjectivePo
     }
 }
 
+// Checkpoints store each coordinate as five radix-2^51 limbs, the
+// representation of the 64-bit serial and fiat-crypto backends, which
+// convert directly.  The 32-bit field implementations go through the
+// canonical encoding, so their checkpoints use the same format.
+
+/// Return the checkpoint limbs of a coordinate.
+#[cfg(all(curve25519_dalek_bits = "64", not(curve25519_dalek_backend = "fiat")))]
+pub(crate) fn coordinate_to_limbs(fe: &FieldElement) -> [u64; 5] {
+    fe.0
+}
+
+/// Rebuild a coordinate from its checkpoint limbs.
+#[cfg(all(curve25519_dalek_bits = "64", not(curve25519_dalek_backend = "fiat")))]
+pub(crate) fn coordinate_from_limbs(limbs: [u64; 5]) -> FieldElement {
+    FieldElement::from_limbs(limbs)
+}
+
+/// Return the checkpoint limbs of a coordinate.
+#[cfg(all(curve25519_dalek_bits = "64", curve25519_dalek_backend = "fiat"))]
+pub(crate) fn coordinate_to_limbs(fe: &FieldElement) -> [u64; 5] {
+    (fe.0).0
+}
+
+/// Rebuild a coordinate from its checkpoint limbs.
+///
+/// The limbs of a checkpoint written by the serial backend may exceed
+/// fiat-crypto's tight bounds, so they are carried first.
+#[cfg(all(curve25519_dalek_bits = "64", curve25519_dalek_backend = "fiat"))]
+pub(crate) fn coordinate_from_limbs(limbs: [u64; 5]) -> FieldElement {
+    FieldElement::from_limbs(carry_limbs(limbs))
+}
+
+/// Carry `limbs` so that each fits in 51 bits, folding the carry out of
+/// the top limb back in times 19.
+#[cfg(not(all(curve25519_dalek_bits = "64", not(curve25519_dalek_backend = "fiat"))))]
+fn carry_limbs(mut limbs: [u64; 5]) -> [u64; 5] {
+    const LOW_51_BIT_MASK: u64 = (1u64 << 51) - 1;
+
+    // After the first round every carry is at most 2^13, and after the
+    // second at most 1; the third leaves every limb below 2^51.
+    for _ in 0..3 {
+        let top = limbs[4] >> 51;
+        limbs[4] &= LOW_51_BIT_MASK;
+        limbs[0] = limbs[0].wrapping_add(top.wrapping_mul(19));
+        for i in 0..4 {
+            let carry = limbs[i] >> 51;
+            limbs[i] &= LOW_51_BIT_MASK;
+            limbs[i + 1] += carry;
+        }
+    }
+    limbs
+}
+
+/// Return the checkpoint limbs of a coordinate.
+#[cfg(curve25519_dalek_bits = "32")]
+pub(crate) fn coordinate_to_limbs(fe: &FieldElement) -> [u64; 5] {
+    let bytes = fe.as_bytes();
+    let mut limbs = [0u64; 5];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        for j in 0..51 {
+            let bit = 51 * i + j;
+            *limb |= (((bytes[bit / 8] >> (bit % 8)) & 1) as u64) << j;
+        }
+    }
+    limbs
+}
+
+/// Rebuild a coordinate from its checkpoint limbs.
+///
+/// The limbs need not be reduced: they are first carried, then repacked
+/// into the canonical encoding.
+#[cfg(curve25519_dalek_bits = "32")]
+pub(crate) fn coordinate_from_limbs(limbs: [u64; 5]) -> FieldElement {
+    let limbs = carry_limbs(limbs);
+
+    let mut bytes = [0u8; 32];
+    for (i, limb) in limbs.iter().enumerate() {
+        for j in 0..51 {
+            let bit = 51 * i + j;
+            bytes[bit / 8] |= (((limb >> j) & 1) as u8) << (bit % 8);
+        }
+    }
+    FieldElement::from_bytes(&bytes)
+}
+
+/// Limbs of a checkpointed coordinate must be below this bound.  Every
+/// accumulator the step-wise loop checkpoints is the output of a field
+/// multiplication, whose limbs are below \\(2\^{51} + 2\^{13}\\) on the
+/// 64-bit serial backend, below \\(1.1 \cdot 2\^{51}\\) on the 64-bit
+/// fiat-crypto backend, and below \\(2\^{51}\\) on the others.
+const CHECKPOINT_LIMB_BOUND: u64 = 1 << 52;
+
+/// Like [`deserialize_r_from_backup`], but checking that the limbs describe
+/// a point the step-wise loop could have checkpointed, so that a corrupted
+/// checkpoint is not silently carried into the rest of the computation.
+///
+/// # Return
+///
+/// - `Ok` with the checkpointed accumulator;
+/// - `Err(StepError::CheckpointLimbOutOfRange)` if a limb is too large to
+///   be a reduced `FieldElement51` limb;
+/// - `Err(StepError::CheckpointZeroZ)` if the \\(Z\\) coordinate is zero;
+/// - `Err(StepError::CheckpointNotOnCurve)` if \\((X:Y:Z)\\) does not
+///   satisfy the projective curve equation.
+pub fn try_deserialize_r_from_backup(
+    projective_point_bu: [u64; 15],
+) -> Result<ProjectivePoint, StepError> {
+    if projective_point_bu
+        .iter()
+        .any(|&limb| limb >= CHECKPOINT_LIMB_BOUND)
+    {
+        return Err(StepError::CheckpointLimbOutOfRange);
+    }
+
+    let r = deserialize_r_from_backup(projective_point_bu);
+    if bool::from(r.Z.is_zero()) {
+        return Err(StepError::CheckpointZeroZ);
+    }
+    if !r.is_valid() {
+        return Err(StepError::CheckpointNotOnCurve);
+    }
+    Ok(r)
+}
+
+/// Flatten `r` into the limbs handed to a step-wise multiplication's
+/// progress callback; the inverse of [`deserialize_r_from_backup`].
+pub(crate) fn serialize_r_for_backup(r: &ProjectivePoint) -> [u64; 15] {
+    let mut limbs = [0u64; 15];
+    limbs[0..5].copy_from_slice(&coordinate_to_limbs(&r.X));
+    limbs[5..10].copy_from_slice(&coordinate_to_limbs(&r.Y));
+    limbs[10..15].copy_from_slice(&coordinate_to_limbs(&r.Z));
+    limbs
+}
+
+/// Find the starting index: the highest index at which either NAF has a
+/// nonzero digit, or 0 if neither does.
+pub(crate) fn start_index(a_naf: &[i8; 256], b_naf: &[i8; 256]) -> usize {
+    (0..256)
+        .rev()
+        .find(|&i| a_naf[i] != 0 || b_naf[i] != 0)
+        .unwrap_or(0)
+}
+
+/// Return the index of the first digit a step-wise multiplication of
+/// \\(aA + bB\\) processes, i.e. the index [`step_mul`] starts from when
+/// given 300.
+pub fn step_start_index(a: &Scalar, b: &Scalar) -> usize {
+    start_index(
+        &a.non_adjacent_form(5),
+        &b.non_adjacent_form(STEP_B_NAF_WIDTH),
+    )
+}
+
+/// The NAF width the step-wise loop uses for \\(b\\): 8 with the
+/// precomputed basepoint table, 5 without.
+#[cfg(feature = "precomputed-tables")]
+pub(crate) const STEP_B_NAF_WIDTH: usize = 8;
+/// The NAF width the step-wise loop uses for \\(b\\): 8 with the
+/// precomputed basepoint table, 5 without.
+#[cfg(not(feature = "precomputed-tables"))]
+pub(crate) const STEP_B_NAF_WIDTH: usize = 5;
+
+/// The setup a step-wise multiplication of \\(aA + bB\\) performs before its
+/// loop: the NAF digits of \\(a\\) and \\(b\\), and the table of odd
+/// multiples of \\(A\\).
+///
+/// Every call to [`step_mul`] redoes this setup; callers who keep a context
+/// between calls can resume with [`step_mul_with_context`] instead.
+#[derive(Copy, Clone)]
+pub(crate) struct StepMulContext {
+    pub(crate) a_naf: [i8; 256],
+    pub(crate) b_naf: [i8; 256],
+    pub(crate) table_A: NafLookupTable5<ProjectiveNielsPoint>,
+}
+
+impl StepMulContext {
+    pub(crate) fn new(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> StepMulContext {
+        StepMulContext {
+            a_naf: a.non_adjacent_form(5),
+            b_naf: b.non_adjacent_form(STEP_B_NAF_WIDTH),
+            table_A: NafLookupTable5::<ProjectiveNielsPoint>::from(A),
+        }
+    }
+
+    /// The index a fresh start processes first.
+    pub(crate) fn start_index(&self) -> usize {
+        start_index(&self.a_naf, &self.b_naf)
+    }
+}
+
+/// Check that `i_bu` can start a step-wise multiplication: either 300, to
+/// start from scratch, or the index of a NAF digit to resume from.
+pub fn check_start_index(i_bu: usize) -> Result<(), StepError> {
+    if i_bu == 300 || i_bu < 256 {
+        Ok(())
+    } else {
+        Err(StepError::IndexOutOfRange { index: i_bu })
+    }
+}
+
+/// Check the arguments of a step-wise call: [`check_start_index`] on
+/// `i_bu` and [`check_step_budget`] on `budget`.
+///
+/// Returns `None` for the fresh-start value 300, and otherwise the index of
+/// the NAF digit to resume from.
+pub(crate) fn check_step_call(i_bu: usize, budget: usize) -> Result<Option<usize>, StepError> {
+    check_start_index(i_bu)?;
+    check_step_budget(budget)?;
+    Ok(if i_bu == 300 { None } else { Some(i_bu) })
+}
+
 /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
-pub fn step_mul<F: Fn(usize, [u64; 15]) -> ()>(
+///
+/// When the call suspends, the `(index, limbs)` pair to resume from is
+/// passed to `progress_saving_handle`.  The handle is an `FnMut`, so it can
+/// write the checkpoint straight into the caller's own storage; nothing
+/// here allocates or needs `std`.
+///
+/// # Return
+///
+/// - `Ok` with the result and status 2 once the last digit is processed,
+///   or with the identity and status 1 after saving a checkpoint;
+/// - `Err(StepError::IndexOutOfRange)` if [`check_start_index`] rejects
+///   `i_bu`.
+pub fn step_mul<F: FnMut(usize, [u64; 15])>(
     a: &Scalar,
     A: &EdwardsPoint,
     b: &Scalar,
     progress_saving_handle: F,
     i_bu: usize,
     projective_point_bu: [u64; 15],
-) -> (EdwardsPoint, u8) {
-    let a_naf = a.non_adjacent_form(5);
+) -> Result<(EdwardsPoint, u8), StepError> {
+    step_mul_from_point(
+        a,
+        A,
+        b,
+        progress_saving_handle,
+        i_bu,
+        deserialize_r_from_backup(projective_point_bu),
+    )
+}
 
-    #[cfg(feature = "precomputed-tables")]
-    let b_naf = b.non_adjacent_form(8);
-    #[cfg(not(feature = "precomputed-tables"))]
-    let b_naf = b.non_adjacent_form(5);
+/// Like [`step_mul`], but resuming from an accumulator that has already been
+/// reconstructed as a `ProjectivePoint`, e.g. from a compressed checkpoint.
+pub fn step_mul_from_point<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    progress_saving_handle: F,
+    i_bu: usize,
+    r_bu: ProjectivePoint,
+) -> Result<(EdwardsPoint, u8), StepError> {
+    let (point, status, _) = step_mul_counted(
+        a,
+        A,
+        b,
+        progress_saving_handle,
+        i_bu,
+        r_bu,
+        DEFAULT_STEP_BUDGET,
+    )?;
+    Ok((point, status))
+}
+
+/// The number of loop iterations [`step_mul`] performs per call.
+pub const DEFAULT_STEP_BUDGET: usize = 30;
+
+/// The largest useful number of loop iterations per call: one per NAF
+/// digit, enough to finish any multiplication in a single call.
+pub const MAX_STEP_BUDGET: usize = 256;
+
+/// Check that `budget` is a usable number of loop iterations per call:
+/// at least one, and at most [`MAX_STEP_BUDGET`].
+pub fn check_step_budget(budget: usize) -> Result<(), StepError> {
+    if (1..=MAX_STEP_BUDGET).contains(&budget) {
+        Ok(())
+    } else {
+        Err(StepError::BudgetOutOfRange { budget })
+    }
+}
+
+/// Like [`step_mul`], but performing at most `budget` loop iterations per
+/// call instead of [`DEFAULT_STEP_BUDGET`], so that callers can size each
+/// call to their host's gas or time limits.
+///
+/// # Return
+///
+/// As for [`step_mul`], and `Err(StepError::BudgetOutOfRange)` if
+/// [`check_step_budget`] rejects `budget`.
+pub fn step_mul_with_budget<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    progress_saving_handle: F,
+    i_bu: usize,
+    projective_point_bu: [u64; 15],
+    budget: usize,
+) -> Result<(EdwardsPoint, u8), StepError> {
+    let (point, status, _) = step_mul_counted(
+        a,
+        A,
+        b,
+        progress_saving_handle,
+        i_bu,
+        deserialize_r_from_backup(projective_point_bu),
+        budget,
+    )?;
+    Ok((point, status))
+}
+
+/// Like [`step_mul_from_point`], but performing at most `budget` loop
+/// iterations, and also reporting the doublings and additions performed by
+/// this call.
+pub(crate) fn step_mul_counted<F: FnMut(usize, [u64; 15])>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    progress_saving_handle: F,
+    i_bu: usize,
+    r_bu: ProjectivePoint,
+    budget: usize,
+) -> Result<(EdwardsPoint, u8, WorkDone), StepError> {
+    // Reject the call before spending any work on the context.
+    check_step_call(i_bu, budget)?;
 
-    let mut i: usize = 255;
+    step_mul_with_context(
+        &StepMulContext::new(a, A, b),
+        progress_saving_handle,
+        i_bu,
+        r_bu,
+        budget,
+    )
+}
+
+/// Like [`step_mul_counted`], but taking the NAF digits and the table of
+/// \\(A\\) from `context` rather than recomputing them, so that the whole
+/// budget goes to doublings and additions.
+///
+/// Returns an error if [`check_step_call`] rejects `i_bu` or `budget`.
+pub(crate) fn step_mul_with_context<F: FnMut(usize, [u64; 15])>(
+    context: &StepMulContext,
+    progress_saving_handle: F,
+    i_bu: usize,
+    r_bu: ProjectivePoint,
+    budget: usize,
+) -> Result<(EdwardsPoint, u8, WorkDone), StepError> {
+    let a_naf = &context.a_naf;
+    let b_naf = &context.b_naf;
+
+    let i;
     let mut r;
-    if i_bu == 300 {
-        // Find starting index
-        for j in (0..256).rev() {
-            i = j;
-            if a_naf[i] != 0 || b_naf[i] != 0 {
-                break;
-            }
+    match check_step_call(i_bu, budget)? {
+        None => {
+            i = context.start_index();
+            r = ProjectivePoint::identity();
+        }
+        Some(i_bu) => {
+            i = i_bu;
+            r = r_bu;
         }
-        r = ProjectivePoint::identity();
-    } else {
-        i = i_bu;
-        r = deserialize_r_from_backup(projective_point_bu);
     }
 
-    let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
+    let table_A = &context.table_A;
     #[cfg(feature = "precomputed-tables")]
     let table_B = &constants::AFFINE_ODD_MULTIPLES_OF_BASEPOINT;
     #[cfg(not(feature = "precomputed-tables"))]
@@ -134,9 +620,11 @@ pub fn step_mul<F: Fn(usize, [u64; 15]) -> ()>(
 
     let mut j = 0;
     let mut ii = i as i64;
+    let mut work = WorkDone::default();
 
-    while ii >= 0 && j < 30 {
+    while ii >= 0 && j < budget {
         let mut t = r.double();
+        work.doublings += 1;
         let ii_usize = ii as usize;
         match a_naf[ii_usize].cmp(&0) {
             Ordering::Greater => t = &t.as_extended() + &table_A.select(a_naf[ii_usize] as usize),
@@ -148,6 +636,7 @@ pub fn step_mul<F: Fn(usize, [u64; 15]) -> ()>(
             Ordering::Less => t = &t.as_extended() - &table_B.select(-b_naf[ii_usize] as usize),
             Ordering::Equal => {}
         }
+        work.additions += (a_naf[ii_usize] != 0) as usize + (b_naf[ii_usize] != 0) as usize;
         r = t.as_projective();
 
         ii -= 1;
@@ -155,18 +644,264 @@ pub fn step_mul<F: Fn(usize, [u64; 15]) -> ()>(
     }
 
     if ii < 0 {
-        return (r.as_extended(), 2);
+        return Ok((r.as_extended(), 2, work));
+    }
+
+    Ok(suspend(progress_saving_handle, ii as usize, r, work))
+}
+
+/// Suspend a step-wise call: hand `(i, limbs of r)` to
+/// `progress_saving_handle` and return the identity with status 1.
+pub(crate) fn suspend<F: FnMut(usize, [u64; 15])>(
+    mut progress_saving_handle: F,
+    i: usize,
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))] mut r: ProjectivePoint,
+    work: WorkDone,
+) -> (EdwardsPoint, u8, WorkDone) {
+    progress_saving_handle(i, serialize_r_for_backup(&r));
+    // The accumulator now lives on in the checkpoint only.
+    #[cfg(feature = "zeroize")]
+    r.zeroize();
+    (EdwardsPoint::default(), 1, work)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mul_with_zero_b_skips_basepoint_table() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let a = Scalar::from(7654321u64);
+
+        let before = metrics::TABLE_B.with(|n| n.get());
+        assert_eq!(mul(&a, &A, &Scalar::ZERO), a * A);
+        assert_eq!(metrics::TABLE_B.with(|n| n.get()), before);
+
+        // A nonzero b still goes through the double-base loop.
+        let b = Scalar::ONE;
+        assert_eq!(mul(&a, &A, &b), a * A + constants::ED25519_BASEPOINT_POINT);
+        assert_eq!(metrics::TABLE_B.with(|n| n.get()), before + 1);
+    }
+    #[test]
+    fn mul_with_naf_width_matches_mul() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let a = -Scalar::from(0x0123_4567_89ab_cdef_u64) * Scalar::from(0xfedc_ba98_u64);
+        let b = Scalar::from(7654321u64).invert();
+        let expected = mul(&a, &A, &b);
+
+        for w in 4..=8 {
+            assert_eq!(mul_with_naf_width(&a, &A, &b, w), Some(expected));
+        }
+    }
+
+    #[test]
+    fn mul_with_naf_width_rejects_out_of_range_widths() {
+        for w in [0, 3, 9, 64] {
+            assert_eq!(
+                mul_with_naf_width(&Scalar::ONE, &EdwardsPoint::identity(), &Scalar::ONE, w),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn mul_with_zero_a_skips_point_table() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let b = Scalar::from(7654321u64);
+
+        let before = metrics::TABLE_A.with(|n| n.get());
+        assert_eq!(
+            mul(&Scalar::ZERO, &A, &b),
+            b * constants::ED25519_BASEPOINT_POINT
+        );
+        assert_eq!(metrics::TABLE_A.with(|n| n.get()), before);
+    }
+
+    #[test]
+    fn start_index_bounds() {
+        assert_eq!(check_start_index(300), Ok(()));
+        assert_eq!(check_start_index(255), Ok(()));
+        assert_eq!(check_start_index(0), Ok(()));
+        for index in [256, 299, 301, usize::MAX] {
+            assert_eq!(
+                check_start_index(index),
+                Err(StepError::IndexOutOfRange { index })
+            );
+        }
+    }
+
+    #[test]
+    fn try_deserialize_r_from_backup_checks_checkpoint() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let a = Scalar::from_bytes_mod_order([0x5a; 32]);
+        let b = Scalar::from_bytes_mod_order([0x3c; 32]);
+        let saved = core::cell::Cell::new(None);
+        mul_with_progress(&a, &A, &b, 40, |_, limbs| saved.set(Some(limbs)));
+        let limbs = saved.get().unwrap();
+
+        let r = try_deserialize_r_from_backup(limbs).unwrap();
+        assert_eq!(
+            r.as_extended().compress(),
+            deserialize_r_from_backup(limbs).as_extended().compress()
+        );
+        let identity = serialize_r_for_backup(&ProjectivePoint::identity());
+        assert!(try_deserialize_r_from_backup(identity).is_ok());
+
+        let mut overflow = limbs;
+        overflow[7] = CHECKPOINT_LIMB_BOUND;
+        assert_eq!(
+            try_deserialize_r_from_backup(overflow).map(|_| ()),
+            Err(StepError::CheckpointLimbOutOfRange)
+        );
+
+        let mut zero_z = limbs;
+        zero_z[10..15].copy_from_slice(&[0; 5]);
+        assert_eq!(
+            try_deserialize_r_from_backup(zero_z).map(|_| ()),
+            Err(StepError::CheckpointZeroZ)
+        );
+        // (0:0:0) satisfies the homogeneous curve equation, so Z is checked
+        // on its own.
+        assert_eq!(
+            try_deserialize_r_from_backup([0; 15]).map(|_| ()),
+            Err(StepError::CheckpointZeroZ)
+        );
+
+        let mut off_curve = limbs;
+        off_curve[5] ^= 1;
+        assert_eq!(
+            try_deserialize_r_from_backup(off_curve).map(|_| ()),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn coordinate_limbs_round_trip() {
+        let fe = FieldElement::from_bytes(&[0x5a; 32]);
+        let limbs = coordinate_to_limbs(&fe);
+        assert!(limbs.iter().all(|&limb| limb < CHECKPOINT_LIMB_BOUND));
+        assert_eq!(coordinate_from_limbs(limbs), fe);
+
+        // Unreduced limbs, as the 64-bit backend may checkpoint, decode to
+        // the same value on every backend: 2^51 in the bottom limb is 1 in
+        // the next, and 2^255 = 19 mod p.
+        let unreduced = coordinate_from_limbs([(1 << 51) + 7, 0, 0, 0, 1 << 51]);
+        assert_eq!(unreduced, coordinate_from_limbs([7 + 19, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn step_mul_saves_into_caller_storage() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let a = Scalar::from_bytes_mod_order([0x5a; 32]);
+        let b = Scalar::from_bytes_mod_order([0x3c; 32]);
+
+        let mut i = 300;
+        let mut r = [0u64; 15];
+        let point = loop {
+            let mut saved = None;
+            let (point, status) = step_mul(
+                &a,
+                &A,
+                &b,
+                |index, limbs| saved = Some((index, limbs)),
+                i,
+                r,
+            )
+            .unwrap();
+            if status == 2 {
+                break point;
+            }
+            let (index, limbs) = saved.unwrap();
+            i = index;
+            r = limbs;
+        };
+        assert_eq!(point, mul(&a, &A, &b));
     }
 
-    // save progress
-    let x_arr = r.X.0;
-    let y_arr = r.Y.0;
-    let z_arr = r.Z.0;
-    let concatenated = [x_arr, y_arr, z_arr].concat();
-    let projective_point_progress: [u64; 15] = concatenated.try_into().unwrap();
+    #[test]
+    fn step_budget_bounds() {
+        assert_eq!(check_step_budget(1), Ok(()));
+        assert_eq!(check_step_budget(DEFAULT_STEP_BUDGET), Ok(()));
+        assert_eq!(check_step_budget(MAX_STEP_BUDGET), Ok(()));
+        for budget in [0, MAX_STEP_BUDGET + 1, usize::MAX] {
+            assert_eq!(
+                check_step_budget(budget),
+                Err(StepError::BudgetOutOfRange { budget })
+            );
+        }
+    }
 
-    i = ii as usize;
+    #[test]
+    fn step_mul_with_budget_matches_mul() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let a = Scalar::from_bytes_mod_order([0x5a; 32]);
+        let b = Scalar::from_bytes_mod_order([0x3c; 32]);
+        let expected = mul(&a, &A, &b);
 
-    progress_saving_handle(i, projective_point_progress);
-    return (EdwardsPoint::default(), 1);
+        for budget in [1, 7, MAX_STEP_BUDGET] {
+            let last = core::cell::Cell::new((300, [0u64; 15]));
+            let mut calls = 0;
+            let point = loop {
+                let (i, r) = last.get();
+                let (point, status) =
+                    step_mul_with_budget(&a, &A, &b, |i, r| last.set((i, r)), i, r, budget)
+                        .unwrap();
+                calls += 1;
+                if status != 1 {
+                    break point;
+                }
+            };
+            assert_eq!(point, expected);
+            if budget == MAX_STEP_BUDGET {
+                assert_eq!(calls, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn step_mul_with_budget_rejects_zero_budget() {
+        let A = constants::ED25519_BASEPOINT_POINT;
+        let result = step_mul_with_budget(
+            &Scalar::ONE,
+            &A,
+            &Scalar::ONE,
+            |_, _| {},
+            300,
+            [0u64; 15],
+            0,
+        );
+        assert_eq!(result, Err(StepError::BudgetOutOfRange { budget: 0 }));
+    }
+
+    #[test]
+    fn step_mul_rejects_oversized_start_index() {
+        let A = constants::ED25519_BASEPOINT_POINT;
+        let result = step_mul(&Scalar::ONE, &A, &Scalar::ONE, |_, _| {}, 256, [0u64; 15]);
+        assert_eq!(result, Err(StepError::IndexOutOfRange { index: 256 }));
+    }
+
+    #[test]
+    fn table_from_odd_multiples() {
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let table = NafLookupTable5::<ProjectiveNielsPoint>::from(&A);
+
+        let rebuilt = NafLookupTable5::from_odd_multiples(table.0).unwrap();
+        for x in (1..16).step_by(2) {
+            let expected = (&EdwardsPoint::identity() + &table.select(x)).as_extended();
+            let actual = (&EdwardsPoint::identity() + &rebuilt.select(x)).as_extended();
+            assert_eq!(actual, expected);
+        }
+        let (a, b) = (Scalar::from(7654321u64), Scalar::from(99u64));
+        assert_eq!(mul_with_table(&a, &rebuilt, &b), mul(&a, &A, &b));
+
+        let precomputed = VartimeNafTable::new(&A);
+        assert_eq!(mul_with_precomputed(&a, &precomputed, &b), mul(&a, &A, &b));
+        assert_eq!(precomputed.point(), &A);
+
+        // Swapping two entries breaks the sequence.
+        let mut corrupted = table.0;
+        corrupted.swap(2, 3);
+        assert!(NafLookupTable5::from_odd_multiples(corrupted).is_none());
+    }
 }
@@ -0,0 +1,87 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// Copyright (c) 2016-2021 isis lovecruft
+// Copyright (c) 2016-2019 Henry de Valence
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Henry de Valence <hdevalence@hdevalence.ca>
+#![allow(non_snake_case)]
+
+use core::cmp::Ordering;
+
+use crate::backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
+use crate::constants;
+use crate::edwards::EdwardsPoint;
+use crate::scalar::Scalar;
+use crate::traits::Identity;
+use crate::window::NafLookupTable5;
+
+/// Compute \\(aA + bB + cC\\) in variable time, where \\(C\\) is the Ed25519
+/// basepoint.
+///
+/// The three NAFs are interleaved in a single double-and-add loop, so the
+/// doublings are shared as in [`super::vartime_double_base::mul`].  When
+/// \\(b = 0\\) this is just a double-base multiplication, which skips
+/// building the table of multiples of \\(B\\).
+pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar, B: &EdwardsPoint, c: &Scalar) -> EdwardsPoint {
+    if b == &Scalar::ZERO {
+        return super::vartime_double_base::mul(a, A, c);
+    }
+
+    let a_naf = a.non_adjacent_form(5);
+    let b_naf = b.non_adjacent_form(5);
+
+    #[cfg(feature = "precomputed-tables")]
+    let c_naf = c.non_adjacent_form(8);
+    #[cfg(not(feature = "precomputed-tables"))]
+    let c_naf = c.non_adjacent_form(5);
+
+    let mut i = (0..256)
+        .rev()
+        .find(|&i| a_naf[i] != 0 || b_naf[i] != 0 || c_naf[i] != 0)
+        .unwrap_or(0);
+
+    let table_A = NafLookupTable5::<ProjectiveNielsPoint>::from(A);
+    let table_B = NafLookupTable5::<ProjectiveNielsPoint>::from(B);
+
+    #[cfg(feature = "precomputed-tables")]
+    let table_C = &constants::AFFINE_ODD_MULTIPLES_OF_BASEPOINT;
+    #[cfg(not(feature = "precomputed-tables"))]
+    let table_C =
+        &NafLookupTable5::<ProjectiveNielsPoint>::from(&constants::ED25519_BASEPOINT_POINT);
+
+    let mut r = ProjectivePoint::identity();
+    loop {
+        let mut t = r.double();
+
+        match a_naf[i].cmp(&0) {
+            Ordering::Greater => t = &t.as_extended() + &table_A.select(a_naf[i] as usize),
+            Ordering::Less => t = &t.as_extended() - &table_A.select(-a_naf[i] as usize),
+            Ordering::Equal => {}
+        }
+
+        match b_naf[i].cmp(&0) {
+            Ordering::Greater => t = &t.as_extended() + &table_B.select(b_naf[i] as usize),
+            Ordering::Less => t = &t.as_extended() - &table_B.select(-b_naf[i] as usize),
+            Ordering::Equal => {}
+        }
+
+        match c_naf[i].cmp(&0) {
+            Ordering::Greater => t = &t.as_extended() + &table_C.select(c_naf[i] as usize),
+            Ordering::Less => t = &t.as_extended() - &table_C.select(-c_naf[i] as usize),
+            Ordering::Equal => {}
+        }
+
+        r = t.as_projective();
+
+        if i == 0 {
+            break;
+        }
+        i -= 1;
+    }
+
+    r.as_extended()
+}
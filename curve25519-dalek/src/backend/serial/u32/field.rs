@@ -126,6 +126,8 @@ impl<'a, 'b> Mul<&'b FieldElement2625> for &'a FieldElement2625 {
 
     #[rustfmt::skip] // keep alignment of z* calculations
     fn mul(self, _rhs: &'b FieldElement2625) -> FieldElement2625 {
+        count_ops!(FieldMul);
+
         /// Helper function to multiply two 32-bit integers with 64 bits
         /// of output.
         #[inline(always)]
@@ -590,11 +592,13 @@ impl FieldElement2625 {
 
     /// Compute `self^2`.
     pub fn square(&self) -> FieldElement2625 {
+        count_ops!(FieldSquare);
         FieldElement2625::reduce(self.square_inner())
     }
 
     /// Compute `2*self^2`.
     pub fn square2(&self) -> FieldElement2625 {
+        count_ops!(FieldSquare);
         let mut coeffs = self.square_inner();
         for coeff in &mut coeffs {
             *coeff += *coeff;
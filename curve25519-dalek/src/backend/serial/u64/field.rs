@@ -113,6 +113,8 @@ impl<'a, 'b> Mul<&'b FieldElement51> for &'a FieldElement51 {
 
     #[rustfmt::skip] // keep alignment of c* calculations
     fn mul(self, _rhs: &'b FieldElement51) -> FieldElement51 {
+        count_ops!(FieldMul);
+
         /// Helper function to multiply two 64-bit integers with 128
         /// bits of output.
         #[inline(always)]
@@ -454,6 +456,7 @@ impl FieldElement51 {
     pub fn pow2k(&self, mut k: u32) -> FieldElement51 {
 
         debug_assert!( k > 0 );
+        count_ops!(FieldSquare, k);
 
         /// Multiply two 64-bit integers with 128 bits of output.
         #[inline(always)]
@@ -112,6 +112,8 @@ impl Identity for ExtendedPoint {
 impl ExtendedPoint {
     /// Compute the double of this point.
     pub fn double(&self) -> ExtendedPoint {
+        count_ops!(PointDouble);
+
         // Want to compute (X1 Y1 Z1 X1+Y1).
         // Not sure how to do this less expensively than computing
         // (X1 Y1 Z1 T1) --(256bit shuffle)--> (X1 Y1 X1 Y1)
@@ -259,6 +261,8 @@ impl Add<&CachedPoint> for &ExtendedPoint {
 
     /// Add an `ExtendedPoint` and a `CachedPoint`.
     fn add(self, other: &CachedPoint) -> ExtendedPoint {
+        count_ops!(PointAdd);
+
         // The coefficients of an `ExtendedPoint` are reduced after
         // every operation.  If the `CachedPoint` was negated, its
         // coefficients grow by one bit.  So on input, `self` is
@@ -84,6 +84,8 @@ impl Identity for ExtendedPoint {
 #[unsafe_target_feature("avx512ifma,avx512vl")]
 impl ExtendedPoint {
     pub fn double(&self) -> ExtendedPoint {
+        count_ops!(PointDouble);
+
         // (Y1 X1 T1 Z1) -- uses vpshufd (1c latency @ 1/c)
         let mut tmp0 = self.0.shuffle(Shuffle::BADC);
 
@@ -139,6 +141,8 @@ impl<'a, 'b> Add<&'b CachedPoint> for &'a ExtendedPoint {
 
     /// Add an `ExtendedPoint` and a `CachedPoint`.
     fn add(self, other: &'b CachedPoint) -> ExtendedPoint {
+        count_ops!(PointAdd);
+
         let mut tmp = self.0;
 
         tmp = tmp.blend(&tmp.diff_sum(), Lanes::AB);
@@ -29,6 +29,7 @@ pub mod spec {
     #[for_target_feature("avx512ifma")]
     use crate::backend::vector::ifma::{CachedPoint, ExtendedPoint};
 
+    use crate::backend::serial::scalar_mul::pippenger::Pippenger as SerialPippenger;
     use crate::edwards::EdwardsPoint;
     use crate::scalar::Scalar;
     use crate::traits::{Identity, VartimeMultiscalarMul};
@@ -49,21 +50,35 @@ pub mod spec {
         {
             let mut scalars = scalars.into_iter();
             let size = scalars.by_ref().size_hint().0;
-            let w = if size < 500 {
-                6
-            } else if size < 800 {
-                7
-            } else {
-                8
-            };
 
+            Pippenger::optional_multiscalar_mul_with_width(
+                SerialPippenger::digit_width(size),
+                scalars,
+                points,
+            )
+        }
+    }
+
+    impl Pippenger {
+        /// Like [`Pippenger::optional_multiscalar_mul`], but with the digit
+        /// width `w` given rather than chosen from the number of terms.
+        pub fn optional_multiscalar_mul_with_width<I, J>(
+            w: usize,
+            scalars: I,
+            points: J,
+        ) -> Option<EdwardsPoint>
+        where
+            I: IntoIterator,
+            I::Item: Borrow<Scalar>,
+            J: IntoIterator<Item = Option<EdwardsPoint>>,
+        {
             let max_digit: usize = 1 << w;
             let digits_count: usize = Scalar::to_radix_2w_size_hint(w);
             let buckets_count: usize = max_digit / 2; // digits are signed+centered hence 2^w/2, excluding 0-th bucket
 
             // Collect optimized scalars and points in a buffer for repeated access
             // (scanning the whole collection per each digit position).
-            let scalars = scalars.map(|s| s.borrow().as_radix_2w(w));
+            let scalars = scalars.into_iter().map(|s| s.borrow().as_radix_2w(w));
 
             let points = points
                 .into_iter()
@@ -36,13 +36,38 @@ pub mod spec {
     #[for_target_feature("avx512ifma")]
     use crate::backend::vector::ifma::constants::BASEPOINT_ODD_LOOKUP_TABLE;
 
+    #[for_target_feature("avx2")]
+    use crate::backend::vector::scalar_mul::variable_base::spec_avx2::mul as variable_base_mul;
+
+    #[for_target_feature("avx512ifma")]
+    use crate::backend::vector::scalar_mul::variable_base::spec_avx512ifma_avx512vl::mul as variable_base_mul;
+
+    use crate::backend::serial::curve_models::ProjectivePoint;
+    use crate::backend::serial::scalar_mul::vartime_double_base::{
+        check_step_call, start_index, suspend, STEP_B_NAF_WIDTH,
+    };
     use crate::edwards::EdwardsPoint;
+    use crate::errors::StepError;
+    use crate::resumable::WorkDone;
     use crate::scalar::Scalar;
     use crate::traits::Identity;
     use crate::window::NafLookupTable5;
 
     /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
+    ///
+    /// When \\(b = 0\\) this is just \\(aA\\), so the basepoint table and the
+    /// \\(b\\) digits are skipped and the variable-base multiply is used instead.
+    /// Symmetrically, when \\(a = 0\\) this is just \\(bB\\), so building the
+    /// table of multiples of \\(A\\) is skipped in favour of the fixed-base
+    /// multiply.
     pub fn mul(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
+        if b == &Scalar::ZERO {
+            return variable_base_mul(A, a);
+        }
+        if a == &Scalar::ZERO {
+            return EdwardsPoint::mul_base(b);
+        }
+
         let a_naf = a.non_adjacent_form(5);
 
         #[cfg(feature = "precomputed-tables")]
@@ -101,4 +126,299 @@ pub mod spec {
 
         Q.into()
     }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint, with the digits of \\(a\\) in a width-`w` NAF.
+    ///
+    /// This is the vector counterpart of the serial backend's
+    /// `mul_with_naf_width`, which describes the trade-off.
+    ///
+    /// Returns `None` unless \\(4 \leq w \leq 8\\).
+    pub fn mul_with_naf_width(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        w: usize,
+    ) -> Option<EdwardsPoint> {
+        Some(match w {
+            4 => mul_with_odd_multiples(a, &odd_multiples::<4>(A), b),
+            5 => mul_with_odd_multiples(a, &odd_multiples::<8>(A), b),
+            6 => mul_with_odd_multiples(a, &odd_multiples::<16>(A), b),
+            7 => mul_with_odd_multiples(a, &odd_multiples::<32>(A), b),
+            8 => mul_with_odd_multiples(a, &odd_multiples::<64>(A), b),
+            _ => return None,
+        })
+    }
+
+    /// The odd multiples \\(A, 3A, \ldots, (2N - 1)A\\).
+    fn odd_multiples<const N: usize>(A: &EdwardsPoint) -> [CachedPoint; N] {
+        let A = ExtendedPoint::from(*A);
+        let mut Ai = [CachedPoint::from(A); N];
+        let A2 = A.double();
+        for i in 1..N {
+            Ai[i] = (&A2 + &Ai[i - 1]).into();
+        }
+        Ai
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint and `odd_multiples_A` holds the \\(N = 2\^{w-2}\\) odd
+    /// multiples of \\(A\\) for a width-\\(w\\) NAF of \\(a\\).
+    fn mul_with_odd_multiples<const N: usize>(
+        a: &Scalar,
+        odd_multiples_A: &[CachedPoint; N],
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        let a_naf = a.non_adjacent_form(N.trailing_zeros() as usize + 2);
+
+        #[cfg(feature = "precomputed-tables")]
+        let b_naf = b.non_adjacent_form(8);
+        #[cfg(not(feature = "precomputed-tables"))]
+        let b_naf = b.non_adjacent_form(5);
+
+        let mut i = start_index(&a_naf, &b_naf);
+
+        #[cfg(feature = "precomputed-tables")]
+        let table_B = &BASEPOINT_ODD_LOOKUP_TABLE;
+
+        #[cfg(not(feature = "precomputed-tables"))]
+        let table_B =
+            &NafLookupTable5::<CachedPoint>::from(&crate::constants::ED25519_BASEPOINT_POINT);
+
+        let mut Q = ExtendedPoint::identity();
+
+        loop {
+            Q = Q.double();
+
+            match a_naf[i].cmp(&0) {
+                Ordering::Greater => Q = &Q + &odd_multiples_A[a_naf[i] as usize / 2],
+                Ordering::Less => Q = &Q - &odd_multiples_A[-a_naf[i] as usize / 2],
+                Ordering::Equal => {}
+            }
+
+            match b_naf[i].cmp(&0) {
+                Ordering::Greater => Q = &Q + &table_B.select(b_naf[i] as usize),
+                Ordering::Less => Q = &Q - &table_B.select(-b_naf[i] as usize),
+                Ordering::Equal => {}
+            }
+
+            if i == 0 {
+                break;
+            }
+            i -= 1;
+        }
+
+        Q.into()
+    }
+
+    /// Like [`mul`], but running at most `budget` iterations of the loop,
+    /// and otherwise behaving as the serial backend's step-wise loop: a
+    /// fresh start is `i_bu = 300`, a suspended call hands `(index, limbs)`
+    /// to `progress_saving_handle` and returns status 1, and a completed
+    /// call returns the result with status 2.
+    ///
+    /// The accumulator crosses calls in the serial backend's form, so that
+    /// checkpoints are interchangeable between backends.  On resume,
+    /// `r_bu` is extended with \\(T = XY/Z\\) and loaded into the vector
+    /// lanes; on suspension, the vector accumulator is converted back to
+    /// an `EdwardsPoint`, whose \\((X:Y:Z)\\) is checkpointed.  Both are
+    /// the same projective point, so a checkpoint written by either
+    /// backend resumes on the other to the same result, though the limbs
+    /// themselves differ.
+    ///
+    /// # Return
+    ///
+    /// `Err` if [`check_step_call`] rejects `i_bu` or `budget`.
+    pub fn step_mul<F: FnMut(usize, [u64; 15])>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        progress_saving_handle: F,
+        i_bu: usize,
+        r_bu: ProjectivePoint,
+        budget: usize,
+    ) -> Result<(EdwardsPoint, u8, WorkDone), StepError> {
+        let resume = check_step_call(i_bu, budget)?;
+
+        let a_naf = a.non_adjacent_form(5);
+        let b_naf = b.non_adjacent_form(STEP_B_NAF_WIDTH);
+
+        let (i, mut Q) = match resume {
+            None => (start_index(&a_naf, &b_naf), ExtendedPoint::identity()),
+            Some(i_bu) => (i_bu, ExtendedPoint::from(r_bu.as_extended())),
+        };
+
+        let table_A = NafLookupTable5::<CachedPoint>::from(A);
+
+        #[cfg(feature = "precomputed-tables")]
+        let table_B = &BASEPOINT_ODD_LOOKUP_TABLE;
+
+        #[cfg(not(feature = "precomputed-tables"))]
+        let table_B =
+            &NafLookupTable5::<CachedPoint>::from(&crate::constants::ED25519_BASEPOINT_POINT);
+
+        let mut j = 0;
+        let mut ii = i as i64;
+        let mut work = WorkDone::default();
+
+        while ii >= 0 && j < budget {
+            let i = ii as usize;
+            Q = Q.double();
+            work.doublings += 1;
+
+            match a_naf[i].cmp(&0) {
+                Ordering::Greater => Q = &Q + &table_A.select(a_naf[i] as usize),
+                Ordering::Less => Q = &Q - &table_A.select(-a_naf[i] as usize),
+                Ordering::Equal => {}
+            }
+            match b_naf[i].cmp(&0) {
+                Ordering::Greater => Q = &Q + &table_B.select(b_naf[i] as usize),
+                Ordering::Less => Q = &Q - &table_B.select(-b_naf[i] as usize),
+                Ordering::Equal => {}
+            }
+            work.additions += (a_naf[i] != 0) as usize + (b_naf[i] != 0) as usize;
+
+            ii -= 1;
+            j += 1;
+        }
+
+        if ii < 0 {
+            return Ok((Q.into(), 2, work));
+        }
+
+        let r = EdwardsPoint::from(Q).as_projective();
+        Ok(suspend(progress_saving_handle, ii as usize, r, work))
+    }
+}
+
+#[cfg(all(test, curve25519_dalek_backend = "simd"))]
+mod test {
+    use super::spec_avx2::{mul, mul_with_naf_width, step_mul};
+    use crate::backend::serial::scalar_mul::vartime_double_base as serial;
+    use crate::edwards::{CompressedEdwardsY, EdwardsPoint};
+    use crate::resumable::{compress_checkpoint, start_index};
+    use crate::scalar::Scalar;
+    use core::cell::Cell;
+
+    /// The SIMD functions may only be called on a CPU with AVX2, so each
+    /// test skips itself elsewhere.
+    fn has_avx2() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    /// The accumulator of a step-wise multiplication at one index, as
+    /// computed by the serial and by the SIMD backend.
+    struct BackendCheckpoints {
+        serial_limbs: [u64; 15],
+        simd_limbs: [u64; 15],
+        serial_compressed: CompressedEdwardsY,
+        simd_compressed: CompressedEdwardsY,
+    }
+
+    /// Run \\(aA + bB\\) on both backends until `index` is the next digit
+    /// to process, and collect both accumulators.
+    ///
+    /// The backends use different point representations, so the limbs
+    /// are not expected to agree, but the compressed encodings must: a
+    /// checkpoint resumed on the other backend relies on it.
+    fn compare_backend_checkpoints(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        index: usize,
+    ) -> BackendCheckpoints {
+        let start = start_index(a, b);
+        assert!(index < start, "index must be below the start index");
+
+        let saved = Cell::new(None);
+        serial::mul_with_progress(a, A, b, start - index, |i, limbs| {
+            if saved.get().is_none() {
+                assert_eq!(i, index);
+                saved.set(Some(limbs));
+            }
+        });
+        let serial_limbs = saved.get().unwrap();
+
+        let saved = Cell::new(None);
+        let identity = serial::deserialize_r_from_backup([0; 15]);
+        let save = |i, limbs| saved.set(Some((i, limbs)));
+        let (_, status, _) = step_mul(a, A, b, save, 300, identity, start - index).unwrap();
+        assert_eq!(status, 1);
+        let (i, simd_limbs) = saved.get().unwrap();
+        assert_eq!(i, index);
+
+        BackendCheckpoints {
+            serial_limbs,
+            simd_limbs,
+            serial_compressed: compress_checkpoint(serial_limbs).unwrap(),
+            simd_compressed: compress_checkpoint(simd_limbs).unwrap(),
+        }
+    }
+
+    #[test]
+    fn serial_and_simd_checkpoints_agree_canonically() {
+        if !has_avx2() {
+            return;
+        }
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        for index in [0, 1, 100, start_index(&a, &b) - 1] {
+            let checkpoints = compare_backend_checkpoints(&a, &A, &b, index);
+            assert_ne!(checkpoints.serial_limbs, checkpoints.simd_limbs);
+            assert_eq!(checkpoints.serial_compressed, checkpoints.simd_compressed);
+        }
+    }
+
+    #[test]
+    fn mul_with_naf_width_matches_serial() {
+        if !has_avx2() {
+            return;
+        }
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        for w in 0..=9 {
+            assert_eq!(
+                mul_with_naf_width(&a, &A, &b, w),
+                serial::mul_with_naf_width(&a, &A, &b, w)
+            );
+        }
+        assert_eq!(mul_with_naf_width(&a, &A, &b, 6), Some(mul(&a, &A, &b)));
+    }
+
+    #[test]
+    fn step_mul_resumes_across_backends() {
+        if !has_avx2() {
+            return;
+        }
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = mul(&a, &A, &b);
+
+        // Alternate between the backends after every chunk.
+        for simd_first in [false, true] {
+            let saved = Cell::new((300, [0u64; 15]));
+            let mut use_simd = simd_first;
+            loop {
+                let (i, limbs) = saved.get();
+                let r = serial::deserialize_r_from_backup(limbs);
+                let save = |i, limbs| saved.set((i, limbs));
+                let (point, status, _) = if use_simd {
+                    step_mul(&a, &A, &b, save, i, r, 7).unwrap()
+                } else {
+                    serial::step_mul_counted(&a, &A, &b, save, i, r, 7).unwrap()
+                };
+                if status == 2 {
+                    assert_eq!(point, expected);
+                    break;
+                }
+                assert_eq!(status, 1);
+                use_simd = !use_simd;
+            }
+        }
+    }
 }
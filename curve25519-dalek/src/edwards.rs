@@ -93,6 +93,9 @@
 // affine and projective cakes and eat both of them too.
 #![allow(non_snake_case)]
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use core::array::TryFromSliceError;
 use core::borrow::Borrow;
 use core::fmt::Debug;
@@ -104,7 +107,7 @@ use core::ops::{Mul, MulAssign};
 use cfg_if::cfg_if;
 
 #[cfg(feature = "digest")]
-use digest::{generic_array::typenum::U64, Digest};
+use digest::{crypto_common::BlockSizeUser, generic_array::typenum::U64, Digest};
 
 #[cfg(feature = "group")]
 use {
@@ -112,8 +115,8 @@ use {
     subtle::CtOption,
 };
 
-#[cfg(feature = "group")]
-use rand_core::RngCore;
+#[cfg(feature = "rand_core")]
+use rand_core::{CryptoRng, RngCore};
 
 use subtle::Choice;
 use subtle::ConditionallyNegatable;
@@ -125,7 +128,13 @@ use zeroize::Zeroize;
 
 use crate::constants;
 
+#[cfg(feature = "alloc")]
+use crate::errors::MultiscalarError;
+use crate::errors::{InputError, StepError, TableError};
 use crate::field::FieldElement;
+use crate::resumable::CompressedCheckpoint;
+#[cfg(feature = "alloc")]
+use crate::resumable::{Checkpoint, StepMulState, StepResult};
 use crate::scalar::{clamp_integer, Scalar};
 
 use crate::montgomery::MontgomeryPoint;
@@ -141,12 +150,13 @@ use crate::window::{
     LookupTableRadix64,
 };
 
-#[cfg(feature = "precomputed-tables")]
 use crate::traits::BasepointTable;
 
 use crate::traits::ValidityCheck;
 use crate::traits::{Identity, IsIdentity};
 
+use crate::window::{LookupTable, NafLookupTable5};
+
 #[cfg(feature = "alloc")]
 use crate::traits::MultiscalarMul;
 #[cfg(feature = "alloc")]
@@ -261,6 +271,9 @@ use serde::de::Visitor;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "serde")]
+use crate::serde_encoding;
+
 #[cfg(feature = "serde")]
 impl Serialize for EdwardsPoint {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -564,9 +577,54 @@ impl EdwardsPoint {
 
     /// Compress this point to `CompressedEdwardsY` format.
     pub fn compress(&self) -> CompressedEdwardsY {
+        self.compress_with_z_inverse(&self.Z.invert())
+    }
+
+    /// Compress each of `points` to `CompressedEdwardsY` format.
+    ///
+    /// Compressing a point costs an inversion of its \\(Z\\) coordinate.
+    /// This uses Montgomery's trick to invert all the \\(Z\\) coordinates
+    /// with one inversion and three multiplications per point, which is
+    /// much cheaper than calling [`EdwardsPoint::compress`] on each.
+    #[cfg(feature = "alloc")]
+    pub fn compress_batch(points: &[EdwardsPoint]) -> Vec<CompressedEdwardsY> {
+        let mut zinvs: Vec<FieldElement> = points.iter().map(|P| P.Z).collect();
+        FieldElement::batch_invert(&mut zinvs);
+        points
+            .iter()
+            .zip(zinvs.iter())
+            .map(|(P, zinv)| P.compress_with_z_inverse(zinv))
+            .collect()
+    }
+
+    /// Convert this point to affine coordinates.
+    ///
+    /// This costs one field inversion; to convert many points, use
+    /// [`EdwardsPoint::batch_to_affine`].
+    pub fn to_affine(&self) -> AffinePoint {
         let recip = self.Z.invert();
-        let x = &self.X * &recip;
-        let y = &self.Y * &recip;
+        AffinePoint {
+            x: &self.X * &recip,
+            y: &self.Y * &recip,
+        }
+    }
+
+    /// Convert each of `points` to affine coordinates.
+    ///
+    /// As with [`EdwardsPoint::compress_batch`], the \\(Z\\) coordinates are
+    /// inverted together with Montgomery's trick, so the whole batch costs
+    /// one inversion.
+    #[cfg(feature = "alloc")]
+    pub fn batch_to_affine(points: &[EdwardsPoint]) -> Vec<AffinePoint> {
+        let projective: Vec<ProjectivePoint> =
+            points.iter().map(EdwardsPoint::as_projective).collect();
+        ProjectivePoint::batch_to_affine(&projective)
+    }
+
+    /// Compress this point, given the inverse of its \\(Z\\) coordinate.
+    fn compress_with_z_inverse(&self, recip: &FieldElement) -> CompressedEdwardsY {
+        let x = &self.X * recip;
+        let y = &self.Y * recip;
         let mut s: [u8; 32];
 
         s = y.as_bytes();
@@ -605,6 +663,148 @@ impl EdwardsPoint {
             .expect("Montgomery conversion to Edwards point in Elligator failed")
             .mul_by_cofactor()
     }
+
+    #[cfg(feature = "digest")]
+    /// Hash `msg` to a point of the prime-order subgroup, using the
+    /// `edwards25519_XMD:SHA-512_ELL2_RO_` suite of [RFC 9380] with `D` as
+    /// the hash function and `dst` as the domain separation tag.
+    ///
+    /// The message is expanded to two field elements, each is mapped to
+    /// the curve with Elligator 2, and the sum of the two points has its
+    /// cofactor cleared.  Unlike
+    /// [`nonspec_map_to_curve`](EdwardsPoint::nonspec_map_to_curve), the
+    /// output is indistinguishable from a uniformly random point, and
+    /// matches other implementations of the standard when `D` is SHA-512.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use curve25519_dalek::edwards::EdwardsPoint;
+    /// use sha2::Sha512;
+    ///
+    /// let dst = b"MYAPP-V01-CS01-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+    /// let P = EdwardsPoint::hash_to_curve::<Sha512>(b"message", dst);
+    /// assert!(P.is_torsion_free());
+    /// ```
+    ///
+    /// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+    pub fn hash_to_curve<D>(msg: &[u8], dst: &[u8]) -> EdwardsPoint
+    where
+        D: Digest<OutputSize = U64> + BlockSizeUser + Default,
+    {
+        let [u0, u1] = crate::hash_to_curve::hash_to_field::<D>(msg, dst);
+        let Q0 = crate::hash_to_curve::map_to_curve_elligator2(&u0);
+        let Q1 = crate::hash_to_curve::map_to_curve_elligator2(&u1);
+        (Q0 + Q1).mul_by_cofactor()
+    }
+}
+
+// ------------------------------------------------------------------------
+// Affine points
+// ------------------------------------------------------------------------
+
+/// A point on the Edwards form of Curve25519 in affine coordinates
+/// \\((x, y)\\).
+///
+/// Affine points are the normalized form of [`EdwardsPoint`]s: they
+/// compress without an inversion, and compare equal exactly when their
+/// coordinates do.  Convert many points at once with
+/// [`EdwardsPoint::batch_to_affine`], and back with
+/// [`AffinePoint::to_edwards`] to do arithmetic.
+#[derive(Copy, Clone)]
+pub struct AffinePoint {
+    pub(crate) x: FieldElement,
+    pub(crate) y: FieldElement,
+}
+
+impl AffinePoint {
+    /// Convert this point to extended coordinates.
+    pub fn to_edwards(&self) -> EdwardsPoint {
+        EdwardsPoint {
+            X: self.x,
+            Y: self.y,
+            Z: FieldElement::ONE,
+            T: &self.x * &self.y,
+        }
+    }
+
+    /// Compress this point to `CompressedEdwardsY` format.
+    ///
+    /// Unlike [`EdwardsPoint::compress`], this needs no inversion.
+    pub fn compress(&self) -> CompressedEdwardsY {
+        let mut s = self.y.as_bytes();
+        s[31] ^= self.x.is_negative().unwrap_u8() << 7;
+        CompressedEdwardsY(s)
+    }
+
+    /// The \\(x\\)-coordinate of this point.
+    #[cfg(feature = "hazmat")]
+    pub fn x(&self) -> crate::hazmat::FieldElement {
+        crate::hazmat::FieldElement(self.x)
+    }
+
+    /// The \\(y\\)-coordinate of this point.
+    #[cfg(feature = "hazmat")]
+    pub fn y(&self) -> crate::hazmat::FieldElement {
+        crate::hazmat::FieldElement(self.y)
+    }
+}
+
+impl Identity for AffinePoint {
+    fn identity() -> AffinePoint {
+        AffinePoint {
+            x: FieldElement::ZERO,
+            y: FieldElement::ONE,
+        }
+    }
+}
+
+impl Default for AffinePoint {
+    fn default() -> AffinePoint {
+        AffinePoint::identity()
+    }
+}
+
+impl ConstantTimeEq for AffinePoint {
+    fn ct_eq(&self, other: &AffinePoint) -> Choice {
+        self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y)
+    }
+}
+
+impl PartialEq for AffinePoint {
+    fn eq(&self, other: &AffinePoint) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for AffinePoint {}
+
+impl ConditionallySelectable for AffinePoint {
+    fn conditional_select(a: &AffinePoint, b: &AffinePoint, choice: Choice) -> AffinePoint {
+        AffinePoint {
+            x: FieldElement::conditional_select(&a.x, &b.x, choice),
+            y: FieldElement::conditional_select(&a.y, &b.y, choice),
+        }
+    }
+}
+
+impl Debug for AffinePoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "AffinePoint{{\n\tx: {:?},\n\ty: {:?}\n}}",
+            &self.x, &self.y
+        )
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for AffinePoint {
+    /// Reset this `AffinePoint` to the identity element.
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+        self.y = FieldElement::ONE;
+    }
 }
 
 // ------------------------------------------------------------------------
@@ -784,6 +984,54 @@ impl EdwardsPoint {
         };
         Self::mul_base(&s)
     }
+
+    /// Multiply this point by a secret scalar which is never held in memory
+    /// all at once, such as one kept in a secure element.
+    ///
+    /// The scalar's 32 little-endian bytes are requested from `chunks`, `N`
+    /// at a time, from the most significant down: each call is given the
+    /// range of byte positions to write into its `N`-byte buffer, and the
+    /// buffer is wiped as soon as its bytes have been consumed.  At most one
+    /// chunk, and one 4-bit digit of the one before it, is in memory at any
+    /// time.  The bytes are taken as an integer below \\(2^{256}\\), which
+    /// need not be reduced modulo \\(\ell\\), so that e.g. a clamped X25519
+    /// scalar is multiplied as it is.
+    ///
+    /// The multiplication is constant-time in the scalar, like `self *
+    /// scalar`, at the cost of one extra window of four doublings and an
+    /// addition.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the product;
+    /// - `Err(err)` with the first error returned by `chunks`, after which
+    ///   no more chunks are requested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not divide 32.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::{EdwardsPoint, Scalar};
+    /// use core::convert::Infallible;
+    ///
+    /// let secret = Scalar::from(1234567u64);
+    /// let P = EdwardsPoint::mul_base(&Scalar::from(42u64));
+    /// let product = P.mul_streaming(|range, chunk: &mut [u8; 8]| {
+    ///     // A secure element would be asked for these bytes here
+    ///     chunk.copy_from_slice(&secret.as_bytes()[range]);
+    ///     Ok::<(), Infallible>(())
+    /// });
+    /// assert_eq!(product, Ok(P * secret));
+    /// ```
+    pub fn mul_streaming<const N: usize, E, F>(&self, chunks: F) -> Result<EdwardsPoint, E>
+    where
+        F: FnMut(core::ops::Range<usize>, &mut [u8; N]) -> Result<(), E>,
+    {
+        crate::backend::serial::scalar_mul::variable_base::mul_streaming(self, chunks)
+    }
 }
 
 // ------------------------------------------------------------------------
@@ -852,6 +1100,11 @@ impl VartimeMultiscalarMul for EdwardsPoint {
         // Use this as the hint to decide which algorithm to use.
         let size = s_lo;
 
+        #[cfg(feature = "parallel")]
+        if size >= 2 * PARALLEL_MSM_MIN_CHUNK_LEN && rayon::current_num_threads() > 1 {
+            return parallel_optional_multiscalar_mul(scalars, points);
+        }
+
         if size < 190 {
             crate::backend::straus_optional_multiscalar_mul(scalars, points)
         } else {
@@ -860,6 +1113,53 @@ impl VartimeMultiscalarMul for EdwardsPoint {
     }
 }
 
+/// The fewest terms each thread takes when the `parallel` feature splits a
+/// multiscalar multiplication across threads.
+///
+/// Pippenger's method gets cheaper per term as the input grows, so chunks
+/// much smaller than this lose more to the extra bucket sums than they
+/// gain from the extra threads.
+#[cfg(feature = "parallel")]
+const PARALLEL_MSM_MIN_CHUNK_LEN: usize = 1024;
+
+/// Compute a multiscalar multiplication by splitting the terms into one
+/// chunk per thread, running Pippenger's method on each chunk on the
+/// `rayon` thread pool, and summing the partial results.
+#[cfg(feature = "parallel")]
+fn parallel_optional_multiscalar_mul<I, J>(scalars: I, points: J) -> Option<EdwardsPoint>
+where
+    I: Iterator,
+    I::Item: Borrow<Scalar>,
+    J: Iterator<Item = Option<EdwardsPoint>>,
+{
+    use rayon::prelude::*;
+
+    let scalars: Vec<Scalar> = scalars.map(|s| *s.borrow()).collect();
+    let points: Vec<EdwardsPoint> = points.collect::<Option<_>>()?;
+
+    let threads = rayon::current_num_threads();
+    let chunk_len = core::cmp::max(
+        (scalars.len() + threads - 1) / threads,
+        PARALLEL_MSM_MIN_CHUNK_LEN,
+    );
+
+    scalars
+        .par_chunks(chunk_len)
+        .zip(points.par_chunks(chunk_len))
+        .map(|(scalars, points)| {
+            crate::backend::pippenger_optional_multiscalar_mul(
+                scalars,
+                points.iter().map(|P| Some(*P)),
+            )
+        })
+        .try_reduce(EdwardsPoint::identity, |a, b| Some(a + b))
+}
+
+/// The number of terms the streaming multiscalar multiplications, e.g.
+/// [`EdwardsPoint::vartime_multiscalar_mul_compressed`], hold at once.
+#[cfg(feature = "alloc")]
+pub const MSM_STREAM_CHUNK_LEN: usize = 1024;
+
 /// Precomputation for variable-time multiscalar multiplication with `EdwardsPoint`s.
 // This wraps the inner implementation in a facade type so that we can
 // decouple stability of the inner type from the stability of the
@@ -905,6 +1205,417 @@ impl VartimePrecomputedMultiscalarMul for VartimeEdwardsPrecomputation {
     }
 }
 
+impl EdwardsPoint {
+    /// Compute \\(\sum\_i s\_i P\_i\\) in constant time, for a number of
+    /// terms `N` fixed at compile time.
+    ///
+    /// This is [`EdwardsPoint::multiscalar_mul`] without the `alloc`
+    /// requirement: the precomputed tables live in arrays of length `N` on
+    /// the stack, about 1.3KB per term, so it suits the small
+    /// multiplications of `no_std` targets.  It always uses the serial
+    /// backend.
+    pub fn multiscalar_mul_fixed<const N: usize>(
+        scalars: &[Scalar; N],
+        points: &[EdwardsPoint; N],
+    ) -> EdwardsPoint {
+        crate::backend::serial::scalar_mul::fixed_straus::FixedStraus::multiscalar_mul(
+            scalars, points,
+        )
+    }
+
+    /// Compute \\(\sum\_i s\_i P\_i\\) in variable time, for a number of
+    /// terms `N` fixed at compile time.
+    ///
+    /// This is [`EdwardsPoint::vartime_multiscalar_mul`] without the
+    /// `alloc` requirement, with the same stack use and backend as
+    /// [`EdwardsPoint::multiscalar_mul_fixed`].
+    pub fn vartime_multiscalar_mul_fixed<const N: usize>(
+        scalars: &[Scalar; N],
+        points: &[EdwardsPoint; N],
+    ) -> EdwardsPoint {
+        crate::backend::serial::scalar_mul::fixed_straus::FixedStraus::vartime_multiscalar_mul(
+            scalars, points,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EdwardsPoint {
+    /// Compute the running sums \\(\sum\_{i < k} s\_i P\_i\\) of a
+    /// multiscalar multiplication, one for each \\(k = 1, \ldots, n\\).
+    ///
+    /// The last entry equals [`EdwardsPoint::multiscalar_mul`] over the
+    /// same inputs.  Each prefix is normalized to \\(Z = 1\\) with a single
+    /// batched inversion at the end, so that later compressions or
+    /// comparisons of the prefixes are cheap.
+    ///
+    /// All \\(n\\) prefixes are held at once, costing one `EdwardsPoint` (160
+    /// bytes with the 64-bit backends) per term, plus one field element per
+    /// term during the batched inversion.  Use
+    /// [`EdwardsPoint::multiscalar_mul`] when only the total is needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scalars` and `points` have different lengths.
+    pub fn multiscalar_mul_prefix_sums<I, J>(scalars: I, points: J) -> Vec<EdwardsPoint>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+        J: IntoIterator,
+        J::Item: Borrow<EdwardsPoint>,
+    {
+        let scalars: Vec<Scalar> = scalars.into_iter().map(|s| *s.borrow()).collect();
+        let points: Vec<EdwardsPoint> = points.into_iter().map(|P| *P.borrow()).collect();
+        assert_eq!(scalars.len(), points.len());
+
+        let mut acc = EdwardsPoint::identity();
+        let prefixes: Vec<EdwardsPoint> = scalars
+            .iter()
+            .zip(points.iter())
+            .map(|(s, P)| {
+                acc += s * P;
+                acc
+            })
+            .collect();
+
+        EdwardsPoint::batch_to_affine(&prefixes)
+            .iter()
+            .map(AffinePoint::to_edwards)
+            .collect()
+    }
+
+    /// Like [`EdwardsPoint::vartime_multiscalar_mul`], but reporting
+    /// mismatched inputs as an error instead of panicking.
+    ///
+    /// An empty multiscalar multiplication is the identity.  This suits
+    /// callers who assemble `scalars` and `points` dynamically and would
+    /// rather not check their lengths up front.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// use curve25519_dalek::constants::ED25519_BASEPOINT_POINT as B;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    /// use curve25519_dalek::errors::MultiscalarError;
+    /// use curve25519_dalek::scalar::Scalar;
+    /// use curve25519_dalek::traits::Identity;
+    ///
+    /// let two = Scalar::from(2u64);
+    /// let empty = EdwardsPoint::try_vartime_multiscalar_mul(&[], &[]);
+    /// assert_eq!(empty, Ok(EdwardsPoint::identity()));
+    /// assert_eq!(EdwardsPoint::try_vartime_multiscalar_mul(&[two], &[B]), Ok(B + B));
+    /// assert_eq!(
+    ///     EdwardsPoint::try_vartime_multiscalar_mul(&[two], &[]),
+    ///     Err(MultiscalarError::LengthMismatch { scalars: 1, points: 0 })
+    /// );
+    /// # }
+    /// ```
+    pub fn try_vartime_multiscalar_mul(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+    ) -> Result<EdwardsPoint, MultiscalarError> {
+        if scalars.len() != points.len() {
+            return Err(MultiscalarError::LengthMismatch {
+                scalars: scalars.len(),
+                points: points.len(),
+            });
+        }
+        if scalars.is_empty() {
+            return Ok(EdwardsPoint::identity());
+        }
+        Ok(EdwardsPoint::vartime_multiscalar_mul(scalars, points))
+    }
+
+    /// The Pippenger window, in bits, that
+    /// [`EdwardsPoint::vartime_multiscalar_mul`] uses for `n` terms.
+    ///
+    /// The window is chosen by a cost model of the algorithm, calibrated
+    /// by measurement, and grows from 4 to 8 bits with `n`.  Inputs of
+    /// fewer than 190 terms are multiplied with Straus's method instead,
+    /// which has no such window.
+    pub fn pippenger_window(n: usize) -> usize {
+        crate::backend::serial::scalar_mul::pippenger::Pippenger::digit_width(n)
+    }
+
+    /// Like [`EdwardsPoint::try_vartime_multiscalar_mul`], but always using
+    /// Pippenger's method, with a window of `window` bits rather than the
+    /// one [`EdwardsPoint::pippenger_window`] picks.
+    ///
+    /// This is for benchmarking and tuning: a fixed window makes timings
+    /// reproducible across releases whose heuristics differ.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the sum, which is the identity for empty inputs;
+    /// - `Err(MultiscalarError::LengthMismatch)` if there is not exactly one
+    ///   point per scalar;
+    /// - `Err(MultiscalarError::WindowOutOfRange)` unless `window` is
+    ///   between 4 and 8.
+    pub fn vartime_multiscalar_mul_with_window(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+        window: usize,
+    ) -> Result<EdwardsPoint, MultiscalarError> {
+        use crate::backend::serial::scalar_mul::pippenger::Pippenger;
+
+        if !(Pippenger::MIN_DIGIT_WIDTH..=Pippenger::MAX_DIGIT_WIDTH).contains(&window) {
+            return Err(MultiscalarError::WindowOutOfRange { window });
+        }
+        if scalars.len() != points.len() {
+            return Err(MultiscalarError::LengthMismatch {
+                scalars: scalars.len(),
+                points: points.len(),
+            });
+        }
+        if scalars.is_empty() {
+            return Ok(EdwardsPoint::identity());
+        }
+        Ok(
+            crate::backend::pippenger_optional_multiscalar_mul_with_width(
+                window,
+                scalars,
+                points.iter().map(|P| Some(*P)),
+            )
+            .expect("all points are present"),
+        )
+    }
+
+    /// Compute \\(\sum\_i s\_i P\_i\\) in variable time, reading the terms
+    /// as `(scalar, compressed point)` pairs from an iterator.
+    ///
+    /// The terms are decompressed and multiplied [`MSM_STREAM_CHUNK_LEN`]
+    /// at a time, and only the running sum is kept between chunks, so the
+    /// memory used is bounded independently of the number of terms.  This
+    /// suits inputs too large to hold as `EdwardsPoint`s, e.g. streamed from
+    /// disk.  Each chunk is a full multiscalar multiplication, so a long
+    /// input costs somewhat more than one multiplication over all of it.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the sum, which is the identity for an empty input;
+    /// - `Err(MultiscalarError::InvalidPoint { index })` for the first term
+    ///   whose point does not decompress.  No terms after it are read.
+    pub fn vartime_multiscalar_mul_compressed<I>(terms: I) -> Result<EdwardsPoint, MultiscalarError>
+    where
+        I: IntoIterator<Item = (Scalar, CompressedEdwardsY)>,
+    {
+        EdwardsPoint::vartime_multiscalar_mul_stream(
+            terms
+                .into_iter()
+                .map(|(scalar, compressed)| (scalar, compressed.decompress())),
+        )
+    }
+
+    /// The streaming loop behind
+    /// [`EdwardsPoint::vartime_multiscalar_mul_compressed`], over terms
+    /// whose points are decoded lazily, as the iterator is advanced.
+    pub(crate) fn vartime_multiscalar_mul_stream<I>(
+        mut terms: I,
+    ) -> Result<EdwardsPoint, MultiscalarError>
+    where
+        I: Iterator<Item = (Scalar, Option<EdwardsPoint>)>,
+    {
+        let mut scalars = Vec::with_capacity(MSM_STREAM_CHUNK_LEN);
+        let mut points = Vec::with_capacity(MSM_STREAM_CHUNK_LEN);
+        let mut sum = EdwardsPoint::identity();
+        let mut index = 0;
+
+        loop {
+            scalars.clear();
+            points.clear();
+            for (scalar, point) in terms.by_ref().take(MSM_STREAM_CHUNK_LEN) {
+                let point = point.ok_or(MultiscalarError::InvalidPoint { index })?;
+                scalars.push(scalar);
+                points.push(point);
+                index += 1;
+            }
+            if !scalars.is_empty() {
+                sum += EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+            }
+            if scalars.len() < MSM_STREAM_CHUNK_LEN {
+                return Ok(sum);
+            }
+        }
+    }
+
+    /// Compute \\(\sum\_i s\_i P\_i\\) in variable time, a bounded number
+    /// of iterations at a time, for environments with a hard per-call
+    /// compute budget.
+    ///
+    /// This takes and returns the same types as [`resumable::step_mul`]:
+    /// start with [`StepMulState::Fresh`], and while the result is
+    /// [`StepResult::InProgress`], pass its checkpoint back as
+    /// [`StepMulState::Resumed`].  The serial Straus loop is used on every
+    /// backend, so the computation is split into chunks everywhere.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the outcome of the call;
+    /// - `Err(MultiscalarError::LengthMismatch)` if `scalars` and `points`
+    ///   have different lengths;
+    /// - `Err(MultiscalarError::Step)`, with the error
+    ///   [`resumable::step_mul`] reports, if a resumed checkpoint cannot be
+    ///   resumed from.
+    ///
+    /// [`resumable::step_mul`]: crate::resumable::step_mul
+    pub fn step_vartime_multiscalar_mul(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+        state: StepMulState,
+    ) -> Result<StepResult, MultiscalarError> {
+        let (index, r) = state.to_start().map_err(MultiscalarError::Step)?;
+        let mut saved = None;
+        let (point, status) =
+            crate::backend::serial::scalar_mul::straus::Straus::step_multiscalar_mul(
+                scalars,
+                points,
+                |i, limbs| saved = Some(Checkpoint::new(i, limbs)),
+                index,
+                r,
+            )?;
+        match (status, saved) {
+            (1, Some(checkpoint)) => Ok(StepResult::InProgress(checkpoint)),
+            _ => Ok(StepResult::Done(point)),
+        }
+    }
+
+    /// Check many equations \\(a\_i A\_i + b\_i B = C\_i\\) at once, where
+    /// \\(B\\) is the Ed25519 basepoint, in variable time.
+    ///
+    /// Each entry of `equations` is \\((a\_i, A\_i, b\_i, C\_i)\\).  The
+    /// equations are checked together with a random linear combination:
+    /// for random 128-bit \\(z\_i\\) drawn from `rng`, a single multiscalar
+    /// multiplication checks
+    /// $$
+    /// \sum\_i z\_i a\_i A\_i + \Big(\sum\_i z\_i b\_i\Big) B - \sum\_i z\_i C\_i = 0.
+    /// $$
+    /// If that fails, the equations are split in halves and each half is
+    /// checked the same way, down to the single equations which fail, so
+    /// that \\(k\\) failures among \\(n\\) equations cost about
+    /// \\(2k \log\_2 n\\) further multiscalar multiplications.  With the
+    /// `rayon` feature, the halves are checked in parallel.
+    ///
+    /// A single equation is always checked exactly.  For larger sets, an
+    /// equation whose sides differ by a point of small order can cancel
+    /// against another such equation, or against its own \\(z\_i\\), and be
+    /// missed; callers who need to detect those should multiply both sides
+    /// by the cofactor, or check such equations one at a time.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(())` if every equation holds;
+    /// - `Err(indices)` with the indices of the failing equations, in
+    ///   increasing order, otherwise.
+    #[cfg(feature = "rand_core")]
+    pub fn vartime_batch_verify_double_scalar_mul_basepoint<R>(
+        equations: &[(Scalar, EdwardsPoint, Scalar, EdwardsPoint)],
+        rng: &mut R,
+    ) -> Result<(), Vec<usize>>
+    where
+        R: RngCore + CryptoRng + ?Sized,
+    {
+        let weights: Vec<Scalar> = equations
+            .iter()
+            .map(|_| {
+                let mut bytes = [0u8; 32];
+                rng.fill_bytes(&mut bytes[..16]);
+                Scalar::from_bytes_mod_order(bytes)
+            })
+            .collect();
+
+        let failures = batch_verify_failures(equations, &weights, 0);
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+/// The indices, offset by `offset`, of the equations which fail the random
+/// linear combination check of
+/// [`EdwardsPoint::vartime_batch_verify_double_scalar_mul_basepoint`].
+#[cfg(all(feature = "alloc", feature = "rand_core"))]
+fn batch_verify_failures(
+    equations: &[(Scalar, EdwardsPoint, Scalar, EdwardsPoint)],
+    weights: &[Scalar],
+    offset: usize,
+) -> Vec<usize> {
+    match equations {
+        [] => return Vec::new(),
+        [(a, A, b, C)] => {
+            return if EdwardsPoint::vartime_double_scalar_mul_basepoint(a, A, b) == *C {
+                Vec::new()
+            } else {
+                vec![offset]
+            };
+        }
+        _ => {}
+    }
+
+    let mut b_sum = Scalar::ZERO;
+    let mut scalars = Vec::with_capacity(2 * equations.len() + 1);
+    let mut points = Vec::with_capacity(2 * equations.len() + 1);
+    for ((a, A, b, C), z) in equations.iter().zip(weights) {
+        scalars.push(z * a);
+        points.push(*A);
+        scalars.push(-z);
+        points.push(*C);
+        b_sum += z * b;
+    }
+    scalars.push(b_sum);
+    points.push(constants::ED25519_BASEPOINT_POINT);
+    if EdwardsPoint::vartime_multiscalar_mul(&scalars, &points).is_identity() {
+        return Vec::new();
+    }
+
+    let mid = equations.len() / 2;
+    let (left, right) = equations.split_at(mid);
+    let (left_weights, right_weights) = weights.split_at(mid);
+    #[cfg(feature = "rayon")]
+    let (mut failures, right_failures) = rayon::join(
+        || batch_verify_failures(left, left_weights, offset),
+        || batch_verify_failures(right, right_weights, offset + mid),
+    );
+    #[cfg(not(feature = "rayon"))]
+    let (mut failures, right_failures) = (
+        batch_verify_failures(left, left_weights, offset),
+        batch_verify_failures(right, right_weights, offset + mid),
+    );
+    failures.extend(right_failures);
+    failures
+}
+
+/// A representation of a point which can be passed as \\(A\\) to
+/// [`EdwardsPoint::vartime_double_scalar_mul_basepoint_from`].
+///
+/// The lookup table for \\(A\\) is built from extended coordinates, so each
+/// representation converts to them in the cheapest way available:
+///
+/// * an [`EdwardsPoint`] is already extended and is used as is;
+/// * a [`RistrettoPoint`](crate::ristretto::RistrettoPoint) wraps an
+///   extended representative, which is used as is;
+/// * an [`AffinePoint`] \\((x, y)\\) costs one multiplication, for
+///   \\(T = xy\\), and no inversion.
+pub trait DoubleBaseInput {
+    /// Return this point in extended coordinates.
+    fn to_extended(&self) -> EdwardsPoint;
+}
+
+impl DoubleBaseInput for EdwardsPoint {
+    fn to_extended(&self) -> EdwardsPoint {
+        *self
+    }
+}
+
+impl DoubleBaseInput for AffinePoint {
+    fn to_extended(&self) -> EdwardsPoint {
+        self.to_edwards()
+    }
+}
+
 impl EdwardsPoint {
     /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
     pub fn vartime_double_scalar_mul_basepoint(
@@ -915,38 +1626,469 @@ impl EdwardsPoint {
         crate::backend::vartime_double_base_mul(a, A, b)
     }
 
-    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
-    pub fn step_vartime_double_scalar_mul_basepoint<F: Fn(usize, [u64; 15]) -> ()>(
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint, with the digits of \\(a\\) in a width-`width` NAF.
+    ///
+    /// [`EdwardsPoint::vartime_double_scalar_mul_basepoint`] uses width 5.
+    /// Wider NAFs make fewer additions in the main loop, but need a table
+    /// of \\(2\^{\mathrm{width} - 2}\\) multiples of \\(A\\), which costs as
+    /// many additions to build and 160 bytes of stack per entry.  Width 4
+    /// runs about as fast as width 5 with half the table, which suits
+    /// memory-constrained targets; wider NAFs are slower for a single
+    /// multiplication, and are here for tuning.
+    ///
+    /// This only covers the one-shot multiplication: the step-wise
+    /// multiplications always use width 5, since their checkpoints depend
+    /// on the digits.
+    ///
+    /// # Return
+    ///
+    /// `None` unless `width` is between 4 and 8.
+    pub fn vartime_double_scalar_mul_basepoint_with_naf_width(
         a: &Scalar,
         A: &EdwardsPoint,
         b: &Scalar,
-        progress_saving_handle: F,
-        i: usize,
-        projective_point: [u64; 15],
-    ) -> (EdwardsPoint, u8) {
-        crate::backend::step_vartime_double_base_mul(
-            a,
-            A,
-            b,
-            progress_saving_handle,
-            i,
-            projective_point,
-        )
+        width: usize,
+    ) -> Option<EdwardsPoint> {
+        crate::backend::vartime_double_base_mul_with_naf_width(a, A, b, width)
     }
-}
 
-#[cfg(feature = "precomputed-tables")]
-macro_rules! impl_basepoint_table {
-    (Name = $name:ident, LookupTable = $table:ident, Point = $point:ty, Radix = $radix:expr, Additions = $adds:expr) => {
-        /// A precomputed table of multiples of a basepoint, for accelerating
-        /// fixed-base scalar multiplication.  One table, for the Ed25519
-        /// basepoint, is provided in the [`constants`] module.
-        ///
-        /// The basepoint tables are reasonably large, so they should probably be boxed.
-        ///
-        /// The sizes for the tables and the number of additions required for one scalar
-        /// multiplication are as follows:
-        ///
+    /// Compute \\(aA + bB + cC\\) in variable time, where \\(C\\) is the
+    /// Ed25519 basepoint.
+    ///
+    /// This interleaves the three scalars in one double-and-add loop, like
+    /// [`EdwardsPoint::vartime_double_scalar_mul_basepoint`], and is cheaper
+    /// than the general [`VartimeMultiscalarMul`] machinery for three terms.
+    pub fn vartime_triple_scalar_mul_basepoint(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        B: &EdwardsPoint,
+        c: &Scalar,
+    ) -> EdwardsPoint {
+        crate::backend::vartime_triple_base_mul(a, A, b, B, c)
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint and `table_A` holds the precomputed multiples of \\(A\\).
+    ///
+    /// This always uses the serial double-base multiplication, even when a
+    /// SIMD backend is selected.
+    pub fn vartime_double_scalar_mul_basepoint_precomputed(
+        a: &Scalar,
+        table_A: &VartimeNafTable,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        crate::backend::serial::scalar_mul::vartime_double_base::mul_with_precomputed(a, table_A, b)
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint, accepting \\(A\\) in any representation implementing
+    /// [`DoubleBaseInput`].
+    ///
+    /// See [`DoubleBaseInput`] for the accepted forms and what each costs to
+    /// convert; an `A` that is already an `EdwardsPoint` costs nothing extra.
+    pub fn vartime_double_scalar_mul_basepoint_from<P: DoubleBaseInput + ?Sized>(
+        a: &Scalar,
+        A: &P,
+        b: &Scalar,
+    ) -> EdwardsPoint {
+        crate::backend::vartime_double_base_mul(a, &A.to_extended(), b)
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519
+    /// basepoint, rejecting an identity \\(A\\).
+    ///
+    /// The product is well defined for \\(A = 0\\) (it is \\(bB\\), which
+    /// [`EdwardsPoint::vartime_double_scalar_mul_basepoint`] happily returns),
+    /// so this is a protocol-policy guard rather than a mathematical
+    /// requirement: many protocols treat an identity public key as invalid,
+    /// and this saves their callers a separate check.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(aA + bB)` if `A` is not the identity;
+    /// - `Err(InputError::IdentityPoint)` if it is.
+    pub fn try_vartime_double_scalar_mul_basepoint(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+    ) -> Result<EdwardsPoint, InputError> {
+        if A.is_identity() {
+            return Err(InputError::IdentityPoint);
+        }
+        Ok(crate::backend::vartime_double_base_mul(a, A, b))
+    }
+
+    /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the Ed25519 basepoint.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the result and status 2 once the last digit is
+    ///   processed, or with the identity and status 1 after passing a
+    ///   checkpoint to `progress_saving_handle`;
+    /// - `Err(StepError::IndexOutOfRange)` if `i` is neither below 256 nor
+    ///   the fresh-start value 300.
+    ///
+    /// The checkpointed accumulator is used as given; see
+    /// [`EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint`] to
+    /// validate it first.
+    ///
+    /// [`resumable::step_mul`] offers the same loop with typed states in
+    /// place of the index 300 and the status byte.
+    ///
+    /// [`resumable::step_mul`]: crate::resumable::step_mul
+    pub fn step_vartime_double_scalar_mul_basepoint<F: FnMut(usize, [u64; 15])>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        progress_saving_handle: F,
+        i: usize,
+        projective_point: [u64; 15],
+    ) -> Result<(EdwardsPoint, u8), StepError> {
+        crate::backend::step_vartime_double_base_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            projective_point,
+        )
+    }
+
+    /// Like [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`], but
+    /// also checking the checkpointed accumulator first.
+    ///
+    /// A checkpoint read back from storage may be corrupted.  The step-wise
+    /// loop rejects an index it cannot resume from, but would carry a
+    /// corrupted accumulator into a wrong result; this returns an error
+    /// instead.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the same `(point, status)` pair as the unchecked method;
+    /// - `Err(StepError::IndexOutOfRange)` if `i` is neither below 256 nor
+    ///   the fresh-start value 300;
+    /// - `Err(StepError::CheckpointLimbOutOfRange)`,
+    ///   `Err(StepError::CheckpointZeroZ)`, or
+    ///   `Err(StepError::CheckpointNotOnCurve)` if `i` is not 300 and
+    ///   `projective_point` has a limb too large for a reduced field
+    ///   element, a zero \\(Z\\) coordinate, or is not on the curve.
+    pub fn try_step_vartime_double_scalar_mul_basepoint<F: FnMut(usize, [u64; 15])>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        progress_saving_handle: F,
+        i: usize,
+        projective_point: [u64; 15],
+    ) -> Result<(EdwardsPoint, u8), StepError> {
+        use crate::backend::serial::scalar_mul::vartime_double_base;
+
+        vartime_double_base::check_start_index(i)?;
+        if i != 300 {
+            vartime_double_base::try_deserialize_r_from_backup(projective_point)?;
+        }
+        crate::backend::step_vartime_double_base_mul(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            projective_point,
+        )
+    }
+
+    /// Like [`EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint`],
+    /// but performing at most `budget` loop iterations per call instead of
+    /// [`DEFAULT_STEP_BUDGET`].
+    ///
+    /// This always runs the serial step-wise loop, whichever backend is
+    /// selected, so that each call does the same bounded amount of work.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the same `(point, status)` pair as the unchecked method;
+    /// - `Err(StepError::IndexOutOfRange)` if `i` is neither below 256 nor
+    ///   the fresh-start value 300;
+    /// - `Err(StepError::BudgetOutOfRange)` if `budget` is zero or more than
+    ///   [`MAX_STEP_BUDGET`];
+    /// - one of the checkpoint errors of the method above if `i` is not 300
+    ///   and `projective_point` is not a valid accumulator.
+    ///
+    /// [`DEFAULT_STEP_BUDGET`]: crate::resumable::DEFAULT_STEP_BUDGET
+    /// [`MAX_STEP_BUDGET`]: crate::resumable::MAX_STEP_BUDGET
+    pub fn try_step_vartime_double_scalar_mul_basepoint_with_budget<F: FnMut(usize, [u64; 15])>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        progress_saving_handle: F,
+        i: usize,
+        projective_point: [u64; 15],
+        budget: usize,
+    ) -> Result<(EdwardsPoint, u8), StepError> {
+        use crate::backend::serial::scalar_mul::vartime_double_base;
+
+        vartime_double_base::check_start_index(i)?;
+        vartime_double_base::check_step_budget(budget)?;
+        if i != 300 {
+            vartime_double_base::try_deserialize_r_from_backup(projective_point)?;
+        }
+        crate::backend::step_vartime_double_base_mul_with_budget(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            i,
+            projective_point,
+            budget,
+        )
+    }
+
+    /// Resume [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`] from
+    /// a checkpoint whose accumulator was stored as a `CompressedEdwardsY`
+    /// (see [`Checkpoint::to_compressed_bytes`]), rather than as raw limbs.
+    ///
+    /// The accumulator is decompressed once, before any iterations run, so
+    /// resuming this way costs one extra square root over the limb-based
+    /// path.  Progress is still reported to `progress_saving_handle` as raw
+    /// limbs.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the same `(point, status)` pair as the limb-based method;
+    /// - `Err(StepError::BasepointMismatch)` if `checkpoint` was written by
+    ///   a build with a different basepoint;
+    /// - `Err(StepError::IndexOutOfRange)` if its index is neither below 256
+    ///   nor the fresh-start value 300;
+    /// - `Err(StepError::CheckpointDecompression)` if its accumulator is
+    ///   not the encoding of a curve point.
+    ///
+    /// [`Checkpoint::to_compressed_bytes`]: crate::resumable::Checkpoint::to_compressed_bytes
+    pub fn step_vartime_double_scalar_mul_basepoint_compressed<F: FnMut(usize, [u64; 15])>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        progress_saving_handle: F,
+        checkpoint: &CompressedCheckpoint,
+    ) -> Result<(EdwardsPoint, u8), StepError> {
+        checkpoint.check_basepoint()?;
+        crate::backend::serial::scalar_mul::vartime_double_base::check_start_index(
+            checkpoint.index(),
+        )?;
+        let r = checkpoint
+            .point()
+            .decompress()
+            .ok_or(StepError::CheckpointDecompression)?;
+
+        crate::backend::step_vartime_double_base_mul_from_point(
+            a,
+            A,
+            b,
+            progress_saving_handle,
+            checkpoint.index(),
+            r.as_projective(),
+        )
+    }
+
+    /// Process up to `budget` bits of the scalar multiplication \\(aA\\),
+    /// one bit at a time, most significant bit first.
+    ///
+    /// This is the slowest resumable multiplication, with no windowing at
+    /// all: each bit costs one doubling and one addition.  In exchange it
+    /// has the finest checkpoint granularity and the simplest state, just
+    /// the accumulator `acc` and the count `bits_remaining` of bits still to
+    /// process, so it suits budgets too tight for a chunk of the windowed
+    /// [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`].
+    ///
+    /// Start with `acc` the identity and `bits_remaining` 256, then pass the
+    /// returned pair back in until `bits_remaining` reaches zero, at which
+    /// point `acc` is \\(aA\\).  Each bit is processed in constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits_remaining` is greater than 256.
+    pub fn mul_bits(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        acc: &EdwardsPoint,
+        bits_remaining: usize,
+        budget: usize,
+    ) -> (EdwardsPoint, usize) {
+        assert!(bits_remaining <= 256, "a scalar has only 256 bits");
+
+        let mut acc = *acc;
+        let mut i = bits_remaining;
+        while i > 0 && bits_remaining - i < budget {
+            i -= 1;
+            let bit = Choice::from((a.bytes[i >> 3] >> (i & 7)) & 1);
+            acc = acc.double();
+            let sum = acc + A;
+            acc.conditional_assign(&sum, bit);
+        }
+        (acc, i)
+    }
+}
+
+/// Verifies many equations \\(R = sB - hA\\) which all share the same
+/// point \\(A\\), such as the signatures made by a single Ed25519 key.
+///
+/// [`EdwardsPoint::vartime_double_scalar_mul_basepoint`] builds a table of
+/// the odd multiples \\(A, 3A, \ldots, 15A\\) on every call, at a cost of one
+/// doubling and seven additions.  `BatchVerifierSameKey` builds that table
+/// once in [`BatchVerifierSameKey::new`], so checking \\(N\\) signatures by one
+/// key saves \\(N - 1\\) table constructions.
+///
+/// This always uses the serial double-base multiplication, even when a
+/// SIMD backend is selected.
+#[derive(Copy, Clone, Debug)]
+pub struct BatchVerifierSameKey {
+    table_A: VartimeNafTable,
+}
+
+impl BatchVerifierSameKey {
+    /// Build the table of multiples of `A` used by every verification.
+    pub fn new(A: &EdwardsPoint) -> BatchVerifierSameKey {
+        BatchVerifierSameKey {
+            table_A: VartimeNafTable::new(A),
+        }
+    }
+
+    /// The point \\(A\\) this verifier was built for.
+    pub fn point(&self) -> &EdwardsPoint {
+        self.table_A.point()
+    }
+
+    /// Check, in variable time, whether \\(sB - hA\\) compresses to
+    /// `expected_R`, where \\(B\\) is the Ed25519 basepoint.
+    pub fn verify(&self, s: &Scalar, h: &Scalar, expected_R: &CompressedEdwardsY) -> bool {
+        let R =
+            EdwardsPoint::vartime_double_scalar_mul_basepoint_precomputed(&-h, &self.table_A, s);
+        R.compress() == *expected_R
+    }
+}
+
+/// The odd multiples \\(A, 3A, \ldots, 15A\\) of a point \\(A\\), as used by
+/// the variable-time double-base multiplication.
+///
+/// [`EdwardsPoint::vartime_double_scalar_mul_basepoint`] rebuilds this table
+/// on every call, at a cost of one doubling and seven additions.  Callers
+/// multiplying the same \\(A\\) many times can build a `VartimeNafTable`
+/// once and pass it to
+/// [`EdwardsPoint::vartime_double_scalar_mul_basepoint_precomputed`]
+/// instead.
+#[derive(Copy, Clone, Debug)]
+pub struct VartimeNafTable {
+    A: EdwardsPoint,
+    table: NafLookupTable5<ProjectiveNielsPoint>,
+}
+
+impl VartimeNafTable {
+    /// Build the table of odd multiples of `A`.
+    pub fn new(A: &EdwardsPoint) -> VartimeNafTable {
+        VartimeNafTable {
+            A: *A,
+            table: NafLookupTable5::<ProjectiveNielsPoint>::from(A),
+        }
+    }
+
+    /// The point \\(A\\) this table was built for.
+    pub fn point(&self) -> &EdwardsPoint {
+        &self.A
+    }
+
+    pub(crate) fn lookup_table(&self) -> &NafLookupTable5<ProjectiveNielsPoint> {
+        &self.table
+    }
+
+    /// The length of the encoding produced by [`VartimeNafTable::to_bytes`].
+    pub const BYTE_LEN: usize = 8 * ProjectiveNielsPoint::BYTE_LEN;
+
+    /// Encode this table as the canonical encodings of the coordinates
+    /// \\((Y+X, Y-X, Z, 2dXY)\\) of \\(A, 3A, \ldots, 15A\\).
+    pub fn to_bytes(&self) -> [u8; 1024] {
+        let mut bytes = [0u8; Self::BYTE_LEN];
+        for (chunk, entry) in bytes
+            .chunks_exact_mut(ProjectiveNielsPoint::BYTE_LEN)
+            .zip(self.table.0.iter())
+        {
+            chunk.copy_from_slice(&entry.to_bytes());
+        }
+        bytes
+    }
+
+    /// Decode a table encoded by [`VartimeNafTable::to_bytes`].
+    ///
+    /// Every coordinate must be canonical and every entry a point on the
+    /// curve, and the entries must be the odd multiples of the first one.
+    /// The check costs about twice as much as building the table from
+    /// \\(A\\), so this is worthwhile when \\(A\\) itself is not at hand.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VartimeNafTable, TableError> {
+        if bytes.len() != Self::BYTE_LEN {
+            return Err(TableError::Length {
+                expected: Self::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut Ai = [ProjectiveNielsPoint::identity(); 8];
+        let chunks = bytes.chunks_exact(ProjectiveNielsPoint::BYTE_LEN);
+        for (index, (entry, chunk)) in Ai.iter_mut().zip(chunks).enumerate() {
+            let mut encoding = [0u8; 128];
+            encoding.copy_from_slice(chunk);
+            *entry = ProjectiveNielsPoint::from_bytes(&encoding)
+                .ok_or(TableError::InvalidEntry { index })?;
+        }
+
+        let table = NafLookupTable5::from_odd_multiples(Ai).ok_or(TableError::Inconsistent)?;
+        Ok(VartimeNafTable {
+            A: (&EdwardsPoint::identity() + &Ai[0]).as_extended(),
+            table,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for VartimeNafTable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_encoding::serialize_chunks(serializer, 1, [self.to_bytes()])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for VartimeNafTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_encoding::deserialize_with::<_, _, _, _, { VartimeNafTable::BYTE_LEN }>(
+            deserializer,
+            "a table of odd multiples of a point",
+            VartimeNafTable::from_bytes,
+        )
+    }
+}
+
+impl<'a> From<&'a EdwardsPoint> for VartimeNafTable {
+    fn from(A: &'a EdwardsPoint) -> VartimeNafTable {
+        VartimeNafTable::new(A)
+    }
+}
+
+#[cfg(feature = "precomputed-tables")]
+macro_rules! impl_basepoint_table {
+    (Name = $name:ident, LookupTable = $table:ident, Point = $point:ty, Radix = $radix:expr, Additions = $adds:expr) => {
+        /// A precomputed table of multiples of a basepoint, for accelerating
+        /// fixed-base scalar multiplication.  One table, for the Ed25519
+        /// basepoint, is provided in the [`constants`] module.
+        ///
+        /// The basepoint tables are reasonably large, so they should probably be boxed.
+        ///
+        /// The sizes for the tables and the number of additions required for one scalar
+        /// multiplication are as follows:
+        ///
         /// * [`EdwardsBasepointTableRadix16`]: 30KB, 64A
         ///   (this is the default size, and is used for
         ///   [`constants::ED25519_BASEPOINT_TABLE`])
@@ -1088,6 +2230,94 @@ macro_rules! impl_basepoint_table {
                 write!(f, "])")
             }
         }
+
+        impl $name {
+            /// The length of the encoding produced by `to_bytes`.
+            pub const BYTE_LEN: usize = 32 * (1 << ($radix - 1)) * AffineNielsPoint::BYTE_LEN;
+
+            /// Encode this table, so that it can be built once, e.g. on a
+            /// host, and loaded with `from_bytes` without recomputation.
+            ///
+            /// The encoding is the entries of each of the 32 subtables in
+            /// order, each as the canonical encodings of its coordinates
+            /// \\((y+x, y-x, 2dxy)\\).
+            #[cfg(feature = "alloc")]
+            pub fn to_bytes(&self) -> Vec<u8> {
+                let mut bytes = Vec::with_capacity(Self::BYTE_LEN);
+                for table in self.0.iter() {
+                    for entry in table.0.iter() {
+                        bytes.extend_from_slice(&entry.to_bytes());
+                    }
+                }
+                bytes
+            }
+
+            /// Decode a table encoded by `to_bytes`.
+            ///
+            /// Every coordinate must be canonical and every entry a point
+            /// on the curve, which costs a handful of field multiplications
+            /// per entry.  The entries are not checked to be the multiples
+            /// of a single basepoint, as that costs as much as building the
+            /// table: only load tables from storage you trust.
+            pub fn from_bytes(bytes: &[u8]) -> Result<$name, TableError> {
+                if bytes.len() != Self::BYTE_LEN {
+                    return Err(TableError::Length {
+                        expected: Self::BYTE_LEN,
+                        actual: bytes.len(),
+                    });
+                }
+
+                let mut table = $name([$table::default(); 32]);
+                let chunks = bytes.chunks_exact(AffineNielsPoint::BYTE_LEN);
+                let entries = table.0.iter_mut().flat_map(|t| t.0.iter_mut());
+                for (index, (entry, chunk)) in entries.zip(chunks).enumerate() {
+                    let mut encoding = [0u8; 96];
+                    encoding.copy_from_slice(chunk);
+                    *entry = AffineNielsPoint::from_bytes(&encoding)
+                        .ok_or(TableError::InvalidEntry { index })?;
+                }
+                Ok(table)
+            }
+        }
+
+        // Serializes as the bytes of `to_bytes`, decoding each entry as it
+        // arrives, with the same checks as `from_bytes`.
+        #[cfg(feature = "serde")]
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serde_encoding::serialize_chunks(
+                    serializer,
+                    Self::BYTE_LEN / AffineNielsPoint::BYTE_LEN,
+                    self.0.iter().flat_map(|t| t.0.iter()).map(|e| e.to_bytes()),
+                )
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                const ENTRIES: usize = 1 << ($radix - 1);
+                let mut table = $name([$table::default(); 32]);
+                serde_encoding::deserialize_chunks(
+                    deserializer,
+                    Self::BYTE_LEN / AffineNielsPoint::BYTE_LEN,
+                    "a basepoint table",
+                    |index, chunk: &[u8; AffineNielsPoint::BYTE_LEN]| {
+                        table.0[index / ENTRIES].0[index % ENTRIES] =
+                            AffineNielsPoint::from_bytes(chunk)
+                                .ok_or(TableError::InvalidEntry { index })?;
+                        Ok::<(), TableError>(())
+                    },
+                )?;
+                Ok(table)
+            }
+        }
     };
 } // End macro_rules! impl_basepoint_table
 
@@ -1209,6 +2439,159 @@ cfg_if! {
     }
 }
 
+/// A precomputed table of multiples of a basepoint, trading speed for size,
+/// for fixed-base scalar multiplication on memory-constrained targets.
+///
+/// The scalar is written in radix 16, as for `EdwardsBasepointTable`, and
+/// its 64 digits are split into `SUBTABLES` runs of consecutive digits.  The
+/// table holds the multiples \\(1, \ldots, 8\\) of the power of 16 times
+/// \\(B\\) at the start of each run, and the runs are processed together,
+/// multiplying the accumulator by 16 between digit positions.  A
+/// multiplication always costs 64 additions, each a constant-time lookup in
+/// an 8-entry table, and \\(4 (64 / \mathrm{SUBTABLES} - 1)\\) doublings:
+///
+/// * `EdwardsBasepointTableCompact<32>`: 30KB, 4 doublings, the same
+///   layout as `EdwardsBasepointTable`;
+/// * `EdwardsBasepointTableCompact<8>`: 7.5KB, 28 doublings;
+/// * `EdwardsBasepointTableCompact<4>`: 3.75KB, 60 doublings;
+/// * `EdwardsBasepointTableCompact<1>`: 960 bytes, 252 doublings.
+///
+/// `SUBTABLES` must divide 64; other values fail to compile.  The table does
+/// not need the `precomputed-tables` feature: it is built at run time with
+/// [`BasepointTable::create`].
+#[derive(Clone)]
+pub struct EdwardsBasepointTableCompact<const SUBTABLES: usize>(
+    [LookupTable<AffineNielsPoint>; SUBTABLES],
+);
+
+impl<const SUBTABLES: usize> EdwardsBasepointTableCompact<SUBTABLES> {
+    /// The number of radix-16 digit positions between consecutive subtables.
+    const STRIDE: usize = {
+        assert!(
+            SUBTABLES > 0 && 64 % SUBTABLES == 0,
+            "SUBTABLES must divide 64"
+        );
+        64 / SUBTABLES
+    };
+}
+
+impl<const SUBTABLES: usize> BasepointTable for EdwardsBasepointTableCompact<SUBTABLES> {
+    type Point = EdwardsPoint;
+
+    /// Create a table of precomputed multiples of `basepoint`.
+    fn create(basepoint: &EdwardsPoint) -> Self {
+        let stride = Self::STRIDE;
+        let mut table = EdwardsBasepointTableCompact([LookupTable::default(); SUBTABLES]);
+        let mut P = *basepoint;
+        for subtable in table.0.iter_mut() {
+            // P = 16^(stride * j) * B
+            *subtable = LookupTable::from(&P);
+            P = P.mul_by_pow_2(4 * stride as u32);
+        }
+        table
+    }
+
+    /// Get the basepoint for this table as an `EdwardsPoint`.
+    fn basepoint(&self) -> EdwardsPoint {
+        (&EdwardsPoint::identity() + &self.0[0].select(1)).as_extended()
+    }
+
+    /// Write the radix-16 digits of the scalar as \\(a\_{k + sj}\\), with
+    /// stride \\(s = 64 / \mathrm{SUBTABLES}\\), so that
+    /// $$
+    ///     a B = \sum\_{k=0}^{s-1} 16\^k \sum\_{j} a\_{k + sj} (16\^{sj} B),
+    /// $$
+    /// and evaluate the outer sum by Horner's rule, selecting each
+    /// \\(a\_{k + sj} (16\^{sj} B)\\) from subtable \\(j\\) in constant time.
+    fn mul_base(&self, scalar: &Scalar) -> EdwardsPoint {
+        let stride = Self::STRIDE;
+        let a = scalar.as_radix_16();
+
+        let mut P = EdwardsPoint::identity();
+        for k in (0..stride).rev() {
+            if k != stride - 1 {
+                P = P.mul_by_pow_2(4);
+            }
+            for (j, subtable) in self.0.iter().enumerate() {
+                P = (&P + &subtable.select(a[k + stride * j])).as_extended();
+            }
+        }
+
+        P
+    }
+}
+
+// Serializes as the entries of each subtable in order, each as the
+// canonical encodings of its coordinates \((y+x, y-x, 2dxy)\), like the
+// other basepoint tables.
+
+#[cfg(feature = "serde")]
+impl<const SUBTABLES: usize> Serialize for EdwardsBasepointTableCompact<SUBTABLES> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_encoding::serialize_chunks(
+            serializer,
+            8 * SUBTABLES,
+            self.0.iter().flat_map(|t| t.0.iter()).map(|e| e.to_bytes()),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const SUBTABLES: usize> Deserialize<'de> for EdwardsBasepointTableCompact<SUBTABLES> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut table = EdwardsBasepointTableCompact([LookupTable::default(); SUBTABLES]);
+        // Evaluate the check that SUBTABLES divides 64, as `create` does
+        let _ = Self::STRIDE;
+        serde_encoding::deserialize_chunks(
+            deserializer,
+            8 * SUBTABLES,
+            "a compact basepoint table",
+            |index, chunk: &[u8; AffineNielsPoint::BYTE_LEN]| {
+                table.0[index / 8].0[index % 8] = AffineNielsPoint::from_bytes(chunk)
+                    .ok_or(TableError::InvalidEntry { index })?;
+                Ok::<(), TableError>(())
+            },
+        )?;
+        Ok(table)
+    }
+}
+
+impl<'b, const SUBTABLES: usize> Mul<&'b Scalar> for &EdwardsBasepointTableCompact<SUBTABLES> {
+    type Output = EdwardsPoint;
+
+    /// Construct an `EdwardsPoint` from a `Scalar` \\(a\\) by
+    /// computing the multiple \\(aB\\) of this basepoint \\(B\\).
+    fn mul(self, scalar: &'b Scalar) -> EdwardsPoint {
+        self.mul_base(scalar)
+    }
+}
+
+impl<'a, const SUBTABLES: usize> Mul<&'a EdwardsBasepointTableCompact<SUBTABLES>> for &Scalar {
+    type Output = EdwardsPoint;
+
+    /// Construct an `EdwardsPoint` from a `Scalar` \\(a\\) by
+    /// computing the multiple \\(aB\\) of this basepoint \\(B\\).
+    fn mul(self, basepoint_table: &'a EdwardsBasepointTableCompact<SUBTABLES>) -> EdwardsPoint {
+        basepoint_table * self
+    }
+}
+
+impl<const SUBTABLES: usize> Debug for EdwardsBasepointTableCompact<SUBTABLES> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "EdwardsBasepointTableCompact<{}>([", SUBTABLES)?;
+        for table in self.0.iter() {
+            write!(f, "\n\t{:?},", table)?;
+        }
+        write!(f, "\n])")
+    }
+}
+
 impl EdwardsPoint {
     /// Multiply by the cofactor: return \\(\[8\]P\\).
     pub fn mul_by_cofactor(&self) -> EdwardsPoint {
@@ -1284,6 +2667,56 @@ impl EdwardsPoint {
     pub fn is_torsion_free(&self) -> bool {
         (self * constants::BASEPOINT_ORDER_PRIVATE).is_identity()
     }
+
+    /// Determine, for each of `points`, whether it is torsion-free, as
+    /// [`EdwardsPoint::is_torsion_free`] does.
+    ///
+    /// This runs in variable time, so is only for public points such as
+    /// the keys and signature components of batch verification.  In
+    /// exchange, the signed-digit recoding of the group order is shared by
+    /// the whole batch, and each point takes a variable-time multiplication
+    /// by it, which is mostly doublings since the order is \\(2\^{252}\\)
+    /// plus a 125-bit number, instead of a constant-time one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::constants;
+    /// use curve25519_dalek::edwards::EdwardsPoint;
+    ///
+    /// let P = constants::ED25519_BASEPOINT_POINT;
+    /// let Q = constants::EIGHT_TORSION[1];
+    ///
+    /// assert_eq!(
+    ///     EdwardsPoint::batch_is_torsion_free(&[P, P + Q, Q]),
+    ///     [true, false, false]
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn batch_is_torsion_free(points: &[EdwardsPoint]) -> Vec<bool> {
+        use core::cmp::Ordering;
+
+        let order_naf = constants::BASEPOINT_ORDER_PRIVATE.non_adjacent_form(5);
+        let top = order_naf.iter().rposition(|&d| d != 0).unwrap_or(0);
+
+        points
+            .iter()
+            .map(|P| {
+                let table = NafLookupTable5::<ProjectiveNielsPoint>::from(P);
+                let mut r = ProjectivePoint::identity();
+                for &digit in order_naf[..=top].iter().rev() {
+                    let mut t = r.double();
+                    match digit.cmp(&0) {
+                        Ordering::Greater => t = &t.as_extended() + &table.select(digit as usize),
+                        Ordering::Less => t = &t.as_extended() - &table.select(-digit as usize),
+                        Ordering::Equal => {}
+                    }
+                    r = t.as_projective();
+                }
+                r.as_extended().is_identity()
+            })
+            .collect()
+    }
 }
 
 // ------------------------------------------------------------------------
@@ -1615,7 +3048,7 @@ impl GroupEncoding for SubgroupPoint {
 #[cfg(feature = "group")]
 impl PrimeGroup for SubgroupPoint {}
 
-/// Ristretto has a cofactor of 1.
+/// Edwards25519 has a cofactor of 8.
 #[cfg(feature = "group")]
 impl CofactorGroup for EdwardsPoint {
     type Subgroup = SubgroupPoint;
@@ -1641,8 +3074,8 @@ impl CofactorGroup for EdwardsPoint {
 mod test {
     use super::*;
 
-    // If `group` is set, then this is already imported in super
-    #[cfg(not(feature = "group"))]
+    // If `rand_core` is set, then this is already imported in super
+    #[cfg(not(feature = "rand_core"))]
     use rand_core::RngCore;
 
     #[cfg(feature = "alloc")]
@@ -1698,42 +3131,267 @@ mod test {
         0x40, 0xa5,
     ]);
 
-    /// A_SCALAR * (A_TIMES_BASEPOINT) + B_SCALAR * BASEPOINT
-    /// computed with ed25519.py
-    static DOUBLE_SCALAR_MULT_RESULT: CompressedEdwardsY = CompressedEdwardsY([
-        0x7d, 0xfd, 0x6c, 0x45, 0xaf, 0x6d, 0x6e, 0x0e, 0xba, 0x20, 0x37, 0x1a, 0x23, 0x64, 0x59,
-        0xc4, 0xc0, 0x46, 0x83, 0x43, 0xde, 0x70, 0x4b, 0x85, 0x09, 0x6f, 0xfe, 0x35, 0x4f, 0x13,
-        0x2b, 0x42,
-    ]);
+    /// A_SCALAR * (A_TIMES_BASEPOINT) + B_SCALAR * BASEPOINT
+    /// computed with ed25519.py
+    static DOUBLE_SCALAR_MULT_RESULT: CompressedEdwardsY = CompressedEdwardsY([
+        0x7d, 0xfd, 0x6c, 0x45, 0xaf, 0x6d, 0x6e, 0x0e, 0xba, 0x20, 0x37, 0x1a, 0x23, 0x64, 0x59,
+        0xc4, 0xc0, 0x46, 0x83, 0x43, 0xde, 0x70, 0x4b, 0x85, 0x09, 0x6f, 0xfe, 0x35, 0x4f, 0x13,
+        0x2b, 0x42,
+    ]);
+
+    /// Test round-trip decompression for the basepoint.
+    #[test]
+    fn basepoint_decompression_compression() {
+        let base_X = FieldElement::from_bytes(&BASE_X_COORD_BYTES);
+        let bp = constants::ED25519_BASEPOINT_COMPRESSED
+            .decompress()
+            .unwrap();
+        assert!(bp.is_valid());
+        // Check that decompression actually gives the correct X coordinate
+        assert_eq!(base_X, bp.X);
+        assert_eq!(bp.compress(), constants::ED25519_BASEPOINT_COMPRESSED);
+    }
+
+    /// Test sign handling in decompression
+    #[test]
+    fn decompression_sign_handling() {
+        // Manually set the high bit of the last byte to flip the sign
+        let mut minus_basepoint_bytes = *constants::ED25519_BASEPOINT_COMPRESSED.as_bytes();
+        minus_basepoint_bytes[31] |= 1 << 7;
+        let minus_basepoint = CompressedEdwardsY(minus_basepoint_bytes)
+            .decompress()
+            .unwrap();
+        // Test projective coordinates exactly since we know they should
+        // only differ by a flipped sign.
+        assert_eq!(minus_basepoint.X, -(&constants::ED25519_BASEPOINT_POINT.X));
+        assert_eq!(minus_basepoint.Y, constants::ED25519_BASEPOINT_POINT.Y);
+        assert_eq!(minus_basepoint.Z, constants::ED25519_BASEPOINT_POINT.Z);
+        assert_eq!(minus_basepoint.T, -(&constants::ED25519_BASEPOINT_POINT.T));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn compress_batch_matches_compress() {
+        let points: Vec<EdwardsPoint> = (0u64..10)
+            .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(i))
+            .chain(constants::EIGHT_TORSION.iter().copied())
+            .collect();
+        let compressed = EdwardsPoint::compress_batch(&points);
+        assert_eq!(compressed.len(), points.len());
+        for (c, P) in compressed.iter().zip(points.iter()) {
+            assert_eq!(*c, P.compress());
+        }
+        assert!(EdwardsPoint::compress_batch(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_multiscalar_mul_matches_serial() {
+        let n = 4 * PARALLEL_MSM_MIN_CHUNK_LEN + 17;
+        let scalars: Vec<Scalar> = (0..n as u64)
+            .map(|i| Scalar::from(i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+            .collect();
+        let points: Vec<EdwardsPoint> = (0..n as u64)
+            .map(|i| EdwardsPoint::mul_base(&Scalar::from(i + 1)))
+            .collect();
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let parallel = pool.install(|| EdwardsPoint::vartime_multiscalar_mul(&scalars, &points));
+        let serial = crate::backend::pippenger_optional_multiscalar_mul(
+            &scalars,
+            points.iter().map(|P| Some(*P)),
+        );
+        assert_eq!(Some(parallel), serial);
+
+        let mut optional: Vec<Option<EdwardsPoint>> = points.iter().map(|P| Some(*P)).collect();
+        assert_eq!(
+            pool.install(|| EdwardsPoint::optional_multiscalar_mul(&scalars, optional.clone())),
+            serial
+        );
+        optional[n - 1] = None;
+        assert_eq!(
+            pool.install(|| EdwardsPoint::optional_multiscalar_mul(&scalars, optional)),
+            None
+        );
+    }
+
+    #[test]
+    fn compact_basepoint_table() {
+        fn check<const SUBTABLES: usize>() {
+            let table = EdwardsBasepointTableCompact::<SUBTABLES>::create(
+                &constants::ED25519_BASEPOINT_POINT,
+            );
+            assert_eq!(table.basepoint(), constants::ED25519_BASEPOINT_POINT);
+            for s in [Scalar::ZERO, Scalar::ONE, A_SCALAR, B_SCALAR, -Scalar::ONE] {
+                assert_eq!(&table * &s, EdwardsPoint::mul_base(&s));
+            }
+            assert_eq!(
+                table.mul_base_clamped([0xff; 32]),
+                EdwardsPoint::mul_base_clamped([0xff; 32])
+            );
+        }
+
+        check::<1>();
+        check::<4>();
+        check::<8>();
+        check::<32>();
+        check::<64>();
+    }
+
+    #[test]
+    fn affine_round_trip() {
+        let P = constants::ED25519_BASEPOINT_POINT * A_SCALAR;
+        let affine = P.to_affine();
+        assert_eq!(affine.to_edwards(), P);
+        assert_eq!(affine.compress(), P.compress());
+        assert_eq!(affine.to_edwards().Z, FieldElement::ONE);
+        assert_eq!(
+            EdwardsPoint::identity().to_affine(),
+            AffinePoint::identity()
+        );
+        assert_ne!(affine, AffinePoint::identity());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn batch_to_affine_matches_to_affine() {
+        let mut points: Vec<EdwardsPoint> = (0..16u64)
+            .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(i))
+            .collect();
+        points.push(constants::EIGHT_TORSION[3]);
+
+        let batch = EdwardsPoint::batch_to_affine(&points);
+        assert_eq!(batch.len(), points.len());
+        for (P, affine) in points.iter().zip(batch.iter()) {
+            assert_eq!(*affine, P.to_affine());
+            assert_eq!(affine.to_edwards(), *P);
+        }
+        assert!(EdwardsPoint::batch_to_affine(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn batch_is_torsion_free() {
+        let mut points = Vec::new();
+        for i in 0..8u64 {
+            let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(i * 1000 + 1);
+            points.push(P);
+            points.push(P + constants::EIGHT_TORSION[i as usize]);
+            points.push(constants::EIGHT_TORSION[i as usize]);
+        }
+        points.push(EdwardsPoint::identity());
+
+        let expected: Vec<bool> = points.iter().map(|P| P.is_torsion_free()).collect();
+        assert_eq!(EdwardsPoint::batch_is_torsion_free(&points), expected);
+        assert_eq!(expected.iter().filter(|&&b| b).count(), 11);
+        assert!(EdwardsPoint::batch_is_torsion_free(&[]).is_empty());
+    }
 
-    /// Test round-trip decompression for the basepoint.
     #[test]
-    fn basepoint_decompression_compression() {
-        let base_X = FieldElement::from_bytes(&BASE_X_COORD_BYTES);
-        let bp = constants::ED25519_BASEPOINT_COMPRESSED
-            .decompress()
-            .unwrap();
-        assert!(bp.is_valid());
-        // Check that decompression actually gives the correct X coordinate
-        assert_eq!(base_X, bp.X);
-        assert_eq!(bp.compress(), constants::ED25519_BASEPOINT_COMPRESSED);
+    fn multiscalar_mul_fixed() {
+        fn check<const N: usize>() {
+            let mut scalars = [Scalar::ZERO; N];
+            let mut points = [EdwardsPoint::identity(); N];
+            let mut expected = EdwardsPoint::identity();
+            for (i, (s, P)) in scalars.iter_mut().zip(points.iter_mut()).enumerate() {
+                *s = A_SCALAR * Scalar::from(i as u64 + 3) - B_SCALAR;
+                *P = constants::ED25519_BASEPOINT_POINT * Scalar::from(i as u64 + 1);
+                expected += *P * *s;
+            }
+            assert_eq!(
+                EdwardsPoint::multiscalar_mul_fixed(&scalars, &points),
+                expected
+            );
+            assert_eq!(
+                EdwardsPoint::vartime_multiscalar_mul_fixed(&scalars, &points),
+                expected
+            );
+        }
+
+        check::<0>();
+        check::<1>();
+        check::<2>();
+        check::<7>();
     }
 
-    /// Test sign handling in decompression
     #[test]
-    fn decompression_sign_handling() {
-        // Manually set the high bit of the last byte to flip the sign
-        let mut minus_basepoint_bytes = *constants::ED25519_BASEPOINT_COMPRESSED.as_bytes();
-        minus_basepoint_bytes[31] |= 1 << 7;
-        let minus_basepoint = CompressedEdwardsY(minus_basepoint_bytes)
-            .decompress()
+    #[cfg(feature = "alloc")]
+    fn vartime_multiscalar_mul_with_window() {
+        let scalars: Vec<Scalar> = (0..30u64).map(|i| Scalar::from(i * 31 + 5)).collect();
+        let points: Vec<EdwardsPoint> = (0..30u64)
+            .map(|i| EdwardsPoint::mul_base(&Scalar::from(i + 2)))
+            .collect();
+        let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+
+        for window in 4..=8 {
+            assert_eq!(
+                EdwardsPoint::vartime_multiscalar_mul_with_window(&scalars, &points, window),
+                Ok(expected)
+            );
+        }
+        for window in [0, 3, 9] {
+            assert_eq!(
+                EdwardsPoint::vartime_multiscalar_mul_with_window(&scalars, &points, window),
+                Err(MultiscalarError::WindowOutOfRange { window })
+            );
+        }
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul_with_window(&scalars[1..], &points, 6),
+            Err(MultiscalarError::LengthMismatch {
+                scalars: 29,
+                points: 30
+            })
+        );
+        assert!(EdwardsPoint::pippenger_window(190) <= EdwardsPoint::pippenger_window(1 << 20));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vartime_multiscalar_mul_compressed_matches_multiscalar_mul() {
+        let n = 2 * MSM_STREAM_CHUNK_LEN + 5;
+        let scalars: Vec<Scalar> = (0..n)
+            .map(|i| Scalar::from(i as u64 * 0x1234_5678 + 1))
+            .collect();
+        let points: Vec<EdwardsPoint> = (0..n)
+            .map(|i| EdwardsPoint::mul_base(&Scalar::from(i as u64 + 7)))
+            .collect();
+        let terms = || {
+            scalars
+                .iter()
+                .zip(points.iter())
+                .map(|(s, P)| (*s, P.compress()))
+        };
+
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul_compressed(terms()),
+            Ok(EdwardsPoint::vartime_multiscalar_mul(&scalars, &points))
+        );
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul_compressed(core::iter::empty()),
+            Ok(EdwardsPoint::identity())
+        );
+
+        let invalid = (0u8..=255)
+            .map(|i| CompressedEdwardsY([i; 32]))
+            .find(|c| c.decompress().is_none())
             .unwrap();
-        // Test projective coordinates exactly since we know they should
-        // only differ by a flipped sign.
-        assert_eq!(minus_basepoint.X, -(&constants::ED25519_BASEPOINT_POINT.X));
-        assert_eq!(minus_basepoint.Y, constants::ED25519_BASEPOINT_POINT.Y);
-        assert_eq!(minus_basepoint.Z, constants::ED25519_BASEPOINT_POINT.Z);
-        assert_eq!(minus_basepoint.T, -(&constants::ED25519_BASEPOINT_POINT.T));
+        let bad_index = MSM_STREAM_CHUNK_LEN + 3;
+        let corrupted = terms().enumerate().map(
+            |(i, (s, c))| {
+                if i == bad_index {
+                    (s, invalid)
+                } else {
+                    (s, c)
+                }
+            },
+        );
+        assert_eq!(
+            EdwardsPoint::vartime_multiscalar_mul_compressed(corrupted),
+            Err(MultiscalarError::InvalidPoint { index: bad_index })
+        );
     }
 
     /// Test that computing 1*basepoint gives the correct basepoint.
@@ -1910,6 +3568,54 @@ mod test {
         assert_eq!(aP128, aP256);
     }
 
+    /// Check that basepoint tables survive a round trip through their encoding.
+    #[cfg(all(feature = "precomputed-tables", feature = "alloc"))]
+    #[test]
+    fn basepoint_table_bytes_round_trip() {
+        use crate::errors::TableError;
+
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let table = EdwardsBasepointTableRadix32::create(&P);
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), EdwardsBasepointTableRadix32::BYTE_LEN);
+
+        let loaded = EdwardsBasepointTableRadix32::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.basepoint(), P);
+        assert_eq!(&loaded * &A_SCALAR, A_SCALAR * P);
+
+        let encoded = ED25519_BASEPOINT_TABLE.to_bytes();
+        let loaded = EdwardsBasepointTable::from_bytes(&encoded).unwrap();
+        assert_eq!(&loaded * &A_SCALAR, ED25519_BASEPOINT_TABLE * &A_SCALAR);
+
+        assert_eq!(
+            EdwardsBasepointTable::from_bytes(&encoded[1..]).unwrap_err(),
+            TableError::Length {
+                expected: EdwardsBasepointTable::BYTE_LEN,
+                actual: EdwardsBasepointTable::BYTE_LEN - 1,
+            }
+        );
+
+        // Entry 9 is the second entry of the second subtable.
+        let mut corrupted = bytes.clone();
+        corrupted[9 * 96 + 40] ^= 1;
+        assert_eq!(
+            EdwardsBasepointTableRadix32::from_bytes(&corrupted).unwrap_err(),
+            TableError::InvalidEntry { index: 9 }
+        );
+
+        // A coordinate of p is not canonical, even though it decodes to 0.
+        let mut unreduced = bytes;
+        unreduced[64..96].copy_from_slice(&[
+            0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0x7f,
+        ]);
+        assert_eq!(
+            EdwardsBasepointTableRadix32::from_bytes(&unreduced).unwrap_err(),
+            TableError::InvalidEntry { index: 0 }
+        );
+    }
+
     /// Check that converting to projective and then back to extended round-trips.
     #[test]
     fn basepoint_projective_extended_round_trip() {
@@ -1929,6 +3635,65 @@ mod test {
         assert_eq!(bp16.compress(), BASE16_CMPRSSD);
     }
 
+    /// Check that mul_streaming multiplies by the full 256-bit value in
+    /// every chunk size
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn mul_streaming_matches_mul() {
+        fn streamed<const N: usize>(P: &EdwardsPoint, bytes: [u8; 32]) -> EdwardsPoint {
+            let mut requested = Vec::new();
+            let product = P
+                .mul_streaming(|range, chunk: &mut [u8; N]| {
+                    requested.push(range.start);
+                    chunk.copy_from_slice(&bytes[range]);
+                    Ok::<(), ()>(())
+                })
+                .unwrap();
+            assert_eq!(
+                requested,
+                (0..32 / N).rev().map(|k| k * N).collect::<Vec<_>>()
+            );
+            product
+        }
+
+        let mut csprng = rand_core::OsRng;
+        // Give the point torsion, so that reducing the scalar would be noticed
+        let P = EdwardsPoint::mul_base(&Scalar::random(&mut csprng)) + constants::EIGHT_TORSION[1];
+
+        for _ in 0..20 {
+            let mut bytes = [0u8; 32];
+            csprng.fill_bytes(&mut bytes);
+            let expected = P.mul_clamped(bytes);
+            let clamped = clamp_integer(bytes);
+            assert_eq!(streamed::<1>(&P, clamped), expected);
+            assert_eq!(streamed::<2>(&P, clamped), expected);
+            assert_eq!(streamed::<4>(&P, clamped), expected);
+            assert_eq!(streamed::<8>(&P, clamped), expected);
+            assert_eq!(streamed::<16>(&P, clamped), expected);
+            assert_eq!(streamed::<32>(&P, clamped), expected);
+        }
+
+        // Every digit at least 8, and the top bit set: 2^256 - 1
+        let mut low = [0xff; 32];
+        low[31] = 0x7f;
+        let expected = P * Scalar { bytes: low } + P.mul_by_pow_2(255);
+        assert_eq!(streamed::<4>(&P, [0xff; 32]), expected);
+        assert_eq!(streamed::<4>(&P, [0; 32]), EdwardsPoint::identity());
+
+        // An error stops the stream at the chunk that failed
+        let mut calls = 0;
+        let result = P.mul_streaming(|range, _: &mut [u8; 8]| {
+            calls += 1;
+            if range.start == 16 {
+                Err("key store unavailable")
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err("key store unavailable"));
+        assert_eq!(calls, 2);
+    }
+
     /// Check that mul_base_clamped and mul_clamped agree
     #[test]
     fn mul_base_clamped() {
@@ -2046,6 +3811,33 @@ mod test {
         assert!(!constants::ED25519_BASEPOINT_POINT.is_identity());
     }
 
+    #[cfg(feature = "group")]
+    #[test]
+    fn group_impls() {
+        crate::test_support::check_group_impl::<EdwardsPoint>();
+        crate::test_support::check_group_impl::<SubgroupPoint>();
+    }
+
+    #[cfg(feature = "group")]
+    #[test]
+    fn subgroup_point_rejects_torsion() {
+        let B = constants::ED25519_BASEPOINT_POINT;
+        let P = B + constants::EIGHT_TORSION[1];
+
+        assert!(bool::from(CofactorGroup::is_torsion_free(&B)));
+        assert!(!bool::from(CofactorGroup::is_torsion_free(&P)));
+        assert!(bool::from(
+            SubgroupPoint::from_bytes(&B.compress().to_bytes()).is_some()
+        ));
+        assert!(bool::from(
+            SubgroupPoint::from_bytes(&P.compress().to_bytes()).is_none()
+        ));
+        assert_eq!(
+            EdwardsPoint::from(CofactorGroup::clear_cofactor(&P)),
+            B.mul_by_cofactor()
+        );
+    }
+
     /// Rust's debug builds have overflow and underflow trapping,
     /// and enable `debug_assert!()`.  This performs many scalar
     /// multiplications to attempt to trigger possible overflows etc.
@@ -2204,6 +3996,32 @@ mod test {
             assert_eq!(result.compress(), DOUBLE_SCALAR_MULT_RESULT);
         }
 
+        #[test]
+        fn triple_scalar_mul_basepoint_vs_separate_muls() {
+            use crate::backend::serial::scalar_mul::vartime_triple_base;
+
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let B = constants::ED25519_BASEPOINT_POINT * Scalar::from(987654321u64);
+            let c = Scalar::from(0x1234_5678_9abc_def0u64);
+            for b in [Scalar::ZERO, Scalar::ONE, B_SCALAR, -A_SCALAR] {
+                for a in [Scalar::ZERO, A_SCALAR] {
+                    let expected = a * A + b * B + EdwardsPoint::mul_base(&c);
+                    let result =
+                        EdwardsPoint::vartime_triple_scalar_mul_basepoint(&a, &A, &b, &B, &c);
+                    assert_eq!(result.compress(), expected.compress());
+                    let serial = vartime_triple_base::mul(&a, &A, &b, &B, &c);
+                    assert_eq!(serial.compress(), expected.compress());
+                }
+            }
+
+            // With only the basepoint term left this is a fixed-base multiply.
+            let zero = Scalar::ZERO;
+            assert_eq!(
+                EdwardsPoint::vartime_triple_scalar_mul_basepoint(&zero, &A, &zero, &B, &c),
+                EdwardsPoint::mul_base(&c)
+            );
+        }
+
         #[test]
         #[cfg(feature = "alloc")]
         fn multiscalar_mul_vs_ed25519py() {
@@ -2230,6 +4048,465 @@ mod test {
 
             assert_eq!(result_vartime.compress(), result_consttime.compress());
         }
+
+        #[test]
+        fn double_scalar_mul_basepoint_identity_policy() {
+            let A = EdwardsPoint::identity();
+            let bB = B_SCALAR * constants::ED25519_BASEPOINT_POINT;
+
+            assert_eq!(
+                EdwardsPoint::vartime_double_scalar_mul_basepoint(&A_SCALAR, &A, &B_SCALAR),
+                bB
+            );
+            assert_eq!(
+                EdwardsPoint::try_vartime_double_scalar_mul_basepoint(&A_SCALAR, &A, &B_SCALAR),
+                Err(InputError::IdentityPoint)
+            );
+
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let result =
+                EdwardsPoint::try_vartime_double_scalar_mul_basepoint(&A_SCALAR, &A, &B_SCALAR);
+            assert_eq!(result.unwrap().compress(), DOUBLE_SCALAR_MULT_RESULT);
+        }
+
+        #[test]
+        fn double_scalar_mul_basepoint_from_affine() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let extended =
+                EdwardsPoint::vartime_double_scalar_mul_basepoint_from(&A_SCALAR, &A, &B_SCALAR);
+            let affine = EdwardsPoint::vartime_double_scalar_mul_basepoint_from(
+                &A_SCALAR,
+                &A.to_affine(),
+                &B_SCALAR,
+            );
+
+            assert_eq!(extended.compress(), DOUBLE_SCALAR_MULT_RESULT);
+            assert_eq!(affine, extended);
+        }
+
+        #[test]
+        fn step_mul_with_budget() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let last = core::cell::Cell::new((300, [0u64; 15]));
+            let mut calls = 0;
+            let point = loop {
+                let (i, r) = last.get();
+                let (point, status) =
+                    EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint_with_budget(
+                        &A_SCALAR,
+                        &A,
+                        &B_SCALAR,
+                        |i, r| last.set((i, r)),
+                        i,
+                        r,
+                        100,
+                    )
+                    .unwrap();
+                calls += 1;
+                if status != 1 {
+                    break point;
+                }
+            };
+            assert_eq!(point.compress(), DOUBLE_SCALAR_MULT_RESULT);
+            assert!(calls <= 3);
+
+            let result = EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint_with_budget(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                300,
+                [0u64; 15],
+                0,
+            );
+            assert_eq!(
+                result.map(|_| ()),
+                Err(StepError::BudgetOutOfRange { budget: 0 })
+            );
+        }
+
+        #[test]
+        fn step_mul_rejects_corrupted_checkpoint() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let saved = core::cell::Cell::new(None);
+            EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint_with_budget(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |i, r| saved.set(Some((i, r))),
+                300,
+                [0u64; 15],
+                50,
+            )
+            .unwrap();
+            let (i, mut r) = saved.get().unwrap();
+            r[0] ^= 1;
+
+            let result = EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                i,
+                r,
+            );
+            assert_eq!(result.map(|_| ()), Err(StepError::CheckpointNotOnCurve));
+
+            // A fresh start ignores the accumulator.
+            let result = EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                300,
+                [0u64; 15],
+            );
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn step_mul_rejects_oversized_start_index() {
+            use crate::resumable::CompressedCheckpoint;
+
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let error = StepError::IndexOutOfRange { index: 256 };
+
+            let result = EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                256,
+                [0u64; 15],
+            );
+            assert_eq!(result.map(|_| ()), Err(error));
+
+            let result = EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                &CompressedCheckpoint::new(256, A_TIMES_BASEPOINT),
+            );
+            assert_eq!(result.map(|_| ()), Err(error));
+
+            let result = EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                |_, _| {},
+                300,
+                [0u64; 15],
+            );
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        #[cfg(feature = "alloc")]
+        fn multiscalar_mul_prefix_sums_vs_multiscalar_mul() {
+            let scalars: Vec<Scalar> = (1..=6u64).map(|i| Scalar::from(i * 1000 + 7)).collect();
+            let points: Vec<EdwardsPoint> = (1..=6u64)
+                .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(i * 31 + 2))
+                .collect();
+
+            let prefixes = EdwardsPoint::multiscalar_mul_prefix_sums(&scalars, &points);
+            assert_eq!(prefixes.len(), scalars.len());
+            for (k, prefix) in prefixes.iter().enumerate() {
+                let expected = EdwardsPoint::multiscalar_mul(&scalars[..=k], &points[..=k]);
+                assert_eq!(*prefix, expected);
+                assert_eq!(prefix.Z, FieldElement::ONE);
+            }
+            assert_eq!(
+                prefixes.last(),
+                Some(&EdwardsPoint::multiscalar_mul(&scalars, &points))
+            );
+
+            let none: [Scalar; 0] = [];
+            let no_points: [EdwardsPoint; 0] = [];
+            assert!(EdwardsPoint::multiscalar_mul_prefix_sums(&none, &no_points).is_empty());
+        }
+
+        #[test]
+        #[cfg(feature = "alloc")]
+        fn try_vartime_multiscalar_mul_lengths() {
+            let B = constants::ED25519_BASEPOINT_POINT;
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+
+            assert_eq!(
+                EdwardsPoint::try_vartime_multiscalar_mul(&[], &[]),
+                Ok(EdwardsPoint::identity())
+            );
+            assert_eq!(
+                EdwardsPoint::try_vartime_multiscalar_mul(&[A_SCALAR], &[B]),
+                Ok(A)
+            );
+            assert_eq!(
+                EdwardsPoint::try_vartime_multiscalar_mul(&[A_SCALAR, B_SCALAR], &[A, B]),
+                Ok(DOUBLE_SCALAR_MULT_RESULT.decompress().unwrap())
+            );
+
+            assert_eq!(
+                EdwardsPoint::try_vartime_multiscalar_mul(&[A_SCALAR, B_SCALAR], &[A]),
+                Err(MultiscalarError::LengthMismatch {
+                    scalars: 2,
+                    points: 1
+                })
+            );
+            assert_eq!(
+                EdwardsPoint::try_vartime_multiscalar_mul(&[], &[A]),
+                Err(MultiscalarError::LengthMismatch {
+                    scalars: 0,
+                    points: 1
+                })
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "alloc")]
+        fn step_vartime_multiscalar_mul_vs_vartime_multiscalar_mul() {
+            let scalars: Vec<Scalar> = (1..=32u64)
+                .map(|i| Scalar::from_bytes_mod_order([i as u8; 32]))
+                .collect();
+            let points: Vec<EdwardsPoint> = (1..=32u64)
+                .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(i * 31 + 2))
+                .collect();
+            let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+
+            let mut state = StepMulState::Fresh;
+            let mut chunks = 0;
+            let result = loop {
+                chunks += 1;
+                match EdwardsPoint::step_vartime_multiscalar_mul(&scalars, &points, state).unwrap()
+                {
+                    StepResult::InProgress(checkpoint) => state = StepMulState::Resumed(checkpoint),
+                    StepResult::Done(point) => break point,
+                }
+            };
+            assert_eq!(result, expected);
+            assert!(chunks > 1);
+
+            assert_eq!(
+                EdwardsPoint::step_vartime_multiscalar_mul(&[], &[], StepMulState::Fresh),
+                Ok(StepResult::Done(EdwardsPoint::identity()))
+            );
+            assert_eq!(
+                EdwardsPoint::step_vartime_multiscalar_mul(
+                    &scalars,
+                    &points[1..],
+                    StepMulState::Fresh
+                ),
+                Err(MultiscalarError::LengthMismatch {
+                    scalars: 32,
+                    points: 31
+                })
+            );
+            let out_of_range = Checkpoint::new(256, [0u64; 15]);
+            assert_eq!(
+                EdwardsPoint::step_vartime_multiscalar_mul(
+                    &scalars,
+                    &points,
+                    StepMulState::Resumed(out_of_range)
+                ),
+                Err(MultiscalarError::Step(StepError::IndexOutOfRange {
+                    index: 256
+                }))
+            );
+        }
+
+        #[test]
+        #[cfg(all(feature = "alloc", feature = "rand_core"))]
+        fn vartime_batch_verify_double_scalar_mul_basepoint_finds_failures() {
+            let mut rng = rand::thread_rng();
+            let mut equations: Vec<_> = (0..37u64)
+                .map(|i| {
+                    let a = Scalar::from_bytes_mod_order([i as u8 + 1; 32]);
+                    let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(i * 7 + 3);
+                    let b = Scalar::from(i * 1000 + 1);
+                    let C = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+                    (a, A, b, C)
+                })
+                .collect();
+            assert_eq!(
+                EdwardsPoint::vartime_batch_verify_double_scalar_mul_basepoint(
+                    &equations, &mut rng
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                EdwardsPoint::vartime_batch_verify_double_scalar_mul_basepoint(&[], &mut rng),
+                Ok(())
+            );
+
+            for &i in &[0, 17, 18, 36] {
+                equations[i].3 += constants::ED25519_BASEPOINT_POINT;
+            }
+            assert_eq!(
+                EdwardsPoint::vartime_batch_verify_double_scalar_mul_basepoint(
+                    &equations, &mut rng
+                ),
+                Err(vec![0, 17, 18, 36])
+            );
+            assert_eq!(
+                EdwardsPoint::vartime_batch_verify_double_scalar_mul_basepoint(
+                    &equations[17..18],
+                    &mut rng
+                ),
+                Err(vec![0])
+            );
+        }
+
+        #[test]
+        fn batch_verifier_same_key() {
+            let B = constants::ED25519_BASEPOINT_POINT;
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let verifier = BatchVerifierSameKey::new(&A);
+            assert_eq!(verifier.point(), &A);
+
+            let signatures: [(Scalar, Scalar); 4] = [
+                (Scalar::from(1u64), Scalar::from(2u64)),
+                (A_SCALAR, B_SCALAR),
+                (B_SCALAR, A_SCALAR),
+                (Scalar::from(0xdead_beef_u64), -A_SCALAR),
+            ];
+            for (s, h) in signatures.iter() {
+                let R = s * B - h * A;
+                assert!(verifier.verify(s, h, &R.compress()));
+            }
+
+            // The wrong challenge is rejected.
+            let (s, h) = signatures[1];
+            let R = s * B - h * A;
+            assert!(!verifier.verify(&s, &(h + Scalar::ONE), &R.compress()));
+        }
+
+        #[test]
+        fn vartime_naf_table_bytes_round_trip() {
+            use crate::errors::TableError;
+
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let bytes = VartimeNafTable::new(&A).to_bytes();
+            let table = VartimeNafTable::from_bytes(&bytes).unwrap();
+            assert_eq!(table.point(), &A);
+            assert_eq!(
+                EdwardsPoint::vartime_double_scalar_mul_basepoint_precomputed(
+                    &A_SCALAR, &table, &B_SCALAR
+                ),
+                EdwardsPoint::vartime_double_scalar_mul_basepoint(&A_SCALAR, &A, &B_SCALAR)
+            );
+
+            assert_eq!(
+                VartimeNafTable::from_bytes(&bytes[..1000]).unwrap_err(),
+                TableError::Length {
+                    expected: 1024,
+                    actual: 1000
+                }
+            );
+
+            let mut corrupted = bytes;
+            corrupted[3 * 128 + 5] ^= 1;
+            assert_eq!(
+                VartimeNafTable::from_bytes(&corrupted).unwrap_err(),
+                TableError::InvalidEntry { index: 3 }
+            );
+
+            // Valid points which are not the odd multiples of the first.
+            let mut swapped = bytes;
+            swapped[128..256].copy_from_slice(&bytes[256..384]);
+            swapped[256..384].copy_from_slice(&bytes[128..256]);
+            assert_eq!(
+                VartimeNafTable::from_bytes(&swapped).unwrap_err(),
+                TableError::Inconsistent
+            );
+        }
+
+        #[test]
+        fn mul_bits_matches_mul() {
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            for budget in [1, 7, 256] {
+                let mut state = (EdwardsPoint::identity(), 256);
+                let mut steps = 0;
+                while state.1 > 0 {
+                    state = EdwardsPoint::mul_bits(&A_SCALAR, &A, &state.0, state.1, budget);
+                    steps += 1;
+                }
+                assert_eq!(state.0, A_SCALAR * A);
+                assert_eq!(steps, (256 + budget - 1) / budget);
+            }
+        }
+
+        /// Checkpoint a step-wise multiplication once, then finish it both
+        /// from the raw limbs and from the compressed accumulator.
+        #[test]
+        fn step_mul_resume_from_compressed_checkpoint() {
+            use crate::backend::serial::scalar_mul::vartime_double_base::{
+                step_mul, step_mul_from_point,
+            };
+            use crate::resumable::{compress_checkpoint, CompressedCheckpoint};
+            use core::cell::Cell;
+
+            let A = A_TIMES_BASEPOINT.decompress().unwrap();
+            let saved = Cell::new((0usize, [0u64; 15]));
+            let save = |i: usize, limbs: [u64; 15]| saved.set((i, limbs));
+
+            let (_, status) = step_mul(&A_SCALAR, &A, &B_SCALAR, save, 300, [0u64; 15]).unwrap();
+            assert_eq!(status, 1);
+            let first_checkpoint = saved.get();
+
+            let limb_result = loop {
+                let (i, limbs) = saved.get();
+                let (P, status) = step_mul(&A_SCALAR, &A, &B_SCALAR, save, i, limbs).unwrap();
+                if status == 2 {
+                    break P;
+                }
+            };
+
+            saved.set(first_checkpoint);
+            let compressed_result = loop {
+                let (i, limbs) = saved.get();
+                let r = compress_checkpoint(limbs).unwrap().decompress().unwrap();
+                let (P, status) =
+                    step_mul_from_point(&A_SCALAR, &A, &B_SCALAR, save, i, r.as_projective())
+                        .unwrap();
+                if status == 2 {
+                    break P;
+                }
+            };
+
+            assert_eq!(limb_result.compress(), DOUBLE_SCALAR_MULT_RESULT);
+            assert_eq!(compressed_result.compress(), DOUBLE_SCALAR_MULT_RESULT);
+
+            // The public entry point agrees, and rejects non-points.
+            let (i, limbs) = first_checkpoint;
+            let mut P = EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed(
+                &A_SCALAR,
+                &A,
+                &B_SCALAR,
+                save,
+                &CompressedCheckpoint::new(i, compress_checkpoint(limbs).unwrap()),
+            )
+            .unwrap();
+            while P.1 == 1 {
+                let (i, limbs) = saved.get();
+                P = EdwardsPoint::step_vartime_double_scalar_mul_basepoint(
+                    &A_SCALAR, &A, &B_SCALAR, save, i, limbs,
+                )
+                .unwrap();
+            }
+            assert_eq!(P.0.compress(), DOUBLE_SCALAR_MULT_RESULT);
+
+            let mut not_a_point = [0u8; 32];
+            not_a_point[0] = 2;
+            assert_eq!(
+                EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed(
+                    &A_SCALAR,
+                    &A,
+                    &B_SCALAR,
+                    save,
+                    &CompressedCheckpoint::new(i, CompressedEdwardsY(not_a_point)),
+                ),
+                Err(StepError::CheckpointDecompression)
+            );
+        }
     }
 
     #[test]
@@ -2256,6 +4533,47 @@ mod test {
         assert_eq!(bp, constants::ED25519_BASEPOINT_POINT);
     }
 
+    #[test]
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    fn serde_bincode_tables() {
+        use crate::errors::TableError;
+        use alloc::string::ToString;
+
+        // Basepoint tables encode as `to_bytes`, with no prefix
+        #[cfg(feature = "precomputed-tables")]
+        {
+            let table = ED25519_BASEPOINT_TABLE;
+            let encoded = bincode::serialize(table).unwrap();
+            assert_eq!(encoded, table.to_bytes());
+            let decoded: EdwardsBasepointTable = bincode::deserialize(&encoded).unwrap();
+            assert_eq!(decoded.basepoint(), constants::ED25519_BASEPOINT_POINT);
+        }
+
+        let table = EdwardsBasepointTableCompact::<8>::create(&constants::ED25519_BASEPOINT_POINT);
+        let encoded = bincode::serialize(&table).unwrap();
+        assert_eq!(encoded.len(), 8 * 8 * 96);
+        let decoded: EdwardsBasepointTableCompact<8> = bincode::deserialize(&encoded).unwrap();
+        let s = Scalar::from_bytes_mod_order([0x99; 32]);
+        assert_eq!(&s * &decoded, EdwardsPoint::mul_base(&s));
+
+        // An entry which is not a point is rejected, with its index
+        let mut corrupt = encoded.clone();
+        corrupt[5 * 96] ^= 1;
+        let error = bincode::deserialize::<EdwardsBasepointTableCompact<8>>(&corrupt).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            TableError::InvalidEntry { index: 5 }.to_string()
+        );
+        assert!(bincode::deserialize::<EdwardsBasepointTableCompact<8>>(&encoded[..1000]).is_err());
+
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(7u64);
+        let table = VartimeNafTable::new(&A);
+        let encoded = bincode::serialize(&table).unwrap();
+        assert_eq!(encoded[..], table.to_bytes()[..]);
+        let decoded: VartimeNafTable = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.point(), &A);
+    }
+
     ////////////////////////////////////////////////////////////
     // Signal tests from                                      //
     //     https://github.com/signalapp/libsignal-protocol-c/ //
@@ -2307,6 +4625,35 @@ mod test {
         ]
     }
 
+    /// Test vectors from RFC 9380 appendix J.5.1, with the expected points
+    /// compressed.
+    #[test]
+    #[cfg(feature = "digest")]
+    fn hash_to_curve_rfc9380_vectors() {
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "21dc15e10253796df23a7699c8a383ea624cce88c52431f6be220b1a56c8a609",
+            ),
+            (
+                b"abc",
+                "31558a26887f23fb8218f143e69d5f0af2e7831130bd5b432ef23883b895839a",
+            ),
+        ];
+        for (msg, expected) in vectors.iter() {
+            let P = EdwardsPoint::hash_to_curve::<sha2::Sha512>(msg, dst);
+            assert_eq!(hex::encode(P.compress().as_bytes()), *expected);
+            assert!(P.is_torsion_free());
+        }
+
+        // A domain separation tag over 255 bytes is hashed down first.
+        let long_dst = [b'D'; 300];
+        let P = EdwardsPoint::hash_to_curve::<sha2::Sha512>(b"abc", &long_dst);
+        assert!(P.is_torsion_free());
+        assert_ne!(P, EdwardsPoint::hash_to_curve::<sha2::Sha512>(b"abc", dst));
+    }
+
     #[test]
     #[allow(deprecated)]
     #[cfg(all(feature = "alloc", feature = "digest"))]
@@ -0,0 +1,433 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// Copyright (c) 2016-2021 isis lovecruft
+// Copyright (c) 2016-2019 Henry de Valence
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Henry de Valence <hdevalence@hdevalence.ca>
+
+//! Errors which may occur when validating inputs, driving a resumable
+//! computation, or loading its checkpoints.
+
+use core::fmt;
+use core::fmt::Display;
+
+/// Errors which may occur when resuming a step-wise scalar multiplication
+/// from a checkpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum StepError {
+    /// The partial accumulator supplied as a `CompressedEdwardsY` is not
+    /// the encoding of a curve point.
+    CheckpointDecompression,
+    /// The index to resume from is not one the computation can resume
+    /// from: for the double-base loop, neither a NAF digit index (below
+    /// 256) nor the fresh-start value 300; for the constant-time loop, not
+    /// a radix-16 digit index (below 64); for a `MulState`, more digits
+    /// remaining than the computation has.
+    IndexOutOfRange {
+        /// The index that was supplied.
+        index: usize,
+    },
+    /// The checkpoint was written by a build using a different basepoint.
+    BasepointMismatch,
+    /// The number of loop iterations to perform per call is zero, or more
+    /// than there are NAF digits.
+    BudgetOutOfRange {
+        /// The budget that was supplied.
+        budget: usize,
+    },
+    /// A limb of the checkpointed accumulator is too large to be a reduced
+    /// field element limb.
+    CheckpointLimbOutOfRange,
+    /// The checkpointed accumulator has a zero \\(Z\\) coordinate.
+    CheckpointZeroZ,
+    /// The checkpointed accumulator does not satisfy the curve equation.
+    CheckpointNotOnCurve,
+    /// The checkpointed accumulator is a valid point, but not the one the
+    /// computation reaches at the checkpoint's index.
+    CheckpointMismatch,
+}
+
+impl Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            StepError::CheckpointDecompression => {
+                write!(f, "Cannot decompress checkpointed Edwards point")
+            }
+            StepError::IndexOutOfRange { index } => write!(
+                f,
+                "Step index {} is out of range for this computation",
+                index
+            ),
+            StepError::BasepointMismatch => {
+                write!(f, "Checkpoint was written with a different basepoint")
+            }
+            StepError::BudgetOutOfRange { budget } => {
+                write!(f, "Step budget {} is not between 1 and 256", budget)
+            }
+            StepError::CheckpointLimbOutOfRange => {
+                write!(f, "Checkpointed accumulator limb is out of range")
+            }
+            StepError::CheckpointZeroZ => {
+                write!(f, "Checkpointed accumulator has a zero Z coordinate")
+            }
+            StepError::CheckpointNotOnCurve => {
+                write!(f, "Checkpointed accumulator is not on the curve")
+            }
+            StepError::CheckpointMismatch => write!(
+                f,
+                "Checkpointed accumulator does not match the computation's inputs"
+            ),
+        }
+    }
+}
+
+/// Errors which may occur when loading a serialized checkpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BackupError {
+    /// The checkpoint (or the buffer it was to be written to) has the
+    /// wrong length.
+    Length {
+        /// The length the checkpoint format requires.
+        expected: usize,
+        /// The length that was supplied.
+        actual: usize,
+    },
+    /// The trailing checksum does not match the checkpoint contents, e.g.
+    /// because writing the checkpoint was interrupted.
+    Checksum,
+    /// A field element in the checkpoint is not canonically encoded.
+    NonCanonicalField,
+    /// The checkpointed ladder points are not related the way the
+    /// Montgomery ladder keeps them.
+    LadderInvariant,
+    /// The serialized job or checkpoint was written in a format version
+    /// this build does not read.
+    UnsupportedVersion {
+        /// The version byte that was found.
+        version: u8,
+    },
+    /// The serialized job was written by a build using a different
+    /// basepoint.
+    BasepointMismatch,
+    /// The checkpoint's limbs are recorded in a layout other than
+    /// [`Checkpoint::BACKEND_ID`](crate::resumable::Checkpoint::BACKEND_ID).
+    BackendMismatch {
+        /// The layout identifier this build reads.
+        expected: u8,
+        /// The layout identifier recorded in the checkpoint.
+        actual: u8,
+    },
+    /// A field of the serialized job does not decode, e.g. a non-canonical
+    /// scalar or a point which is not on the curve.
+    Malformed,
+}
+
+impl Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BackupError::Length { expected, actual } => write!(
+                f,
+                "Checkpoint must be {} bytes in length, got {}",
+                expected, actual
+            ),
+            BackupError::Checksum => write!(f, "Checkpoint checksum mismatch"),
+            BackupError::NonCanonicalField => {
+                write!(f, "Checkpoint field element is not canonically encoded")
+            }
+            BackupError::LadderInvariant => {
+                write!(
+                    f,
+                    "Checkpoint ladder points do not differ by the input point"
+                )
+            }
+            BackupError::UnsupportedVersion { version } => {
+                write!(f, "Unsupported serialization format version {}", version)
+            }
+            BackupError::BasepointMismatch => {
+                write!(f, "Serialized job was written with a different basepoint")
+            }
+            BackupError::BackendMismatch { expected, actual } => write!(
+                f,
+                "Checkpoint limbs use layout {}, expected {}",
+                actual, expected
+            ),
+            BackupError::Malformed => write!(f, "Serialized job field does not decode"),
+        }
+    }
+}
+
+/// Errors which may occur when a validated entry point rejects its inputs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum InputError {
+    /// The input point is the identity.
+    IdentityPoint,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            InputError::IdentityPoint => write!(f, "Input point is the identity"),
+        }
+    }
+}
+
+/// Errors which may occur when the inputs to a multiscalar multiplication
+/// do not fit together.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MultiscalarError {
+    /// There is not exactly one point per scalar.
+    LengthMismatch {
+        /// The number of scalars supplied.
+        scalars: usize,
+        /// The number of points supplied.
+        points: usize,
+    },
+    /// A compressed input point does not decode.
+    InvalidPoint {
+        /// The position of the offending term in the input.
+        index: usize,
+    },
+    /// The requested Pippenger window is not one the algorithm supports.
+    WindowOutOfRange {
+        /// The window, in bits, that was requested.
+        window: usize,
+    },
+    /// A resumable multiscalar multiplication has more terms than its
+    /// stored state can count.
+    TooManyTerms {
+        /// The number of terms supplied.
+        terms: usize,
+    },
+    /// A step-wise multiscalar multiplication cannot resume from the
+    /// supplied checkpoint.
+    Step(StepError),
+}
+
+impl Display for MultiscalarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            MultiscalarError::LengthMismatch { scalars, points } => write!(
+                f,
+                "Multiscalar multiplication needs one point per scalar, got {} scalars and {} points",
+                scalars, points
+            ),
+            MultiscalarError::InvalidPoint { index } => write!(
+                f,
+                "Multiscalar input {} is not a valid point encoding",
+                index
+            ),
+            MultiscalarError::WindowOutOfRange { window } => write!(
+                f,
+                "Pippenger window of {} bits is outside the supported 4 to 8",
+                window
+            ),
+            MultiscalarError::TooManyTerms { terms } => write!(
+                f,
+                "Resumable multiscalar multiplication supports at most {} terms, got {}",
+                u32::MAX,
+                terms
+            ),
+            MultiscalarError::Step(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Errors which may occur when driving a step-wise scalar multiplication
+/// through a [`CheckpointStore`](crate::resumable::CheckpointStore).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum StoreError<E> {
+    /// The store failed to save or load a checkpoint.
+    Store(E),
+    /// The stored checkpoint could not be resumed from.
+    Step(StepError),
+}
+
+impl<E: Display> Display for StoreError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Store(err) => write!(f, "Checkpoint store failed: {}", err),
+            StoreError::Step(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Errors which may occur when loading a serialized table of precomputed
+/// points.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TableError {
+    /// The input is not the length of a table encoding.
+    Length {
+        /// The length a table encoding has.
+        expected: usize,
+        /// The length of the input.
+        actual: usize,
+    },
+    /// An entry has a non-canonical coordinate or is not a point on the curve.
+    InvalidEntry {
+        /// The position of the entry in the table.
+        index: usize,
+    },
+    /// The entries are valid points, but not the multiples the table holds.
+    Inconsistent,
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TableError::Length { expected, actual } => write!(
+                f,
+                "Serialized table must be {} bytes, got {}",
+                expected, actual
+            ),
+            TableError::InvalidEntry { index } => {
+                write!(f, "Serialized table entry {} is not a valid point", index)
+            }
+            TableError::Inconsistent => {
+                write!(f, "Serialized table entries are not consistent multiples")
+            }
+        }
+    }
+}
+
+/// Errors which may occur when interpolating a polynomial from its values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum InterpolationError {
+    /// There is not exactly one value, or one output, per \\(x\\)-coordinate.
+    LengthMismatch {
+        /// The number of \\(x\\)-coordinates supplied.
+        xs: usize,
+        /// The number of values, or outputs, supplied.
+        values: usize,
+    },
+    /// Two of the \\(x\\)-coordinates are equal.
+    DuplicateX,
+    /// The \\(x\\)-coordinate whose coefficient was asked for is not in the set.
+    MissingX,
+}
+
+impl Display for InterpolationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            InterpolationError::LengthMismatch { xs, values } => write!(
+                f,
+                "Interpolation needs one value per x-coordinate, got {} x-coordinates and {} values",
+                xs, values
+            ),
+            InterpolationError::DuplicateX => write!(f, "Interpolation x-coordinates are not distinct"),
+            InterpolationError::MissingX => {
+                write!(f, "The x-coordinate is not one of the interpolation points")
+            }
+        }
+    }
+}
+
+/// Errors which may occur when replaying the audit log of a step-wise
+/// computation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AuditError {
+    /// A record does not start at the step where the previous one ended,
+    /// or the first record does not start at the beginning.
+    OutOfOrder {
+        /// The position of the record in the log.
+        record: usize,
+    },
+    /// A record's accumulator digest differs from the one the replay
+    /// computed, so the computation it logs was not driven faithfully.
+    AccumulatorMismatch {
+        /// The position of the record in the log.
+        record: usize,
+    },
+    /// The log ends before the computation is complete.
+    Incomplete,
+}
+
+impl Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AuditError::OutOfOrder { record } => write!(
+                f,
+                "Audit record {} does not follow on from the previous record",
+                record
+            ),
+            AuditError::AccumulatorMismatch { record } => write!(
+                f,
+                "Audit record {} does not match the replayed accumulator",
+                record
+            ),
+            AuditError::Incomplete => write!(f, "Audit log ends before the computation completes"),
+        }
+    }
+}
+
+/// Errors which may occur when computing or loading a non-adjacent form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum NafError {
+    /// The width is not between 2 and 8.
+    WidthOutOfRange {
+        /// The width that was supplied.
+        width: usize,
+    },
+    /// A digit violates the non-adjacent form conditions for the width.
+    InvalidDigit {
+        /// The index of the digit, least significant first.
+        index: usize,
+    },
+}
+
+impl Display for NafError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            NafError::WidthOutOfRange { width } => {
+                write!(f, "NAF width {} is not between 2 and 8", width)
+            }
+            NafError::InvalidDigit { index } => {
+                write!(f, "NAF digit {} is not valid for the width", index)
+            }
+        }
+    }
+}
+
+/// Errors which may occur when running a job through a
+/// `resumable::CheckpointManager`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ManagerError<E> {
+    /// The checkpoint store failed to save or load a checkpoint.
+    Store(E),
+    /// The stored checkpoint could not be resumed from.
+    Step(StepError),
+    /// No job was started with this id.
+    UnknownJob {
+        /// The id that was supplied.
+        id: u64,
+    },
+    /// A job was already started with this id.
+    DuplicateJob {
+        /// The id that was supplied.
+        id: u64,
+    },
+    /// The inputs do not match those the job was started with.
+    InputMismatch {
+        /// The id of the job.
+        id: u64,
+    },
+}
+
+impl<E: Display> Display for ManagerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManagerError::Store(err) => write!(f, "Checkpoint store failed: {}", err),
+            ManagerError::Step(err) => write!(f, "{}", err),
+            ManagerError::UnknownJob { id } => write!(f, "No job was started with id {}", id),
+            ManagerError::DuplicateJob { id } => {
+                write!(f, "A job was already started with id {}", id)
+            }
+            ManagerError::InputMismatch { id } => {
+                write!(f, "Inputs do not match those job {} was started with", id)
+            }
+        }
+    }
+}
@@ -204,6 +204,8 @@ impl FieldElement {
     #[rustfmt::skip] // keep alignment of explanatory comments
     #[allow(clippy::let_and_return)]
     pub(crate) fn invert(&self) -> FieldElement {
+        count_ops!(FieldInvert);
+
         // The bits of p-2 = 2^255 -19 -2 are 11010111111...11.
         //
         //                                 nonzero bits of exponent
@@ -0,0 +1,168 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Building blocks of the hash-to-curve standard, [RFC 9380].
+//!
+//! The public entry points are [`EdwardsPoint::hash_to_curve`] and
+//! [`RistrettoPoint::hash_to_group`].
+//!
+//! [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+//! [`EdwardsPoint::hash_to_curve`]: crate::edwards::EdwardsPoint::hash_to_curve
+//! [`RistrettoPoint::hash_to_group`]: crate::ristretto::RistrettoPoint::hash_to_group
+
+#![allow(non_snake_case)]
+
+use digest::crypto_common::BlockSizeUser;
+use digest::generic_array::typenum::U64;
+use digest::generic_array::GenericArray;
+use digest::Digest;
+
+use subtle::{ConditionallyNegatable, ConditionallySelectable};
+
+use crate::constants::MONTGOMERY_A;
+use crate::edwards::EdwardsPoint;
+use crate::field::FieldElement;
+
+/// Fill `out` with `expand_message_xmd(msg, dst, out.len())` as specified
+/// in [RFC 9380 section 5.3.1][xmd], using the hash function `D`.
+///
+/// A domain separation tag longer than 255 bytes is first hashed down, as
+/// the RFC requires.
+///
+/// # Panics
+///
+/// Panics if `out` is longer than 255 digests, the most the construction
+/// can produce.
+///
+/// [xmd]: https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3.1
+pub(crate) fn expand_message_xmd<D>(msg: &[u8], dst: &[u8], out: &mut [u8])
+where
+    D: Digest<OutputSize = U64> + BlockSizeUser + Default,
+{
+    assert!(out.len() <= 255 * 64, "expand_message_xmd output too long");
+
+    let hashed_dst;
+    let dst = if dst.len() > 255 {
+        hashed_dst = D::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize();
+        hashed_dst.as_slice()
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let z_pad = GenericArray::<u8, D::BlockSize>::default();
+    let b_0 = D::new()
+        .chain_update(z_pad)
+        .chain_update(msg)
+        .chain_update((out.len() as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    // b_i = H((b_0 xor b_{i-1}) || i || DST_prime), with b_1 = H(b_0 || 1 || DST_prime)
+    let mut b_i = GenericArray::<u8, U64>::default();
+    for (i, chunk) in out.chunks_mut(64).enumerate() {
+        for (b, b0) in b_i.iter_mut().zip(b_0.iter()) {
+            *b ^= b0;
+        }
+        b_i = D::new()
+            .chain_update(b_i)
+            .chain_update([i as u8 + 1])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        chunk.copy_from_slice(&b_i[..chunk.len()]);
+    }
+}
+
+/// Reduce the 48-byte big-endian integer `bytes` modulo \\(p\\), as
+/// `hash_to_field` does with each of its \\(L = 48\\)-byte strings.
+fn field_from_be_bytes(bytes: &[u8]) -> FieldElement {
+    debug_assert_eq!(bytes.len(), 48);
+    let mut le = [0u8; 48];
+    for (l, b) in le.iter_mut().zip(bytes.iter().rev()) {
+        *l = *b;
+    }
+
+    // Split into the low 248 bits and the high 136 bits, so that neither
+    // half loses its top bit to `from_bytes`.
+    let mut lo = [0u8; 32];
+    lo[..31].copy_from_slice(&le[..31]);
+    let mut hi = [0u8; 32];
+    hi[..17].copy_from_slice(&le[31..]);
+    let mut two_248 = [0u8; 32];
+    two_248[31] = 1;
+
+    &FieldElement::from_bytes(&lo)
+        + &(&FieldElement::from_bytes(&hi) * &FieldElement::from_bytes(&two_248))
+}
+
+/// Hash `msg` to two field elements, as `hash_to_field(msg, 2)` with
+/// `expand_message_xmd` and \\(L = 48\\).
+pub(crate) fn hash_to_field<D>(msg: &[u8], dst: &[u8]) -> [FieldElement; 2]
+where
+    D: Digest<OutputSize = U64> + BlockSizeUser + Default,
+{
+    let mut uniform_bytes = [0u8; 96];
+    expand_message_xmd::<D>(msg, dst, &mut uniform_bytes);
+    [
+        field_from_be_bytes(&uniform_bytes[..48]),
+        field_from_be_bytes(&uniform_bytes[48..]),
+    ]
+}
+
+/// Map a field element to a point of edwards25519 with the Elligator 2
+/// map to curve25519 of [RFC 9380 section 6.7.1][ell2], followed by the
+/// rational map of [section 6.8.2][map].
+///
+/// The result is not in the prime-order subgroup; the caller clears the
+/// cofactor.
+///
+/// [ell2]: https://www.rfc-editor.org/rfc/rfc9380.html#section-6.7.1
+/// [map]: https://www.rfc-editor.org/rfc/rfc9380.html#section-6.8.2
+pub(crate) fn map_to_curve_elligator2(u: &FieldElement) -> EdwardsPoint {
+    let one = FieldElement::ONE;
+    let J = MONTGOMERY_A;
+    let minus_J = -&J;
+
+    // x1 = -J / (1 + 2u^2), or -J if the denominator is zero
+    let mut x1 = &minus_J * &(&u.square2() + &one).invert();
+    x1.conditional_assign(&minus_J, x1.is_zero());
+    let x2 = &(-&x1) - &J;
+
+    // g(x) = x^3 + J x^2 + x
+    let g = |x: &FieldElement| x * &(&(&x.square() + &(&J * x)) + &one);
+
+    let (gx1_is_square, mut y1) = FieldElement::sqrt_ratio_i(&g(&x1), &one);
+    let (_, y2) = FieldElement::sqrt_ratio_i(&g(&x2), &one);
+
+    // `sqrt_ratio_i` returns the nonnegative root, i.e. sgn0(y) = 0; the
+    // root paired with x1 must have sgn0(y) = 1.
+    y1.conditional_negate(!y1.is_negative());
+
+    let s = FieldElement::conditional_select(&x2, &x1, gx1_is_square);
+    let t = FieldElement::conditional_select(&y2, &y1, gx1_is_square);
+
+    // (x, y) = (c1 s / t, (s - 1) / (s + 1)), with c1 = sqrt(-486664) and
+    // sgn0(c1) = 0, or the identity if either denominator is zero.
+    let (_, c1) = FieldElement::sqrt_ratio_i(&(&minus_J - &(&one + &one)), &one);
+    let s_plus_one = &s + &one;
+    let exceptional = t.is_zero() | s_plus_one.is_zero();
+    let mut x = &(&c1 * &s) * &t.invert();
+    let mut y = &(&s - &one) * &s_plus_one.invert();
+    x.conditional_assign(&FieldElement::ZERO, exceptional);
+    y.conditional_assign(&one, exceptional);
+
+    EdwardsPoint {
+        X: x,
+        Y: y,
+        Z: one,
+        T: &x * &y,
+    }
+}
@@ -0,0 +1,326 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Arithmetic in the base field of Curve25519, for building new encodings
+//! and maps on top of the curve.
+//!
+//! # Warning
+//!
+//! This module is for protocol designers who need field arithmetic, e.g.
+//! for an Elligator variant or a custom point encoding, and know how to
+//! use it safely.  Most users want [`EdwardsPoint`], [`RistrettoPoint`] or
+//! [`MontgomeryPoint`] instead.  Nothing here validates that values are
+//! coordinates of points on the curve.
+//!
+//! Unlike the crate's internal field type, whose representation and
+//! limb bounds depend on the backend, [`FieldElement`] keeps its limbs
+//! reduced after every operation, so any sequence of operations is safe,
+//! and is only ever encoded canonically.  All operations are constant
+//! time.
+//!
+//! ```
+//! use curve25519_dalek::hazmat::FieldElement;
+//!
+//! let two = FieldElement::from(2u64);
+//! let (is_square, root) = FieldElement::sqrt_ratio_i(&FieldElement::ONE, &two);
+//! assert!(!bool::from(is_square));
+//! assert_eq!(root.square() * two, FieldElement::SQRT_M1);
+//! ```
+//!
+//! [`EdwardsPoint`]: crate::edwards::EdwardsPoint
+//! [`RistrettoPoint`]: crate::ristretto::RistrettoPoint
+//! [`MontgomeryPoint`]: crate::montgomery::MontgomeryPoint
+
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::constants;
+use crate::field;
+
+/// An element of the field \\( \mathbb Z / (2\^{255} - 19)\\).
+#[derive(Copy, Clone)]
+pub struct FieldElement(pub(crate) field::FieldElement);
+
+impl FieldElement {
+    /// The element \\(0\\).
+    pub const ZERO: FieldElement = FieldElement(field::FieldElement::ZERO);
+
+    /// The element \\(1\\).
+    pub const ONE: FieldElement = FieldElement(field::FieldElement::ONE);
+
+    /// The element \\(-1\\).
+    pub const MINUS_ONE: FieldElement = FieldElement(field::FieldElement::MINUS_ONE);
+
+    /// The nonnegative square root of \\(-1\\).
+    pub const SQRT_M1: FieldElement = FieldElement(constants::SQRT_M1);
+
+    /// Decode the canonical little-endian encoding of a field element.
+    ///
+    /// Returns `None` if `bytes` encodes an integer of \\(p\\) or more,
+    /// including any encoding with the high bit set.
+    pub fn from_canonical_bytes(bytes: [u8; 32]) -> CtOption<FieldElement> {
+        let fe = field::FieldElement::from_bytes(&bytes);
+        CtOption::new(FieldElement(fe), fe.as_bytes().ct_eq(&bytes))
+    }
+
+    /// Decode a little-endian integer, ignoring its high bit, and reduce
+    /// it modulo \\(p\\).
+    ///
+    /// This is how [RFC 7748] decodes Montgomery \\(u\\)-coordinates.
+    ///
+    /// [RFC 7748]: https://www.rfc-editor.org/rfc/rfc7748.html#section-5
+    pub fn from_bytes_mod_order(bytes: [u8; 32]) -> FieldElement {
+        FieldElement(field::FieldElement::from_bytes(&bytes))
+    }
+
+    /// The canonical little-endian encoding of this field element.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.as_bytes()
+    }
+
+    /// Whether this field element is negative, in the sense of the
+    /// Ed25519 paper: its canonical encoding has the low bit set.
+    pub fn is_negative(&self) -> Choice {
+        self.0.is_negative()
+    }
+
+    /// Whether this field element is zero.
+    pub fn is_zero(&self) -> Choice {
+        self.0.is_zero()
+    }
+
+    /// Compute the square of this field element.
+    pub fn square(&self) -> FieldElement {
+        FieldElement(self.0.square())
+    }
+
+    /// Compute the inverse of this field element, or zero if it is zero.
+    pub fn invert(&self) -> FieldElement {
+        FieldElement(self.0.invert())
+    }
+
+    /// Compute the nonnegative square root of this field element, if it is
+    /// a square.
+    pub fn sqrt(&self) -> CtOption<FieldElement> {
+        let (is_square, root) = FieldElement::sqrt_ratio_i(self, &FieldElement::ONE);
+        CtOption::new(root, is_square)
+    }
+
+    /// Compute the nonnegative square root of \\(u/v\\), or of
+    /// \\(i u/v\\) where \\(i = \sqrt{-1}\\) if \\(u/v\\) is not a square.
+    ///
+    /// The returned `Choice` is set iff \\(u/v\\) is a square, with the
+    /// exception that \\(v = 0\\) gives `(u == 0, 0)`.  This is the
+    /// `SQRT_RATIO_M1` function of the [Ristretto specification].
+    ///
+    /// [Ristretto specification]: https://www.rfc-editor.org/rfc/rfc9496.html#section-4.2
+    pub fn sqrt_ratio_i(u: &FieldElement, v: &FieldElement) -> (Choice, FieldElement) {
+        let (is_square, root) = field::FieldElement::sqrt_ratio_i(&u.0, &v.0);
+        (is_square, FieldElement(root))
+    }
+}
+
+impl From<u64> for FieldElement {
+    fn from(x: u64) -> FieldElement {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&x.to_le_bytes());
+        FieldElement::from_bytes_mod_order(bytes)
+    }
+}
+
+impl Default for FieldElement {
+    fn default() -> FieldElement {
+        FieldElement::ZERO
+    }
+}
+
+impl Debug for FieldElement {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "FieldElement({:?})", self.to_bytes())
+    }
+}
+
+impl ConstantTimeEq for FieldElement {
+    fn ct_eq(&self, other: &FieldElement) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl PartialEq for FieldElement {
+    fn eq(&self, other: &FieldElement) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for FieldElement {}
+
+impl ConditionallySelectable for FieldElement {
+    fn conditional_select(a: &FieldElement, b: &FieldElement, choice: Choice) -> FieldElement {
+        FieldElement(field::FieldElement::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for FieldElement {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<'a, 'b> Add<&'b FieldElement> for &'a FieldElement {
+    type Output = FieldElement;
+    fn add(self, rhs: &'b FieldElement) -> FieldElement {
+        // Subtraction reduces the limbs, which addition leaves loose on
+        // some backends, so that sums can be chained indefinitely.
+        FieldElement(&(&self.0 + &rhs.0) - &field::FieldElement::ZERO)
+    }
+}
+
+define_add_variants!(
+    LHS = FieldElement,
+    RHS = FieldElement,
+    Output = FieldElement
+);
+
+impl<'b> AddAssign<&'b FieldElement> for FieldElement {
+    fn add_assign(&mut self, rhs: &'b FieldElement) {
+        *self = (self as &FieldElement) + rhs;
+    }
+}
+
+define_add_assign_variants!(LHS = FieldElement, RHS = FieldElement);
+
+impl<'a, 'b> Sub<&'b FieldElement> for &'a FieldElement {
+    type Output = FieldElement;
+    fn sub(self, rhs: &'b FieldElement) -> FieldElement {
+        FieldElement(&self.0 - &rhs.0)
+    }
+}
+
+define_sub_variants!(
+    LHS = FieldElement,
+    RHS = FieldElement,
+    Output = FieldElement
+);
+
+impl<'b> SubAssign<&'b FieldElement> for FieldElement {
+    fn sub_assign(&mut self, rhs: &'b FieldElement) {
+        *self = (self as &FieldElement) - rhs;
+    }
+}
+
+define_sub_assign_variants!(LHS = FieldElement, RHS = FieldElement);
+
+impl<'a, 'b> Mul<&'b FieldElement> for &'a FieldElement {
+    type Output = FieldElement;
+    fn mul(self, rhs: &'b FieldElement) -> FieldElement {
+        FieldElement(&self.0 * &rhs.0)
+    }
+}
+
+define_mul_variants!(
+    LHS = FieldElement,
+    RHS = FieldElement,
+    Output = FieldElement
+);
+
+impl<'b> MulAssign<&'b FieldElement> for FieldElement {
+    fn mul_assign(&mut self, rhs: &'b FieldElement) {
+        *self = (self as &FieldElement) * rhs;
+    }
+}
+
+define_mul_assign_variants!(LHS = FieldElement, RHS = FieldElement);
+
+impl<'a> Neg for &'a FieldElement {
+    type Output = FieldElement;
+    fn neg(self) -> FieldElement {
+        FieldElement(-&self.0)
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = FieldElement;
+    fn neg(self) -> FieldElement {
+        -&self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The encoding of \\(p = 2^{255} - 19\\).
+    const P_BYTES: [u8; 32] = [
+        0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ];
+
+    #[test]
+    fn canonical_encoding() {
+        let x = FieldElement::from(0x0123_4567_89ab_cdef_u64);
+        assert_eq!(FieldElement::from_canonical_bytes(x.to_bytes()).unwrap(), x);
+
+        // p - 1 is the largest canonical encoding
+        let mut bytes = P_BYTES;
+        bytes[0] -= 1;
+        assert_eq!(
+            FieldElement::from_canonical_bytes(bytes).unwrap(),
+            FieldElement::MINUS_ONE
+        );
+
+        // p itself and anything with the high bit set are rejected
+        assert!(bool::from(
+            FieldElement::from_canonical_bytes(P_BYTES).is_none()
+        ));
+        let mut high = FieldElement::ONE.to_bytes();
+        high[31] |= 0x80;
+        assert!(bool::from(
+            FieldElement::from_canonical_bytes(high).is_none()
+        ));
+
+        // but reduced, or masked, by the lenient decoding
+        assert_eq!(
+            FieldElement::from_bytes_mod_order(P_BYTES),
+            FieldElement::ZERO
+        );
+        assert_eq!(FieldElement::from_bytes_mod_order(high), FieldElement::ONE);
+    }
+
+    #[test]
+    fn chained_operations_stay_reduced() {
+        let x = FieldElement::MINUS_ONE;
+        let mut sum = FieldElement::ZERO;
+        let mut difference = FieldElement::ZERO;
+        for _ in 0..10_000 {
+            sum += x;
+            difference -= x;
+        }
+        assert_eq!(sum, -FieldElement::from(10_000u64));
+        assert_eq!(difference, FieldElement::from(10_000u64));
+        assert_eq!(sum * difference, -FieldElement::from(100_000_000u64));
+    }
+
+    #[test]
+    fn invert_and_sqrt() {
+        let x = FieldElement::from(12345u64);
+        assert_eq!(x * x.invert(), FieldElement::ONE);
+        assert_eq!(FieldElement::ZERO.invert(), FieldElement::ZERO);
+
+        let root = x.square().sqrt().unwrap();
+        assert_eq!(root.square(), x.square());
+        assert!(!bool::from(root.is_negative()));
+
+        assert_eq!(FieldElement::SQRT_M1.square(), FieldElement::MINUS_ONE);
+        // 2 is not a square mod p
+        assert!(bool::from(FieldElement::from(2u64).sqrt().is_none()));
+    }
+}
@@ -53,7 +53,7 @@
 extern crate alloc;
 
 // TODO: move std-dependent tests to `tests/`
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 #[macro_use]
 extern crate std;
 
@@ -86,6 +86,27 @@ pub mod constants;
 // External (and internal) traits.
 pub mod traits;
 
+// Errors for the resumable computation API
+pub mod errors;
+
+// Checkpointing helpers for step-wise scalar multiplication
+pub mod resumable;
+
+// Polynomial evaluation and Lagrange interpolation over the scalar field
+pub mod polynomial;
+
+// Field arithmetic for building new encodings and maps
+#[cfg(feature = "hazmat")]
+pub mod hazmat;
+
+// Byte-oriented step-wise multiplication for WebAssembly hosts
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// Per-thread counts of field and point operations
+#[cfg(feature = "op-counts")]
+pub mod op_counts;
+
 //------------------------------------------------------------------------
 // curve25519-dalek internal modules
 //------------------------------------------------------------------------
@@ -102,6 +123,18 @@ pub(crate) mod backend;
 // Generic code for window lookups
 pub(crate) mod window;
 
+// Serde support for encodings made of fixed-size chunks
+#[cfg(feature = "serde")]
+pub(crate) mod serde_encoding;
+
+// Hashing to the curve as specified by RFC 9380
+#[cfg(feature = "digest")]
+pub(crate) mod hash_to_curve;
+
+// Checks shared between the test modules of the point types
+#[cfg(all(test, feature = "group"))]
+mod test_support;
+
 pub use crate::{
     edwards::EdwardsPoint, montgomery::MontgomeryPoint, ristretto::RistrettoPoint, scalar::Scalar,
 };
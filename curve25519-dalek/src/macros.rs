@@ -11,6 +11,18 @@
 
 //! Internal macros.
 
+/// Record `n` (by default one) operations of the given `op_counts::Op`
+/// kind when the `op-counts` feature is enabled, and do nothing otherwise.
+macro_rules! count_ops {
+    ($op:ident) => {
+        count_ops!($op, 1)
+    };
+    ($op:ident, $n:expr) => {
+        #[cfg(feature = "op-counts")]
+        $crate::op_counts::record($crate::op_counts::Op::$op, $n as u64);
+    };
+}
+
 /// Define borrow and non-borrow variants of `Add`.
 macro_rules! define_add_variants {
     (LHS = $lhs:ty, RHS = $rhs:ty, Output = $out:ty) => {
@@ -51,15 +51,20 @@
 
 use core::{
     hash::{Hash, Hasher},
-    ops::{Mul, MulAssign},
+    ops::{Mul, MulAssign, Range},
 };
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::constants::{APLUS2_OVER_FOUR, MONTGOMERY_A, MONTGOMERY_A_NEG};
 use crate::edwards::{CompressedEdwardsY, EdwardsPoint};
+use crate::errors::BackupError;
 use crate::field::FieldElement;
 use crate::scalar::{clamp_integer, Scalar};
 
-use crate::traits::Identity;
+use crate::resumable::{clamp_steps, WorkDone};
+use crate::traits::{CostModel, Identity};
 
 use subtle::Choice;
 use subtle::ConstantTimeEq;
@@ -165,33 +170,49 @@ impl MontgomeryPoint {
     /// [here](https://neilmadden.blog/2020/05/28/whats-the-curve25519-clamping-all-about/).
     /// When in doubt, use [`Self::mul_clamped`].
     pub fn mul_bits_be(&self, bits: impl Iterator<Item = bool>) -> MontgomeryPoint {
-        // Algorithm 8 of Costello-Smith 2017
-        let affine_u = FieldElement::from_bytes(&self.0);
-        let mut x0 = ProjectivePoint::identity();
-        let mut x1 = ProjectivePoint {
-            U: affine_u,
-            W: FieldElement::ONE,
-        };
-
-        // Go through the bits from most to least significant, using a sliding window of 2
-        let mut prev_bit = false;
-        for cur_bit in bits {
-            let choice: u8 = (prev_bit ^ cur_bit) as u8;
-
-            debug_assert!(choice == 0 || choice == 1);
-
-            ProjectivePoint::conditional_swap(&mut x0, &mut x1, choice.into());
-            differential_add_and_double(&mut x0, &mut x1, &affine_u);
-
-            prev_bit = cur_bit;
+        let mut ladder = LadderState::init(self);
+        for bit in bits {
+            ladder.process_bit(bit);
         }
-        // The final value of prev_bit above is scalar.bits()[0], i.e., the LSB of scalar
-        ProjectivePoint::conditional_swap(&mut x0, &mut x1, Choice::from(prev_bit as u8));
-        // Don't leave the bit in the stack
-        #[cfg(feature = "zeroize")]
-        prev_bit.zeroize();
+        ladder.finish()
+    }
 
-        x0.as_affine()
+    /// Multiply this point by a secret scalar which is never held in memory
+    /// all at once, running the ladder over its bits as each chunk of it
+    /// arrives.
+    ///
+    /// The chunks are requested as for [`EdwardsPoint::mul_streaming`], and
+    /// the scalar's bytes are likewise taken as an unreduced 256-bit
+    /// integer: unlike [`Self::mul_clamped`], no clamping is applied, so a
+    /// key store holding an unclamped X25519 key should clamp it as it
+    /// hands out the first and last bytes.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the product;
+    /// - `Err(err)` with the first error returned by `chunks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not divide 32.
+    pub fn mul_streaming<const N: usize, E, F>(&self, chunks: F) -> Result<MontgomeryPoint, E>
+    where
+        F: FnMut(Range<usize>, &mut [u8; N]) -> Result<(), E>,
+    {
+        let mut ladder = LadderState::init(self);
+        let streamed = crate::scalar::stream_scalar_bytes(chunks, |byte| {
+            for i in (0..8).rev() {
+                ladder.process_bit((byte >> i) & 1 == 1);
+            }
+        });
+        match streamed {
+            Ok(()) => Ok(ladder.finish()),
+            Err(err) => {
+                #[cfg(feature = "zeroize")]
+                ladder.zeroize();
+                Err(err)
+            }
+        }
     }
 
     /// View this `MontgomeryPoint` as an array of bytes.
@@ -204,6 +225,75 @@ impl MontgomeryPoint {
         self.0
     }
 
+    /// Map an Elligator2 representative to the point it represents.
+    ///
+    /// Every 32-byte string is a representative, and uniformly random
+    /// strings map to points whose distribution is close enough to uniform
+    /// for the strings to hide that they encode points.  The top two bits
+    /// of `representative` are ignored, so that they can be filled with
+    /// random padding by [`MontgomeryPoint::to_elligator_representative`]'s
+    /// callers.
+    ///
+    /// This is the Elligator2 map with \\(Z = 2\\) of [RFC 9380 section
+    /// 6.7.1](https://www.rfc-editor.org/rfc/rfc9380.html#section-6.7.1),
+    /// keeping only the \\(u\\)-coordinate.
+    pub fn from_elligator_representative(representative: &[u8; 32]) -> MontgomeryPoint {
+        let mut r_bytes = *representative;
+        r_bytes[31] &= 0x3f;
+        elligator_encode(&FieldElement::from_bytes(&r_bytes))
+    }
+
+    /// Compute an Elligator2 representative of this point, the inverse of
+    /// [`MontgomeryPoint::from_elligator_representative`].
+    ///
+    /// Each \\(u\\)-coordinate the forward map reaches has up to two
+    /// representatives, one for each sign of the \\(v\\)-coordinate the full
+    /// map to \\((u, v)\\) produces; `v_is_negative` chooses between them.
+    /// A protocol which only transmits \\(u\\) can pick either, e.g. at
+    /// random, and retry with a fresh point when neither exists.
+    ///
+    /// The representative is the root which is at most \\((p - 1)/2\\), so
+    /// its top two bits are zero.  To make the encoding indistinguishable
+    /// from random bytes, the caller must replace them with random bits.
+    ///
+    /// # Return
+    ///
+    /// * `Some(representative)` if the forward map takes some
+    ///   representative to this point with the chosen sign of \\(v\\);
+    ///
+    /// * `None` otherwise, which is the case for about half of the points
+    ///   on the curve, and for every point on the twist.
+    pub fn to_elligator_representative(&self, v_is_negative: bool) -> Option<[u8; 32]> {
+        // The forward map computes d = -A / (1 + 2r^2) and outputs u = d
+        // (with v negative) or u = -d - A (with v nonnegative).  Solving
+        // for r^2 gives -(u + A) / 2u in the first case and -u / 2(u + A)
+        // in the second.
+        let u = FieldElement::from_bytes(&self.0);
+        let u_plus_A = &u + &MONTGOMERY_A;
+        let (num, den) = if v_is_negative {
+            (-&u_plus_A, &u + &u)
+        } else {
+            (-&u, &u_plus_A + &u_plus_A)
+        };
+        let (was_square, mut r) = FieldElement::sqrt_ratio_i(&num, &den);
+
+        // Of the roots r and -r, choose the one at most (p - 1) / 2, which
+        // is the one whose double is even when reduced.
+        r.conditional_negate((&r + &r).is_negative());
+
+        // Map r forward, to reject points on the twist and the exceptional
+        // cases of the formulas above.
+        let (image, image_v_is_negative) = elligator_map(&r);
+        let valid = was_square
+            & image.ct_eq(&u)
+            & image_v_is_negative.ct_eq(&Choice::from(v_is_negative as u8));
+        if valid.into() {
+            Some(r.as_bytes())
+        } else {
+            None
+        }
+    }
+
     /// Attempt to convert to an `EdwardsPoint`, using the supplied
     /// choice of sign for the `EdwardsPoint`.
     ///
@@ -236,30 +326,81 @@ impl MontgomeryPoint {
         // on the twist, not the curve, so we can reject it early.
 
         let u = FieldElement::from_bytes(&self.0);
+        let u_plus_one_inv = (&u + &FieldElement::ONE).invert();
+        edwards_from_u(&u, &u_plus_one_inv, sign)
+    }
 
-        if u == FieldElement::MINUS_ONE {
-            return None;
-        }
+    /// Convert many points with [`MontgomeryPoint::to_edwards`], each
+    /// with its choice of sign, sharing a single field inversion between
+    /// them.
+    ///
+    /// Each conversion still performs the square root of Edwards point
+    /// decompression, so this saves one of the two exponentiations per
+    /// point.  The entry for each point is `None` exactly when
+    /// `to_edwards` would return `None`.
+    ///
+    /// ```
+    /// use curve25519_dalek::montgomery::MontgomeryPoint;
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let points: Vec<(MontgomeryPoint, u8)> = (1..5u64)
+    ///     .map(|i| (MontgomeryPoint::mul_base(&Scalar::from(i)), (i % 2) as u8))
+    ///     .collect();
+    /// let converted = MontgomeryPoint::batch_to_edwards(&points);
+    /// for ((P, sign), Q) in points.iter().zip(converted.iter()) {
+    ///     assert_eq!(*Q, P.to_edwards(*sign));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn batch_to_edwards(points: &[(MontgomeryPoint, u8)]) -> Vec<Option<EdwardsPoint>> {
+        let us: Vec<FieldElement> = points
+            .iter()
+            .map(|(P, _)| FieldElement::from_bytes(&P.0))
+            .collect();
+        let mut inverses: Vec<FieldElement> = us.iter().map(|u| u + &FieldElement::ONE).collect();
+        // Zeros, from u = -1, are left as they are and rejected below
+        FieldElement::batch_invert(&mut inverses);
+
+        points
+            .iter()
+            .zip(us.iter().zip(inverses.iter()))
+            .map(|((_, sign), (u, u_plus_one_inv))| edwards_from_u(u, u_plus_one_inv, *sign))
+            .collect()
+    }
+}
 
-        let one = FieldElement::ONE;
+/// The Edwards point with \\(y = (u - 1)/(u + 1)\\) and the given sign,
+/// given \\(u\\) and the inverse of \\(u + 1\\); see
+/// [`MontgomeryPoint::to_edwards`].
+fn edwards_from_u(
+    u: &FieldElement,
+    u_plus_one_inv: &FieldElement,
+    sign: u8,
+) -> Option<EdwardsPoint> {
+    if *u == FieldElement::MINUS_ONE {
+        return None;
+    }
 
-        let y = &(&u - &one) * &(&u + &one).invert();
+    let y = &(u - &FieldElement::ONE) * u_plus_one_inv;
 
-        let mut y_bytes = y.as_bytes();
-        y_bytes[31] ^= sign << 7;
+    let mut y_bytes = y.as_bytes();
+    y_bytes[31] ^= sign << 7;
 
-        CompressedEdwardsY(y_bytes).decompress()
-    }
+    CompressedEdwardsY(y_bytes).decompress()
 }
 
 /// Perform the Elligator2 mapping to a Montgomery point.
 ///
 /// See <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-10#section-6.7.1>
-//
-// TODO Determine how much of the hash-to-group API should be exposed after the CFRG
-//      draft gets into a more polished/accepted state.
-#[allow(unused)]
 pub(crate) fn elligator_encode(r_0: &FieldElement) -> MontgomeryPoint {
+    let (u, _) = elligator_map(r_0);
+    MontgomeryPoint(u.as_bytes())
+}
+
+/// The Elligator2 map of [`elligator_encode`], also returning whether the
+/// image is \\(d = -A/(1+2r^2)\\) itself rather than \\(-d-A\\), which is
+/// when the full map to \\((u, v)\\) gives a negative \\(v\\).
+fn elligator_map(r_0: &FieldElement) -> (FieldElement, Choice) {
     let one = FieldElement::ONE;
     let d_1 = &one + &r_0.square2(); /* 2r^2 */
 
@@ -278,7 +419,7 @@ pub(crate) fn elligator_encode(r_0: &FieldElement) -> MontgomeryPoint {
     let mut u = &d + &Atemp; /* d, or d+A if nonsquare */
     u.conditional_negate(!eps_is_sq); /* d, or -d-A if nonsquare */
 
-    MontgomeryPoint(u.as_bytes())
+    (u, eps_is_sq)
 }
 
 /// A `ProjectivePoint` holds a point on the projective line
@@ -351,6 +492,9 @@ fn differential_add_and_double(
     Q: &mut ProjectivePoint,
     affine_PmQ: &FieldElement,
 ) {
+    count_ops!(PointAdd);
+    count_ops!(PointDouble);
+
     let t0 = &P.U + &P.W;
     let t1 = &P.U - &P.W;
     let t2 = &Q.U + &Q.W;
@@ -386,6 +530,329 @@ fn differential_add_and_double(
     Q.W = t17;  // W_{Q'} = U_D * 4 (W_P U_Q - U_P W_Q)^2
 }
 
+/// The Montgomery ladder as a state machine, driven one scalar bit at a
+/// time by the caller.
+///
+/// This is the loop of [`MontgomeryPoint::mul_bits_be`] turned inside out,
+/// for callers which need to interleave the ladder with other work, e.g.
+/// waiting for an accelerator between field operations.  Unlike
+/// [`MontgomeryStepState`], the state is kept as field elements rather than
+/// their encodings, so there is no per-call validation or encoding cost,
+/// and it is not meant to be stored.
+///
+/// ```
+/// use curve25519_dalek::montgomery::{LadderState, MontgomeryPoint};
+/// use curve25519_dalek::scalar::Scalar;
+///
+/// let P = MontgomeryPoint::mul_base(&Scalar::from(7u64));
+/// let n = Scalar::from(0b1011u64);
+///
+/// let mut ladder = LadderState::init(&P);
+/// for bit in [true, false, true, true] {
+///     // the caller may do other work between bits
+///     ladder.process_bit(bit);
+/// }
+/// assert_eq!(ladder.finish(), P * n);
+/// ```
+///
+/// The state depends on the bits processed so far, so, like the scalar,
+/// it is secret.
+#[derive(Clone, Debug)]
+pub struct LadderState {
+    affine_u: FieldElement,
+    x0: ProjectivePoint,
+    x1: ProjectivePoint,
+    prev_bit: bool,
+}
+
+impl LadderState {
+    /// Start a ladder computing \\( u\_0(\[n\]P) \\), where `point` is
+    /// \\( u\_0(P) \\), before any bit of \\(n\\) has been processed.
+    pub fn init(point: &MontgomeryPoint) -> LadderState {
+        // Algorithm 8 of Costello-Smith 2017
+        let affine_u = FieldElement::from_bytes(&point.0);
+        LadderState {
+            affine_u,
+            x0: ProjectivePoint::identity(),
+            x1: ProjectivePoint {
+                U: affine_u,
+                W: FieldElement::ONE,
+            },
+            prev_bit: false,
+        }
+    }
+
+    /// Process the next bit of \\(n\\), from most to least significant.
+    ///
+    /// This costs one conditional swap and one differential addition and
+    /// doubling, and runs in constant time whatever the bit.
+    pub fn process_bit(&mut self, bit: bool) {
+        // The swap for each bit is deferred to the next, using a sliding
+        // window of 2, so only the change between bits is swapped on
+        let choice: u8 = (self.prev_bit ^ bit) as u8;
+
+        debug_assert!(choice == 0 || choice == 1);
+
+        ProjectivePoint::conditional_swap(&mut self.x0, &mut self.x1, choice.into());
+        differential_add_and_double(&mut self.x0, &mut self.x1, &self.affine_u);
+
+        self.prev_bit = bit;
+    }
+
+    /// Apply the pending swap and return \\( u\_0(\[n\]P) \\) for the bits
+    /// of \\(n\\) processed so far.
+    pub fn finish(mut self) -> MontgomeryPoint {
+        // The final value of prev_bit is the LSB of the scalar
+        ProjectivePoint::conditional_swap(
+            &mut self.x0,
+            &mut self.x1,
+            Choice::from(self.prev_bit as u8),
+        );
+        let result = self.x0.as_affine();
+        // Don't leave the state in the stack
+        #[cfg(feature = "zeroize")]
+        self.zeroize();
+
+        result
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for LadderState {
+    fn zeroize(&mut self) {
+        self.affine_u.zeroize();
+        self.x0.U.zeroize();
+        self.x0.W.zeroize();
+        self.x1.U.zeroize();
+        self.x1.W.zeroize();
+        self.prev_bit.zeroize();
+    }
+}
+
+/// A snapshot of the Montgomery ladder part-way through a multiplication.
+///
+/// The ladder keeps two projective points \\( (X\_2 : Z\_2) \\) and
+/// \\( (X\_3 : Z\_3) \\) whose difference is the input point \\( u \\).
+/// Each coordinate is stored as the canonical little-endian encoding of a
+/// field element.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct MontgomeryLadderCheckpoint {
+    /// The \\(u\\)-coordinate of the point being multiplied.
+    pub u: MontgomeryPoint,
+    /// The \\(X\\)-coordinate of the first ladder point.
+    pub x2: [u8; 32],
+    /// The \\(Z\\)-coordinate of the first ladder point.
+    pub z2: [u8; 32],
+    /// The \\(X\\)-coordinate of the second ladder point.
+    pub x3: [u8; 32],
+    /// The \\(Z\\)-coordinate of the second ladder point.
+    pub z3: [u8; 32],
+}
+
+/// Check that a Montgomery ladder checkpoint could have been produced by
+/// the ladder, before resuming from it.
+///
+/// Every coordinate must be a canonical field element encoding, neither
+/// ladder point may be \\( (0 : 0) \\), and the two points must differ by
+/// \\( u \\). The last check uses the biquadratic relation between the
+/// \\(u\\)-coordinates of \\( P \\), \\( Q \\) and \\( P - Q \\),
+/// $$
+///     u^2 (X\_2 Z\_3 - X\_3 Z\_2)^2
+///     - 2u \big( (X\_2 X\_3 + Z\_2 Z\_3)(X\_2 Z\_3 + X\_3 Z\_2) + 2A X\_2 X\_3 Z\_2 Z\_3 \big)
+///     + (X\_2 X\_3 - Z\_2 Z\_3)^2 = 0,
+/// $$
+/// which costs a handful of field multiplications and no inversions.
+///
+/// This catches corrupted or truncated-and-padded checkpoints; it cannot
+/// tell which step of the ladder a valid checkpoint belongs to.
+pub fn validate_montgomery_checkpoint(
+    state: &MontgomeryLadderCheckpoint,
+) -> Result<(), BackupError> {
+    let decode = |bytes: &[u8; 32]| {
+        let fe = FieldElement::from_bytes(bytes);
+        if fe.as_bytes() == *bytes {
+            Ok(fe)
+        } else {
+            Err(BackupError::NonCanonicalField)
+        }
+    };
+    let X2 = decode(&state.x2)?;
+    let Z2 = decode(&state.z2)?;
+    let X3 = decode(&state.x3)?;
+    let Z3 = decode(&state.z3)?;
+
+    if bool::from(X2.is_zero() & Z2.is_zero()) || bool::from(X3.is_zero() & Z3.is_zero()) {
+        return Err(BackupError::LadderInvariant);
+    }
+
+    let u = FieldElement::from_bytes(&state.u.0);
+    let X2X3 = &X2 * &X3;
+    let Z2Z3 = &Z2 * &Z3;
+    let X2Z3 = &X2 * &Z3;
+    let X3Z2 = &X3 * &Z2;
+
+    let t0 = &u.square() * &(&X2Z3 - &X3Z2).square();
+    let t1 = &(&X2X3 + &Z2Z3) * &(&X2Z3 + &X3Z2);
+    let t2 = &MONTGOMERY_A * &(&X2X3 * &Z2Z3);
+    let t3 = &u * &(&t1 + &(&t2 + &t2));
+    let t4 = (&X2X3 - &Z2Z3).square();
+    let relation = &(&t0 - &(&t3 + &t3)) + &t4;
+
+    if bool::from(relation.is_zero()) {
+        Ok(())
+    } else {
+        Err(BackupError::LadderInvariant)
+    }
+}
+
+/// The state of a step-wise Montgomery ladder multiplication between calls
+/// to [`MontgomeryPoint::step_mul`] or [`MontgomeryPoint::step_mul_clamped`].
+///
+/// The ladder processes the scalar's bits 254 down to 0, one
+/// differential-add-and-double per bit, deferring each conditional swap to
+/// the next bit as [`MontgomeryPoint::mul_bits_be`] does.
+///
+/// Like the points themselves, `swap` is derived from the secret scalar, so
+/// a stored state must be protected as carefully as the scalar.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MontgomeryStepState {
+    /// The ladder points \\( (X\_2 : Z\_2) \\) and \\( (X\_3 : Z\_3) \\), and
+    /// the point being multiplied.
+    pub ladder: MontgomeryLadderCheckpoint,
+    /// The number of scalar bits still to process: the next bit processed is
+    /// bit `bit_index - 1`.  A fresh state starts at 255, and a completed
+    /// one is at 0.
+    pub bit_index: u16,
+    /// The last bit processed, i.e. the conditional swap still pending.
+    pub swap: bool,
+}
+
+impl MontgomeryStepState {
+    /// Start a step-wise multiplication of `point`.
+    pub fn new(point: &MontgomeryPoint) -> MontgomeryStepState {
+        MontgomeryStepState {
+            ladder: MontgomeryLadderCheckpoint {
+                u: *point,
+                x2: FieldElement::ONE.as_bytes(),
+                z2: FieldElement::ZERO.as_bytes(),
+                x3: FieldElement::from_bytes(&point.0).as_bytes(),
+                z3: FieldElement::ONE.as_bytes(),
+            },
+            bit_index: 255,
+            swap: false,
+        }
+    }
+
+    /// Whether every scalar bit has been processed.
+    pub fn is_done(&self) -> bool {
+        self.bit_index == 0
+    }
+}
+
+/// The cost of finishing the ladder from this state: each remaining bit is
+/// one step, costing one doubling and one differential addition.
+impl CostModel for MontgomeryStepState {
+    fn total_steps(&self) -> usize {
+        self.bit_index as usize
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        let steps = clamp_steps(steps, self.total_steps()).len();
+        WorkDone {
+            doublings: steps,
+            additions: steps,
+        }
+    }
+}
+
+impl MontgomeryPoint {
+    /// Perform up to `budget` steps of the Montgomery ladder computing
+    /// \\( u\_0(\[n\]P) \\) for the `Scalar` \\(n\\), where `state` was
+    /// started with [`MontgomeryStepState::new`] on \\( u\_0(P) \\).
+    ///
+    /// Each step processes one scalar bit in constant time, so the work per
+    /// call depends only on `budget` and on how many bits remain.  Pass
+    /// the same scalar on every call; `state` is updated in place and can
+    /// be stored between calls.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(None)` if bits remain to be processed;
+    /// - `Ok(Some(point))` with the product once the ladder is complete,
+    ///   also on any later call;
+    /// - `Err(BackupError::NonCanonicalField)` or
+    ///   `Err(BackupError::LadderInvariant)` if `state.ladder` fails
+    ///   [`validate_montgomery_checkpoint`];
+    /// - `Err(BackupError::Malformed)` if `state.bit_index` is above 255.
+    pub fn step_mul(
+        scalar: &Scalar,
+        state: &mut MontgomeryStepState,
+        budget: usize,
+    ) -> Result<Option<MontgomeryPoint>, BackupError> {
+        step_ladder(&scalar.bytes, state, budget)
+    }
+
+    /// Like [`MontgomeryPoint::step_mul`], but multiplying by
+    /// `clamp_integer(bytes)`, as [`MontgomeryPoint::mul_clamped`] does.
+    pub fn step_mul_clamped(
+        bytes: [u8; 32],
+        state: &mut MontgomeryStepState,
+        budget: usize,
+    ) -> Result<Option<MontgomeryPoint>, BackupError> {
+        step_ladder(&clamp_integer(bytes), state, budget)
+    }
+}
+
+/// Run up to `budget` ladder steps over the little-endian scalar `bytes`.
+fn step_ladder(
+    bytes: &[u8; 32],
+    state: &mut MontgomeryStepState,
+    budget: usize,
+) -> Result<Option<MontgomeryPoint>, BackupError> {
+    validate_montgomery_checkpoint(&state.ladder)?;
+    if state.bit_index > 255 {
+        return Err(BackupError::Malformed);
+    }
+
+    let affine_u = FieldElement::from_bytes(&state.ladder.u.0);
+    let mut x0 = ProjectivePoint {
+        U: FieldElement::from_bytes(&state.ladder.x2),
+        W: FieldElement::from_bytes(&state.ladder.z2),
+    };
+    let mut x1 = ProjectivePoint {
+        U: FieldElement::from_bytes(&state.ladder.x3),
+        W: FieldElement::from_bytes(&state.ladder.z3),
+    };
+
+    let mut prev_bit = state.swap;
+    let steps = budget.min(state.bit_index as usize);
+    for _ in 0..steps {
+        let i = (state.bit_index - 1) as usize;
+        let cur_bit = (bytes[i >> 3] >> (i & 7)) & 1 == 1;
+        let choice: u8 = (prev_bit ^ cur_bit) as u8;
+
+        ProjectivePoint::conditional_swap(&mut x0, &mut x1, choice.into());
+        differential_add_and_double(&mut x0, &mut x1, &affine_u);
+
+        prev_bit = cur_bit;
+        state.bit_index -= 1;
+    }
+
+    state.ladder.x2 = x0.U.as_bytes();
+    state.ladder.z2 = x0.W.as_bytes();
+    state.ladder.x3 = x1.U.as_bytes();
+    state.ladder.z3 = x1.W.as_bytes();
+    state.swap = prev_bit;
+    #[cfg(feature = "zeroize")]
+    prev_bit.zeroize();
+
+    if !state.is_done() {
+        return Ok(None);
+    }
+    ProjectivePoint::conditional_swap(&mut x0, &mut x1, Choice::from(state.swap as u8));
+    Ok(Some(x0.as_affine()))
+}
+
 define_mul_assign_variants!(LHS = MontgomeryPoint, RHS = Scalar);
 
 define_mul_variants!(
@@ -510,6 +977,37 @@ mod test {
         assert!(minus_one.to_edwards(0).is_none());
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn batch_to_edwards_matches_to_edwards() {
+        let mut csprng = rand_core::OsRng;
+        let minus_one = MontgomeryPoint((-&FieldElement::ONE).as_bytes());
+        let two = MontgomeryPoint((&FieldElement::ONE + &FieldElement::ONE).as_bytes());
+
+        let mut points = Vec::new();
+        for i in 0..16u8 {
+            let P = rand_prime_order_point(csprng).to_montgomery();
+            points.push((P, i & 1));
+        }
+        // Twist points and the exceptional point are rejected in place,
+        // without disturbing the shared inversion for the others
+        points.insert(3, (minus_one, 0));
+        points.insert(7, (two, 1));
+        points.push((MontgomeryPoint::identity(), 0));
+        let mut random = [0u8; 32];
+        csprng.fill_bytes(&mut random);
+        points.push((MontgomeryPoint(random), 1));
+
+        let converted = MontgomeryPoint::batch_to_edwards(&points);
+        assert_eq!(converted.len(), points.len());
+        for ((P, sign), Q) in points.iter().zip(converted.iter()) {
+            assert_eq!(*Q, P.to_edwards(*sign));
+        }
+        assert!(converted[3].is_none());
+        assert!(converted[7].is_none());
+        assert!(MontgomeryPoint::batch_to_edwards(&[]).is_empty());
+    }
+
     #[test]
     fn eq_defined_mod_p() {
         let mut u18_bytes = [0u8; 32];
@@ -543,7 +1041,7 @@ mod test {
         let mut csprng = rand_core::OsRng;
 
         for _ in 0..100 {
-            let p_edwards = rand_prime_order_point(&mut csprng);
+            let p_edwards = rand_prime_order_point(csprng);
             let p_montgomery: MontgomeryPoint = p_edwards.to_montgomery();
 
             let s: Scalar = Scalar::random(&mut csprng);
@@ -562,7 +1060,7 @@ mod test {
 
         for _ in 0..100 {
             // Make a random prime-order point P
-            let p_edwards = rand_prime_order_point(&mut csprng);
+            let p_edwards = rand_prime_order_point(csprng);
             let p_montgomery: MontgomeryPoint = p_edwards.to_montgomery();
 
             // Make a random integer b
@@ -613,6 +1111,49 @@ mod test {
         }
     }
 
+    #[test]
+    fn ladder_state_driven_bit_by_bit() {
+        let mut csprng = rand_core::OsRng;
+        let p = rand_prime_order_point(csprng).to_montgomery();
+        let mut bytes = [0u8; 32];
+        csprng.fill_bytes(&mut bytes);
+        let clamped = clamp_integer(bytes);
+
+        let mut ladder = LadderState::init(&p);
+        for (i, bit) in bytestring_bits_le(&clamped).rev().enumerate() {
+            // Finishing early gives the multiple by the bits so far
+            if i == 100 {
+                let prefix = bytestring_bits_le(&clamped).rev().take(100);
+                assert_eq!(ladder.clone().finish(), p.mul_bits_be(prefix));
+            }
+            ladder.process_bit(bit);
+        }
+        assert_eq!(ladder.finish(), p.mul_clamped(bytes));
+
+        // With no bits processed the result is the identity
+        assert_eq!(LadderState::init(&p).finish(), MontgomeryPoint::identity());
+    }
+
+    #[test]
+    fn mul_streaming_matches_mul_clamped() {
+        let mut csprng = rand_core::OsRng;
+        for _ in 0..20 {
+            let mut bytes = [0u8; 32];
+            csprng.fill_bytes(&mut bytes);
+            let clamped = clamp_integer(bytes);
+            let product = constants::X25519_BASEPOINT
+                .mul_streaming(|range, chunk: &mut [u8; 4]| {
+                    chunk.copy_from_slice(&clamped[range]);
+                    Ok::<(), ()>(())
+                })
+                .unwrap();
+            assert_eq!(product, constants::X25519_BASEPOINT.mul_clamped(bytes));
+        }
+
+        let result = constants::X25519_BASEPOINT.mul_streaming(|_, _: &mut [u8; 32]| Err(()));
+        assert_eq!(result, Err(()));
+    }
+
     /// Check that mul_base_clamped and mul_clamped agree
     #[test]
     fn mul_base_clamped() {
@@ -638,6 +1179,157 @@ mod test {
         }
     }
 
+    /// Run the first `steps` iterations of the ladder on `bits` and
+    /// checkpoint the two ladder points.
+    fn ladder_checkpoint(
+        u: MontgomeryPoint,
+        bits: impl Iterator<Item = bool>,
+        steps: usize,
+    ) -> MontgomeryLadderCheckpoint {
+        let affine_u = FieldElement::from_bytes(&u.0);
+        let mut x0 = ProjectivePoint::identity();
+        let mut x1 = ProjectivePoint {
+            U: affine_u,
+            W: FieldElement::ONE,
+        };
+        let mut prev_bit = false;
+        for cur_bit in bits.take(steps) {
+            ProjectivePoint::conditional_swap(
+                &mut x0,
+                &mut x1,
+                ((prev_bit ^ cur_bit) as u8).into(),
+            );
+            differential_add_and_double(&mut x0, &mut x1, &affine_u);
+            prev_bit = cur_bit;
+        }
+        MontgomeryLadderCheckpoint {
+            u,
+            x2: x0.U.as_bytes(),
+            z2: x0.W.as_bytes(),
+            x3: x1.U.as_bytes(),
+            z3: x1.W.as_bytes(),
+        }
+    }
+
+    #[test]
+    fn validate_montgomery_checkpoint_mid_ladder() {
+        let mut csprng = rand_core::OsRng;
+
+        for steps in [0, 1, 2, 100, 254] {
+            let p = rand_prime_order_point(csprng).to_montgomery();
+            let s = Scalar::random(&mut csprng);
+            let state = ladder_checkpoint(p, s.bits_le().rev().skip(1), steps);
+            assert_eq!(validate_montgomery_checkpoint(&state), Ok(()));
+
+            let mut corrupted = state;
+            corrupted.x3[0] ^= 1;
+            assert_eq!(
+                validate_montgomery_checkpoint(&corrupted),
+                Err(BackupError::LadderInvariant)
+            );
+
+            let mut wrong_point = state;
+            wrong_point.u = constants::X25519_BASEPOINT;
+            assert_eq!(
+                validate_montgomery_checkpoint(&wrong_point),
+                Err(BackupError::LadderInvariant)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_montgomery_checkpoint_rejects_malformed() {
+        let state = ladder_checkpoint(
+            constants::X25519_BASEPOINT,
+            Scalar::ONE.bits_le().rev().skip(1),
+            10,
+        );
+
+        // p = 2^255 - 19 encodes zero, but not canonically
+        let mut p_bytes = [0xff; 32];
+        p_bytes[0] = 0xed;
+        p_bytes[31] = 0x7f;
+        let mut non_canonical = state;
+        non_canonical.z2 = p_bytes;
+        assert_eq!(
+            validate_montgomery_checkpoint(&non_canonical),
+            Err(BackupError::NonCanonicalField)
+        );
+
+        let mut high_bit = state;
+        high_bit.x2[31] |= 0x80;
+        assert_eq!(
+            validate_montgomery_checkpoint(&high_bit),
+            Err(BackupError::NonCanonicalField)
+        );
+
+        let mut degenerate = state;
+        degenerate.x3 = [0u8; 32];
+        degenerate.z3 = [0u8; 32];
+        assert_eq!(
+            validate_montgomery_checkpoint(&degenerate),
+            Err(BackupError::LadderInvariant)
+        );
+    }
+
+    #[test]
+    fn step_mul_matches_mul() {
+        let mut csprng = rand_core::OsRng;
+        let p = rand_prime_order_point(csprng).to_montgomery();
+        let s = Scalar::random(&mut csprng);
+        let mut clamped_bytes = [0u8; 32];
+        csprng.fill_bytes(&mut clamped_bytes);
+
+        for budget in [1, 30, 255] {
+            let mut state = MontgomeryStepState::new(&p);
+            let mut calls = 0;
+            let result = loop {
+                calls += 1;
+                if let Some(point) = MontgomeryPoint::step_mul(&s, &mut state, budget).unwrap() {
+                    break point;
+                }
+                assert_eq!(validate_montgomery_checkpoint(&state.ladder), Ok(()));
+            };
+            assert_eq!(result, p * s);
+            assert_eq!(calls, MontgomeryStepState::new(&p).calls(budget));
+            assert_eq!(
+                MontgomeryPoint::step_mul(&s, &mut state, budget),
+                Ok(Some(result))
+            );
+
+            let mut state = MontgomeryStepState::new(&p);
+            let result = loop {
+                let stepped = MontgomeryPoint::step_mul_clamped(clamped_bytes, &mut state, budget);
+                if let Some(point) = stepped.unwrap() {
+                    break point;
+                }
+            };
+            assert_eq!(result, p.mul_clamped(clamped_bytes));
+        }
+    }
+
+    #[test]
+    fn step_mul_rejects_corrupted_state() {
+        let p = constants::X25519_BASEPOINT;
+        let s = Scalar::from(12345u64);
+        let mut state = MontgomeryStepState::new(&p);
+        assert_eq!(MontgomeryPoint::step_mul(&s, &mut state, 100), Ok(None));
+
+        let mut corrupted = state;
+        corrupted.ladder.x2[0] ^= 1;
+        assert_eq!(
+            MontgomeryPoint::step_mul(&s, &mut corrupted, 100),
+            Err(BackupError::LadderInvariant)
+        );
+
+        let mut out_of_range = state;
+        out_of_range.bit_index = 256;
+        assert_eq!(
+            MontgomeryPoint::step_mul(&s, &mut out_of_range, 100),
+            Err(BackupError::Malformed)
+        );
+    }
+
     #[cfg(feature = "alloc")]
     const ELLIGATOR_CORRECT_OUTPUT: [u8; 32] = [
         0x5f, 0x35, 0x20, 0x00, 0x1c, 0x6c, 0x99, 0x36, 0xa3, 0x12, 0x06, 0xaf, 0xe7, 0xc7, 0xac,
@@ -656,6 +1348,50 @@ mod test {
         assert_eq!(eg.to_bytes(), ELLIGATOR_CORRECT_OUTPUT);
     }
 
+    #[test]
+    fn elligator_representative_round_trip() {
+        let mut encodable = 0;
+        for i in 0..64u64 {
+            let P = constants::X25519_BASEPOINT * Scalar::from(1000 + i);
+            for v_is_negative in [false, true] {
+                if let Some(r) = P.to_elligator_representative(v_is_negative) {
+                    encodable += 1;
+                    assert_eq!(r[31] & 0xc0, 0);
+                    assert_eq!(MontgomeryPoint::from_elligator_representative(&r), P);
+
+                    // The top two bits are padding.
+                    let mut padded = r;
+                    padded[31] |= 0xc0;
+                    assert_eq!(MontgomeryPoint::from_elligator_representative(&padded), P);
+                }
+            }
+        }
+        // Each sign is encodable for about half of the points.
+        assert!((32..96).contains(&encodable), "{} encodable", encodable);
+
+        // Mapping a representative forward and back gives it back for
+        // exactly one sign of v.
+        for i in 0..32u8 {
+            let mut r = [i.wrapping_mul(37); 32];
+            r[31] &= 0x3f;
+            let P = MontgomeryPoint::from_elligator_representative(&r);
+            let fe = FieldElement::from_bytes(&r);
+            let canonical = if bool::from((&fe + &fe).is_negative()) {
+                (-&fe).as_bytes()
+            } else {
+                fe.as_bytes()
+            };
+            assert!([false, true]
+                .iter()
+                .any(|&v| P.to_elligator_representative(v) == Some(canonical)));
+        }
+
+        // u = -A is never an image of the forward map.
+        let minus_A = MontgomeryPoint((-&MONTGOMERY_A).as_bytes());
+        assert_eq!(minus_A.to_elligator_representative(false), None);
+        assert_eq!(minus_A.to_elligator_representative(true), None);
+    }
+
     #[test]
     fn montgomery_elligator_zero_zero() {
         let zero = [0u8; 32];
@@ -0,0 +1,229 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Counts of the field and point operations performed by the calling
+//! thread, for calibrating gas costs and comparing backends.
+//!
+//! With the `op-counts` feature, every field multiplication, squaring and
+//! inversion, and every point addition and doubling, increments a counter
+//! local to the thread performing it.  [`measure`] reports the operations
+//! performed by a closure, such as one scalar multiplication, one
+//! multiscalar multiplication, one run of the Montgomery ladder, or one
+//! chunk of a step-wise multiplication:
+//!
+//! ```
+//! use curve25519_dalek::op_counts;
+//! use curve25519_dalek::{EdwardsPoint, Scalar};
+//!
+//! let a = Scalar::from(1234u64);
+//! let (point, counts) = op_counts::measure(|| EdwardsPoint::mul_base(&a));
+//! assert_eq!(point, EdwardsPoint::mul_base(&a));
+//! assert!(counts.point_additions > 0);
+//! println!("{}", counts);
+//! ```
+//!
+//! Counting happens where the operation is implemented, so the counts
+//! reflect what the selected backend actually does:
+//!
+//! - a field inversion is counted as one inversion, in addition to the
+//!   multiplications and squarings it is computed with;
+//! - the Montgomery ladder's combined differential addition and doubling
+//!   is counted as one addition and one doubling;
+//! - the vector backends count their point additions and doublings, but
+//!   not the field operations within them, which run on four lanes at
+//!   once; the field operations they count are those done serially, e.g.
+//!   when converting a result back to a serial point.
+//!
+//! Counters are per thread, so work on other threads, such as a
+//! multiscalar multiplication split across a `rayon` pool, is not included
+//! in the counts of the thread that started it.  Without the `op-counts`
+//! feature no counting code is compiled in.
+
+use core::cell::Cell;
+use core::fmt::{self, Display};
+use core::ops::{Add, Sub};
+
+/// The number of field and point operations of each kind performed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OpCounts {
+    /// Field multiplications, not including squarings.
+    pub field_muls: u64,
+    /// Field squarings, including doubled squarings.
+    pub field_squarings: u64,
+    /// Field inversions.
+    pub field_inversions: u64,
+    /// Point additions and subtractions.
+    pub point_additions: u64,
+    /// Point doublings.
+    pub point_doublings: u64,
+}
+
+impl OpCounts {
+    /// No operations.
+    pub const ZERO: OpCounts = OpCounts {
+        field_muls: 0,
+        field_squarings: 0,
+        field_inversions: 0,
+        point_additions: 0,
+        point_doublings: 0,
+    };
+}
+
+impl Add for OpCounts {
+    type Output = OpCounts;
+
+    fn add(self, other: OpCounts) -> OpCounts {
+        OpCounts {
+            field_muls: self.field_muls + other.field_muls,
+            field_squarings: self.field_squarings + other.field_squarings,
+            field_inversions: self.field_inversions + other.field_inversions,
+            point_additions: self.point_additions + other.point_additions,
+            point_doublings: self.point_doublings + other.point_doublings,
+        }
+    }
+}
+
+impl Sub for OpCounts {
+    type Output = OpCounts;
+
+    /// The operations in `self` but not in `other`, for an `other` taken
+    /// earlier on the same thread.
+    fn sub(self, other: OpCounts) -> OpCounts {
+        OpCounts {
+            field_muls: self.field_muls - other.field_muls,
+            field_squarings: self.field_squarings - other.field_squarings,
+            field_inversions: self.field_inversions - other.field_inversions,
+            point_additions: self.point_additions - other.point_additions,
+            point_doublings: self.point_doublings - other.point_doublings,
+        }
+    }
+}
+
+impl Display for OpCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} M, {} S, {} I, {} point additions, {} point doublings",
+            self.field_muls,
+            self.field_squarings,
+            self.field_inversions,
+            self.point_additions,
+            self.point_doublings
+        )
+    }
+}
+
+/// A kind of operation recorded by `count_ops!`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Op {
+    FieldMul,
+    FieldSquare,
+    FieldInvert,
+    PointAdd,
+    PointDouble,
+}
+
+std::thread_local! {
+    static COUNTS: Cell<OpCounts> = const { Cell::new(OpCounts::ZERO) };
+}
+
+/// Record `n` operations of kind `op` on the calling thread.
+pub(crate) fn record(op: Op, n: u64) {
+    COUNTS.with(|counts| {
+        let mut c = counts.get();
+        match op {
+            Op::FieldMul => c.field_muls += n,
+            Op::FieldSquare => c.field_squarings += n,
+            Op::FieldInvert => c.field_inversions += n,
+            Op::PointAdd => c.point_additions += n,
+            Op::PointDouble => c.point_doublings += n,
+        }
+        counts.set(c);
+    });
+}
+
+/// The operations performed by the calling thread since it started, or
+/// since its last [`reset`].
+pub fn current() -> OpCounts {
+    COUNTS.with(Cell::get)
+}
+
+/// Set the calling thread's counts back to zero.
+pub fn reset() {
+    COUNTS.with(|counts| counts.set(OpCounts::ZERO));
+}
+
+/// Run `f`, returning its result and the operations it performed on the
+/// calling thread.
+///
+/// Calls may be nested: the counts of an inner call are included in those
+/// of the outer one.
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> (R, OpCounts) {
+    let before = current();
+    let result = f();
+    (result, current() - before)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constants;
+    use crate::field::FieldElement;
+    use crate::montgomery::LadderState;
+    use crate::resumable::{JobHandle, JobStatus};
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn inversion_counts() {
+        let x = FieldElement::from_bytes(&[9u8; 32]);
+        let (_, counts) = measure(|| x.invert());
+        assert_eq!(
+            counts,
+            OpCounts {
+                field_muls: 11,
+                field_squarings: 254,
+                field_inversions: 1,
+                ..OpCounts::ZERO
+            }
+        );
+    }
+
+    #[test]
+    fn ladder_counts_one_step_per_bit() {
+        let (_, counts) = measure(|| {
+            let mut ladder = LadderState::init(&constants::X25519_BASEPOINT);
+            for i in 0..10 {
+                ladder.process_bit(i % 3 == 0);
+            }
+            ladder.finish()
+        });
+        assert_eq!(counts.point_additions, 10);
+        assert_eq!(counts.point_doublings, 10);
+        assert_eq!(counts.field_inversions, 1);
+    }
+
+    #[test]
+    fn nested_measurements_and_step_chunks() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let point = constants::ED25519_BASEPOINT_POINT * Scalar::from(99u64);
+        let mut job = JobHandle::new(&a, &point, &b);
+
+        let ((inner, work), outer) = measure(|| {
+            let (status, inner) = measure(|| job.step());
+            let work = match status {
+                JobStatus::InProgress { work, .. } | JobStatus::Done { work, .. } => work,
+            };
+            (inner, work)
+        });
+        assert_eq!(inner, outer);
+        assert!(inner.point_doublings >= work.doublings as u64);
+        assert!(inner.point_additions >= work.additions as u64);
+
+        let (_, counts) = measure(|| point + point);
+        assert_eq!(counts.point_additions, 1);
+        assert_eq!(counts.point_doublings, 0);
+    }
+}
@@ -0,0 +1,296 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Polynomials over the scalar field, as used by threshold schemes.
+//!
+//! A \\(t\\)-of-\\(n\\) secret sharing splits a secret \\(s\\) into the values
+//! \\(f(x\_1), \ldots, f(x\_n)\\) of a random polynomial \\(f\\) of degree
+//! \\(t - 1\\) with \\(f(0) = s\\). Any \\(t\\) shares recover \\(s\\) by
+//! Lagrange interpolation at zero:
+//! $$
+//! s = \sum\_i \lambda\_i f(x\_i), \qquad
+//! \lambda\_i = \prod\_{j \neq i} \frac{x\_j}{x\_j - x\_i}.
+//! $$
+//!
+//! The \\(x\\)-coordinates, i.e. participant identifiers, are treated as
+//! public: checks on them may be variable-time. The coefficients of a
+//! polynomial and the shares are treated as secret, and are only ever used
+//! in constant-time arithmetic.
+//!
+//! ```
+//! use curve25519_dalek::polynomial;
+//! use curve25519_dalek::scalar::Scalar;
+//!
+//! // f(x) = 7 + 5x + 3x^2
+//! let f = [Scalar::from(7u64), Scalar::from(5u64), Scalar::from(3u64)];
+//! let xs = [Scalar::from(2u64), Scalar::from(4u64), Scalar::from(5u64)];
+//! let ys = [
+//!     polynomial::evaluate(&f, &xs[0]),
+//!     polynomial::evaluate(&f, &xs[1]),
+//!     polynomial::evaluate(&f, &xs[2]),
+//! ];
+//!
+//! assert_eq!(polynomial::interpolate_at_zero(&xs, &ys), Ok(Scalar::from(7u64)));
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::errors::InterpolationError;
+use crate::scalar::Scalar;
+
+/// Evaluate the polynomial with the given coefficients, lowest degree
+/// first, at `x`.
+///
+/// This runs in time depending only on the number of coefficients.
+pub fn evaluate(coefficients: &[Scalar], x: &Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// The numerator \\(\prod\_{j \neq i} x\_j\\) and denominator
+/// \\(\prod\_{j \neq i} (x\_j - x\_i)\\) of the Lagrange coefficient of
+/// `xs[i]`.
+fn numerator_denominator(xs: &[Scalar], i: usize) -> (Scalar, Scalar) {
+    let x_i = &xs[i];
+    let mut num = Scalar::ONE;
+    let mut den = Scalar::ONE;
+    for (j, x_j) in xs.iter().enumerate() {
+        if j != i {
+            num *= x_j;
+            den *= x_j - x_i;
+        }
+    }
+    (num, den)
+}
+
+impl Scalar {
+    /// Compute the Lagrange coefficient of `x_i` for interpolation at zero
+    /// from the points with \\(x\\)-coordinates `xs`, that is
+    /// \\(\prod\_{x\_j \neq x\_i} x\_j / (x\_j - x\_i)\\).
+    ///
+    /// `xs` must contain `x_i`. To compute the coefficients of every point
+    /// at once, with a single inversion, use
+    /// [`polynomial::lagrange_coefficients_into`](crate::polynomial::lagrange_coefficients_into).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InterpolationError::MissingX`] if `x_i` is not in `xs`,
+    /// and [`InterpolationError::DuplicateX`] if the entries of `xs` are
+    /// not distinct.
+    pub fn lagrange_coefficient(x_i: &Scalar, xs: &[Scalar]) -> Result<Scalar, InterpolationError> {
+        let i = xs
+            .iter()
+            .position(|x| x == x_i)
+            .ok_or(InterpolationError::MissingX)?;
+        for (j, x_j) in xs.iter().enumerate() {
+            if xs[j + 1..].contains(x_j) {
+                return Err(InterpolationError::DuplicateX);
+            }
+        }
+
+        let (num, den) = numerator_denominator(xs, i);
+        Ok(num * den.invert())
+    }
+}
+
+/// Write the Lagrange coefficient of each of the points with
+/// \\(x\\)-coordinates `xs`, for interpolation at zero, into the matching
+/// entry of `out`.
+///
+/// This takes a single scalar inversion and no allocation, at the cost of
+/// recomputing each coefficient's products twice; for the small sets used
+/// in threshold schemes that is still far cheaper than one inversion per
+/// coefficient.
+///
+/// # Errors
+///
+/// Returns [`InterpolationError::LengthMismatch`] if `out` is not as long
+/// as `xs`, and [`InterpolationError::DuplicateX`] if the entries of `xs`
+/// are not distinct. The contents of `out` are unspecified after an error.
+pub fn lagrange_coefficients_into(
+    xs: &[Scalar],
+    out: &mut [Scalar],
+) -> Result<(), InterpolationError> {
+    if xs.len() != out.len() {
+        return Err(InterpolationError::LengthMismatch {
+            xs: xs.len(),
+            values: out.len(),
+        });
+    }
+
+    // Montgomery's trick, with `out` holding the products of the previous
+    // denominators and the denominators themselves recomputed on the way
+    // back instead of stored.
+    let mut acc = Scalar::ONE;
+    for (i, o) in out.iter_mut().enumerate() {
+        *o = acc;
+        acc *= numerator_denominator(xs, i).1;
+    }
+
+    // Some denominator is zero iff two x-coordinates are equal
+    if acc == Scalar::ZERO {
+        return Err(InterpolationError::DuplicateX);
+    }
+
+    let mut inv = acc.invert();
+    for (i, o) in out.iter_mut().enumerate().rev() {
+        let (num, den) = numerator_denominator(xs, i);
+        *o = num * inv * *o;
+        inv *= den;
+    }
+
+    Ok(())
+}
+
+/// Compute the Lagrange coefficient of each of the points with
+/// \\(x\\)-coordinates `xs`, for interpolation at zero.
+///
+/// See [`lagrange_coefficients_into`].
+///
+/// # Errors
+///
+/// Returns [`InterpolationError::DuplicateX`] if the entries of `xs` are
+/// not distinct.
+#[cfg(feature = "alloc")]
+pub fn lagrange_coefficients(xs: &[Scalar]) -> Result<Vec<Scalar>, InterpolationError> {
+    let mut out = vec![Scalar::ZERO; xs.len()];
+    lagrange_coefficients_into(xs, &mut out)?;
+    Ok(out)
+}
+
+/// Compute \\(f(0)\\) for the unique polynomial \\(f\\) of degree less than
+/// `xs.len()` with \\(f(x\_i) = y\_i\\), i.e. recover a shared secret from
+/// its shares.
+///
+/// The sum is accumulated as a single fraction, so this takes one scalar
+/// inversion and no allocation.
+///
+/// # Errors
+///
+/// Returns [`InterpolationError::LengthMismatch`] if `xs` and `ys` have
+/// different lengths, and [`InterpolationError::DuplicateX`] if the entries
+/// of `xs` are not distinct.
+pub fn interpolate_at_zero(xs: &[Scalar], ys: &[Scalar]) -> Result<Scalar, InterpolationError> {
+    if xs.len() != ys.len() {
+        return Err(InterpolationError::LengthMismatch {
+            xs: xs.len(),
+            values: ys.len(),
+        });
+    }
+
+    // acc_num / acc_den = sum of y_i num_i / den_i so far
+    let mut acc_num = Scalar::ZERO;
+    let mut acc_den = Scalar::ONE;
+    for (i, y_i) in ys.iter().enumerate() {
+        let (num, den) = numerator_denominator(xs, i);
+        acc_num = acc_num * den + y_i * num * acc_den;
+        acc_den *= den;
+    }
+
+    if acc_den == Scalar::ZERO {
+        return Err(InterpolationError::DuplicateX);
+    }
+
+    Ok(acc_num * acc_den.invert())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A degree-3 polynomial and its values at x = 1, ..., 6
+    fn shares() -> ([Scalar; 4], [Scalar; 6], [Scalar; 6]) {
+        let f = [
+            Scalar::from(0xdead_beef_u64),
+            Scalar::from(17u64),
+            -Scalar::from(3u64),
+            Scalar::from(1u64 << 40),
+        ];
+        let mut xs = [Scalar::ZERO; 6];
+        let mut ys = [Scalar::ZERO; 6];
+        for (i, (x, y)) in xs.iter_mut().zip(ys.iter_mut()).enumerate() {
+            *x = Scalar::from(i as u64 + 1);
+            *y = evaluate(&f, x);
+        }
+        (f, xs, ys)
+    }
+
+    #[test]
+    fn evaluate_matches_naive() {
+        let (f, _, _) = shares();
+        let x = Scalar::from(12345u64);
+        let expected = f[0] + f[1] * x + f[2] * x * x + f[3] * x * x * x;
+        assert_eq!(evaluate(&f, &x), expected);
+        assert_eq!(evaluate(&f, &Scalar::ZERO), f[0]);
+        assert_eq!(evaluate(&[], &x), Scalar::ZERO);
+    }
+
+    #[test]
+    fn interpolate_recovers_secret_from_any_threshold_subset() {
+        let (f, xs, ys) = shares();
+        for skip in 0..3 {
+            let sub_xs = [xs[skip], xs[3], xs[4], xs[5]];
+            let sub_ys = [ys[skip], ys[3], ys[4], ys[5]];
+            assert_eq!(interpolate_at_zero(&sub_xs, &sub_ys), Ok(f[0]));
+        }
+        // More shares than needed also work
+        assert_eq!(interpolate_at_zero(&xs, &ys), Ok(f[0]));
+        // Fewer do not
+        assert_ne!(interpolate_at_zero(&xs[..3], &ys[..3]), Ok(f[0]));
+    }
+
+    #[test]
+    fn lagrange_coefficients_agree() {
+        let (f, xs, ys) = shares();
+        let mut coefficients = [Scalar::ZERO; 6];
+        lagrange_coefficients_into(&xs, &mut coefficients).unwrap();
+
+        let mut secret = Scalar::ZERO;
+        for ((x, y), lambda) in xs.iter().zip(ys.iter()).zip(coefficients.iter()) {
+            assert_eq!(Scalar::lagrange_coefficient(x, &xs), Ok(*lambda));
+            secret += lambda * y;
+        }
+        assert_eq!(secret, f[0]);
+
+        #[cfg(feature = "alloc")]
+        assert_eq!(lagrange_coefficients(&xs).unwrap(), coefficients);
+    }
+
+    #[test]
+    fn interpolation_errors() {
+        let (_, mut xs, ys) = shares();
+        let mut out = [Scalar::ZERO; 5];
+
+        assert_eq!(
+            Scalar::lagrange_coefficient(&Scalar::from(7u64), &xs),
+            Err(InterpolationError::MissingX)
+        );
+        assert_eq!(
+            lagrange_coefficients_into(&xs, &mut out),
+            Err(InterpolationError::LengthMismatch { xs: 6, values: 5 })
+        );
+        assert_eq!(
+            interpolate_at_zero(&xs[..5], &ys),
+            Err(InterpolationError::LengthMismatch { xs: 5, values: 6 })
+        );
+
+        xs[4] = xs[2];
+        assert_eq!(
+            Scalar::lagrange_coefficient(&xs[0], &xs),
+            Err(InterpolationError::DuplicateX)
+        );
+        assert_eq!(
+            lagrange_coefficients_into(&xs[1..], &mut out),
+            Err(InterpolationError::DuplicateX)
+        );
+        assert_eq!(
+            interpolate_at_zero(&xs, &ys),
+            Err(InterpolationError::DuplicateX)
+        );
+    }
+}
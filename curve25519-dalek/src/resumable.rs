@@ -0,0 +1,5186 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// Copyright (c) 2016-2021 isis lovecruft
+// Copyright (c) 2016-2019 Henry de Valence
+// See LICENSE for licensing information.
+//
+// Authors:
+// - isis agora lovecruft <isis@patternsinthevoid.net>
+// - Henry de Valence <hdevalence@hdevalence.ca>
+
+//! Helpers for step-wise (resumable) scalar multiplication.
+//!
+//! [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`] computes
+//! \\(aA + bB\\) a bounded number of loop iterations at a time.  When it
+//! runs out of budget it hands the partial accumulator to a callback as
+//! fifteen raw field limbs, together with the index of the next digit to
+//! process, and the caller resumes the computation later from that pair.
+//! [`step_mul`] wraps the same loop in types, taking a [`StepMulState`] and
+//! returning a [`StepResult`] in place of those raw values.
+//! [`PrecomputedStepMulContext`] keeps the NAF digits and lookup table that
+//! each call would otherwise rebuild.
+//!
+//! The raw limbs are 120 bytes and expose the backend's field
+//! representation.  Callers who only need the accumulator up to projective
+//! equivalence can instead store it as a [`CompressedEdwardsY`] with
+//! [`compress_checkpoint`], wrapped with its index in a
+//! [`CompressedCheckpoint`], and resume with
+//! [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed`].
+//!
+//! To persist the limb form, wrap it in a [`Checkpoint`] and store
+//! [`Checkpoint::to_bytes`].  The encoding ends in a CRC-32 of everything
+//! before it, so a blob whose write was cut short (or never finished) is
+//! rejected by [`Checkpoint::from_bytes`] rather than resuming from a
+//! half-written accumulator.
+//!
+//! For capacity planning, [`naf_weight`] and [`NafWeightStats`] report how
+//! many additions a scalar contributes to the loop at a given NAF width,
+//! and [`estimated_steps`] and the [`CostModel`] implementations predict
+//! the number of resume calls, and the work each performs, up front.
+//! Callers who cache NAF digits next to a checkpoint can shrink them from
+//! 256 bytes to [`PACKED_NAF_LEN`] with [`pack_naf`] and [`unpack_naf`].
+//!
+//! [`JobHandle`] wraps the whole loop: it keeps the checkpoint between
+//! chunks, reports the [`WorkDone`] by each one, and summarizes the job in
+//! a [`JobStats`] when it completes.  [`ristretto_difference`] compares two
+//! completed results as Ristretto points.
+//!
+//! With the `rand_core` feature, `Checkpoint::rerandomize` rescales a
+//! checkpoint's accumulator by a random factor so that the stored limbs are
+//! not the representation the loop computed, and `ScalarSplit` runs the
+//! variable-time loop on random shares of the scalars, adding a
+//! constant-time correction at the end.
+//!
+//! [`step_mul_with_store`] instead keeps each job's checkpoint in a
+//! [`CheckpointStore`], such as the in-memory [`MemoryCheckpointStore`] or,
+//! with the `std` feature, the [`std::io::Write`]-backed
+//! `WriterCheckpointStore`.  With the `digest` feature, a
+//! `CheckpointManager` runs many such jobs through one store, refusing to
+//! resume a job's checkpoint with inputs other than its own.
+//!
+//! # Stability
+//!
+//! A checkpoint is only meaningful to a build which computes exactly the
+//! same intermediate values as the build that wrote it: the same basepoint,
+//! the same NAF recoding, the same lookup tables, and the same field
+//! arithmetic.  The tests of this module pin golden values for each of
+//! these (the basepoint fingerprint, the first basepoint table entries, a
+//! checkpoint written after one chunk, and the final result), so that an
+//! accidental change shows up in CI.  If a golden value ever has to change,
+//! checkpoints written before the change can no longer be resumed, and the
+//! checkpoint encoding's format must be bumped along with it.
+
+#![allow(non_snake_case)]
+
+use core::borrow::Borrow;
+use core::cell::Cell;
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+#[cfg(all(feature = "alloc", feature = "digest"))]
+use core::marker::PhantomData;
+use core::ops::Range;
+use core::time::Duration;
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+use digest::{generic_array::typenum::U64, Digest};
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "rand_core")]
+use subtle::ConditionallySelectable;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::backend::serial::curve_models::{ProjectiveNielsPoint, ProjectivePoint};
+#[cfg(feature = "alloc")]
+use crate::backend::serial::scalar_mul::pippenger::Pippenger;
+use crate::backend::serial::scalar_mul::variable_base;
+use crate::backend::serial::scalar_mul::vartime_double_base::{
+    check_start_index, check_step_budget, coordinate_to_limbs, deserialize_r_from_backup,
+    mul_with_progress as serial_mul_with_progress, serialize_r_for_backup, step_mul_counted,
+    step_mul_with_context, step_start_index, try_deserialize_r_from_backup, StepMulContext,
+    STEP_B_NAF_WIDTH,
+};
+use crate::constants;
+#[cfg(feature = "alloc")]
+use crate::edwards::AffinePoint;
+#[cfg(feature = "precomputed-tables")]
+use crate::edwards::EdwardsBasepointTable;
+use crate::edwards::{CompressedEdwardsY, EdwardsPoint};
+#[cfg(all(feature = "alloc", feature = "digest"))]
+use crate::errors::ManagerError;
+#[cfg(feature = "alloc")]
+use crate::errors::MultiscalarError;
+use crate::errors::{AuditError, BackupError, StepError, StoreError};
+use crate::field::FieldElement;
+use crate::ristretto::RistrettoPoint;
+use crate::scalar::Scalar;
+#[cfg(feature = "serde")]
+use crate::serde_encoding;
+use crate::traits::{CostModel, Identity, Interruptible, ValidityCheck};
+#[cfg(feature = "precomputed-tables")]
+use crate::window::LookupTableRadix16;
+use crate::window::{LookupTable, NafLookupTable5};
+
+pub use crate::backend::serial::scalar_mul::vartime_double_base::{
+    DEFAULT_STEP_BUDGET, MAX_STEP_BUDGET,
+};
+
+/// Compress the accumulator handed to a step-wise multiplication's
+/// progress callback.
+///
+/// This costs one field inversion.  The resulting point is the same group
+/// element as the limb-encoded accumulator, but not the same projective
+/// representative, so later checkpoints of a computation resumed from it
+/// will have different limbs while the final result is unchanged.
+///
+/// Returns an error if the limbs are not an accumulator the step-wise loop
+/// could have produced, as for
+/// [`EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint`].
+pub fn compress_checkpoint(projective_point: [u64; 15]) -> Result<CompressedEdwardsY, StepError> {
+    let r: EdwardsPoint = try_deserialize_r_from_backup(projective_point)?.as_extended();
+    Ok(r.compress())
+}
+
+/// Convert each of the accumulators handed to a step-wise multiplication's
+/// progress callback, e.g. those of many jobs in flight, to affine
+/// coordinates.
+///
+/// The whole batch costs one field inversion, where calling
+/// [`compress_checkpoint`] on each would cost one per checkpoint.
+///
+/// Returns the error of the first accumulator that [`compress_checkpoint`]
+/// would reject.
+#[cfg(feature = "alloc")]
+pub fn checkpoints_to_affine(
+    projective_points: &[[u64; 15]],
+) -> Result<Vec<AffinePoint>, StepError> {
+    let points = projective_points
+        .iter()
+        .map(|limbs| try_deserialize_r_from_backup(*limbs))
+        .collect::<Result<Vec<ProjectivePoint>, StepError>>()?;
+    Ok(ProjectivePoint::batch_to_affine(&points))
+}
+
+/// A step-wise multiplication checkpoint: the index of the next digit to
+/// process and the partial accumulator, as handed to the progress callback.
+///
+/// # Encoding
+///
+/// [`Checkpoint::to_bytes`] produces [`Checkpoint::BYTE_LEN`] bytes:
+///
+/// * the [`Checkpoint::FORMAT_VERSION`] byte;
+/// * the [`Checkpoint::BACKEND_ID`] byte of the build which wrote the
+///   checkpoint;
+/// * the [`basepoint_fingerprint`] of that build, as a 4-byte
+///   little-endian integer;
+/// * the index, as a 2-byte little-endian integer;
+/// * the fifteen accumulator limbs, each as an 8-byte little-endian integer;
+/// * a 4-byte little-endian CRC-32 (IEEE) of the preceding 128 bytes.
+///
+/// Storage code should write the bytes in order, so that the checksum is
+/// the last thing to land.  A checkpoint is then either complete and valid,
+/// or detectably invalid: a truncated blob fails the length check and a
+/// blob whose tail was never written fails the checksum.  The checksum
+/// guards against accidents, not against an adversary who can rewrite the
+/// whole blob.
+///
+/// [`Checkpoint::to_compressed_bytes`] produces the smaller
+/// [`Checkpoint::COMPRESSED_BYTE_LEN`]-byte form, in which the limbs are
+/// replaced by the 32-byte [`compress_checkpoint`] of the accumulator.
+///
+/// Every backend writes the limbs in the same radix \\(2\^{51}\\) layout,
+/// so a limb encoding resumes on any build; loading one with another
+/// layout identifier, or in another format version, fails with a typed
+/// error rather than resuming from a misread accumulator.
+/// [`Checkpoint::to_canonical_bytes`] produces the
+/// [`Checkpoint::CANONICAL_BYTE_LEN`]-byte form, in which each of the
+/// accumulator's \\(X\\), \\(Y\\), \\(Z\\) coordinates is replaced by
+/// its canonical 32-byte encoding: it is shorter than the limb form and,
+/// unlike the compressed form, loads without an inversion and square
+/// root.
+///
+/// The header lets a resume detect a checkpoint written by a build with a
+/// different basepoint, whose accumulator would silently produce a wrong
+/// result; see [`Checkpoint::check_basepoint`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    basepoint: u32,
+    index: u16,
+    projective_point: [u64; 15],
+}
+
+/// A checkpoint loaded from the compressed encoding written by
+/// [`Checkpoint::to_compressed_bytes`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CompressedCheckpoint {
+    basepoint: u32,
+    index: u16,
+    point: CompressedEdwardsY,
+}
+
+impl CompressedCheckpoint {
+    /// Wrap an index and a [`compress_checkpoint`] of the accumulator saved
+    /// with it, recording this build's [`basepoint_fingerprint`].
+    pub fn new(index: usize, point: CompressedEdwardsY) -> CompressedCheckpoint {
+        debug_assert!(index <= u16::MAX as usize);
+        CompressedCheckpoint {
+            basepoint: basepoint_fingerprint(),
+            index: index as u16,
+            point,
+        }
+    }
+
+    /// The index of the next digit to process, to pass back on resume.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// The compressed partial accumulator, to pass back on resume.
+    pub fn point(&self) -> CompressedEdwardsY {
+        self.point
+    }
+
+    /// Check that this checkpoint was written by a build with the same
+    /// basepoint as this one; see [`Checkpoint::check_basepoint`].
+    pub fn check_basepoint(&self) -> Result<(), StepError> {
+        check_basepoint_fingerprint(self.basepoint)
+    }
+}
+
+/// A short fingerprint of the basepoint this build uses: the CRC-32 of the
+/// compressed Ed25519 basepoint.
+///
+/// Checkpoints record it so that resuming with a build compiled against
+/// different constants fails cleanly instead of mixing two basepoints in
+/// one result.
+pub fn basepoint_fingerprint() -> u32 {
+    crc32(constants::ED25519_BASEPOINT_COMPRESSED.as_bytes())
+}
+
+fn check_basepoint_fingerprint(fingerprint: u32) -> Result<(), StepError> {
+    if fingerprint == basepoint_fingerprint() {
+        Ok(())
+    } else {
+        Err(StepError::BasepointMismatch)
+    }
+}
+
+/// The serializations of a [`Checkpoint`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CheckpointFormat {
+    /// The raw accumulator limbs, as produced by [`Checkpoint::to_bytes`].
+    Limbs,
+    /// The canonically encoded accumulator coordinates, as produced by
+    /// [`Checkpoint::to_canonical_bytes`].
+    Canonical,
+    /// The compressed accumulator, as produced by
+    /// [`Checkpoint::to_compressed_bytes`].
+    Compressed,
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Checkpoint {
+    /// Overwrite the index and accumulator limbs with zeros.
+    ///
+    /// The accumulator is derived from the scalars being multiplied, so
+    /// callers handling secret scalars should wipe any copy of a checkpoint
+    /// they no longer need; dropped checkpoints are wiped automatically.
+    fn zeroize(&mut self) {
+        self.basepoint.zeroize();
+        self.index.zeroize();
+        self.projective_point.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Checkpoint {}
+
+impl Checkpoint {
+    /// The version of the [`Checkpoint::to_bytes`] encoding this build
+    /// writes and reads.
+    pub const FORMAT_VERSION: u8 = 1;
+
+    /// The identifier of the limb layout recorded in
+    /// [`Checkpoint::to_bytes`].  Every backend writes each coordinate as
+    /// five radix \\(2\^{51}\\) limbs and records this identifier, so a limb
+    /// checkpoint resumes on any backend and word size.
+    pub const BACKEND_ID: u8 = 1;
+
+    /// The length of [`Checkpoint::to_bytes`].
+    pub const BYTE_LEN: usize = 1 + 1 + 4 + 2 + 15 * 8 + 4;
+
+    /// The length of [`Checkpoint::to_compressed_bytes`].
+    pub const COMPRESSED_BYTE_LEN: usize = 4 + 2 + 32 + 4;
+
+    /// The length of [`Checkpoint::to_canonical_bytes`].
+    pub const CANONICAL_BYTE_LEN: usize = 4 + 2 + 3 * 32 + 4;
+
+    /// The number of bytes a checkpoint occupies on the wire in `format`,
+    /// for sizing buffers and rate-limiting checkpoint traffic.
+    ///
+    /// Every format has a constant length, independent of the index and
+    /// of the accumulator: field limbs are always written in full, and
+    /// field elements and compressed points are always 32 bytes.  The compressed form trades 92
+    /// bytes per checkpoint for a field inversion when it is written and a
+    /// square root when it is resumed from.
+    pub fn wire_len(format: CheckpointFormat) -> usize {
+        match format {
+            CheckpointFormat::Limbs => Checkpoint::BYTE_LEN,
+            CheckpointFormat::Canonical => Checkpoint::CANONICAL_BYTE_LEN,
+            CheckpointFormat::Compressed => Checkpoint::COMPRESSED_BYTE_LEN,
+        }
+    }
+
+    /// Wrap the `(index, projective_point)` pair handed to a step-wise
+    /// multiplication's progress callback, recording this build's
+    /// [`basepoint_fingerprint`].
+    pub fn new(index: usize, projective_point: [u64; 15]) -> Checkpoint {
+        debug_assert!(index <= u16::MAX as usize);
+        Checkpoint {
+            basepoint: basepoint_fingerprint(),
+            index: index as u16,
+            projective_point,
+        }
+    }
+
+    /// Check that this checkpoint was written by a build with the same
+    /// basepoint as this one.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(())` if the recorded [`basepoint_fingerprint`] matches;
+    /// - `Err(StepError::BasepointMismatch)` otherwise.
+    pub fn check_basepoint(&self) -> Result<(), StepError> {
+        check_basepoint_fingerprint(self.basepoint)
+    }
+
+    /// The index of the next digit to process, to pass back on resume.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// The share of a step-wise multiplication completed at this
+    /// checkpoint, as a percentage.
+    ///
+    /// `start_index` is the index the multiplication started from, as
+    /// returned by [`start_index`] for its scalars.  The loop performs one
+    /// doubling per index from `start_index` down to zero, so this is
+    /// $$
+    /// 100 \cdot \frac{\mathrm{start} - \mathrm{index}}{\mathrm{start} + 1}.
+    /// $$
+    ///
+    /// # Rounding
+    ///
+    /// The value is computed in `f32` from the exact counts and is not
+    /// rounded to a whole percent.  A checkpoint always has at least one
+    /// iteration left, so it reports strictly less than 100; a completed
+    /// multiplication has no checkpoint and should be shown as 100.  An
+    /// index above `start_index`, i.e. a checkpoint from a different
+    /// multiplication, reports 0.
+    pub fn percent_complete(&self, start_index: usize) -> f32 {
+        let done = start_index.saturating_sub(self.index());
+        100.0 * done as f32 / (start_index + 1) as f32
+    }
+
+    /// The partial accumulator limbs, to pass back on resume.
+    pub fn projective_point(&self) -> [u64; 15] {
+        self.projective_point
+    }
+
+    /// Serialize this checkpoint; see the [type-level docs](Checkpoint)
+    /// for the layout.
+    pub fn to_bytes(&self) -> [u8; Checkpoint::BYTE_LEN] {
+        let mut bytes = [0u8; Checkpoint::BYTE_LEN];
+        bytes[0] = Checkpoint::FORMAT_VERSION;
+        bytes[1] = Checkpoint::BACKEND_ID;
+        bytes[2..6].copy_from_slice(&self.basepoint.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.index.to_le_bytes());
+        for (chunk, limb) in bytes[8..128]
+            .chunks_exact_mut(8)
+            .zip(self.projective_point.iter())
+        {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        let crc = crc32(&bytes[..128]);
+        bytes[128..].copy_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Serialize this checkpoint with its accumulator compressed: the same
+    /// 4-byte basepoint fingerprint and 2-byte index as
+    /// [`Checkpoint::to_bytes`], the 32
+    /// bytes of [`compress_checkpoint`], and a 4-byte little-endian CRC-32
+    /// of the preceding 38 bytes.
+    ///
+    /// Resume from it with
+    /// [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed`],
+    /// after loading it with [`Checkpoint::compressed_from_bytes`].
+    ///
+    /// Returns the error of [`compress_checkpoint`] if the accumulator is
+    /// not one the step-wise loop could have produced.
+    pub fn to_compressed_bytes(&self) -> Result<[u8; Checkpoint::COMPRESSED_BYTE_LEN], StepError> {
+        let compressed = compress_checkpoint(self.projective_point)?;
+        let mut bytes = [0u8; Checkpoint::COMPRESSED_BYTE_LEN];
+        bytes[0..4].copy_from_slice(&self.basepoint.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.index.to_le_bytes());
+        bytes[6..38].copy_from_slice(compressed.as_bytes());
+        let crc = crc32(&bytes[..38]);
+        bytes[38..].copy_from_slice(&crc.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Serialize this checkpoint portably: the same 4-byte basepoint
+    /// fingerprint and 2-byte index as [`Checkpoint::to_bytes`], the
+    /// canonical 32-byte
+    /// encodings of the accumulator's \\(X\\), \\(Y\\), and \\(Z\\)
+    /// coordinates, and a 4-byte little-endian CRC-32 of the preceding 102
+    /// bytes.
+    ///
+    /// Two checkpoints whose limbs differ only in how they represent the
+    /// same field elements produce the same bytes.
+    ///
+    /// Returns an error if the accumulator is not one the step-wise loop
+    /// could have produced, as for [`compress_checkpoint`].
+    pub fn to_canonical_bytes(&self) -> Result<[u8; Checkpoint::CANONICAL_BYTE_LEN], StepError> {
+        let r = try_deserialize_r_from_backup(self.projective_point)?;
+        let mut bytes = [0u8; Checkpoint::CANONICAL_BYTE_LEN];
+        bytes[0..4].copy_from_slice(&self.basepoint.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.index.to_le_bytes());
+        bytes[6..38].copy_from_slice(&r.X.as_bytes());
+        bytes[38..70].copy_from_slice(&r.Y.as_bytes());
+        bytes[70..102].copy_from_slice(&r.Z.as_bytes());
+        let crc = crc32(&bytes[..102]);
+        bytes[102..].copy_from_slice(&crc.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Load a checkpoint written by [`Checkpoint::to_canonical_bytes`],
+    /// possibly by a build with a different backend.
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::Length)` if `bytes` is not exactly
+    ///   [`Checkpoint::CANONICAL_BYTE_LEN`] bytes long;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match;
+    /// - `Err(BackupError::NonCanonicalField)` if a coordinate is not a
+    ///   canonical field element encoding.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Checkpoint, BackupError> {
+        if bytes.len() != Checkpoint::CANONICAL_BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: Checkpoint::CANONICAL_BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut crc = [0u8; 4];
+        crc.copy_from_slice(&bytes[102..]);
+        if crc32(&bytes[..102]) != u32::from_le_bytes(crc) {
+            return Err(BackupError::Checksum);
+        }
+
+        let mut projective_point = [0u64; 15];
+        for (limbs, chunk) in projective_point
+            .chunks_exact_mut(5)
+            .zip(bytes[6..102].chunks_exact(32))
+        {
+            let mut encoding = [0u8; 32];
+            encoding.copy_from_slice(chunk);
+            let coordinate = FieldElement::from_bytes(&encoding);
+            if coordinate.as_bytes() != encoding {
+                return Err(BackupError::NonCanonicalField);
+            }
+            limbs.copy_from_slice(&coordinate_to_limbs(&coordinate));
+        }
+
+        Ok(Checkpoint {
+            basepoint: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            index: u16::from_le_bytes([bytes[4], bytes[5]]),
+            projective_point,
+        })
+    }
+
+    /// Load a checkpoint written by [`Checkpoint::to_compressed_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::Length)` if `bytes` is not exactly
+    ///   [`Checkpoint::COMPRESSED_BYTE_LEN`] bytes long;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match.
+    pub fn compressed_from_bytes(bytes: &[u8]) -> Result<CompressedCheckpoint, BackupError> {
+        if bytes.len() != Checkpoint::COMPRESSED_BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: Checkpoint::COMPRESSED_BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut crc = [0u8; 4];
+        crc.copy_from_slice(&bytes[38..]);
+        if crc32(&bytes[..38]) != u32::from_le_bytes(crc) {
+            return Err(BackupError::Checksum);
+        }
+
+        let basepoint = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let index = u16::from_le_bytes([bytes[4], bytes[5]]);
+        let mut point = [0u8; 32];
+        point.copy_from_slice(&bytes[6..38]);
+        Ok(CompressedCheckpoint {
+            basepoint,
+            index,
+            point: CompressedEdwardsY(point),
+        })
+    }
+
+    /// Serialize this checkpoint into the front of `out`, writing the
+    /// checksum last, and return the number of bytes written.
+    ///
+    /// If `out` is too short to hold the checkpoint, it is zeroed rather
+    /// than left holding a prefix, and an error is returned.
+    pub fn write_to(&self, out: &mut [u8]) -> Result<usize, BackupError> {
+        if out.len() < Checkpoint::BYTE_LEN {
+            out.iter_mut().for_each(|byte| *byte = 0);
+            return Err(BackupError::Length {
+                expected: Checkpoint::BYTE_LEN,
+                actual: out.len(),
+            });
+        }
+        out[..Checkpoint::BYTE_LEN].copy_from_slice(&self.to_bytes());
+        Ok(Checkpoint::BYTE_LEN)
+    }
+
+    /// Deserialize a checkpoint produced by [`Checkpoint::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::UnsupportedVersion)` if the leading version byte
+    ///   is not [`Checkpoint::FORMAT_VERSION`];
+    /// - `Err(BackupError::Length)` if `bytes` is not exactly
+    ///   [`Checkpoint::BYTE_LEN`] bytes long;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match, e.g. because the blob was only partially written;
+    /// - `Err(BackupError::BackendMismatch)` if the checkpoint was written
+    ///   by a build with a different field backend.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Checkpoint, BackupError> {
+        // Check the version first: another version may have another length.
+        match bytes.first() {
+            Some(&version) if version != Checkpoint::FORMAT_VERSION => {
+                return Err(BackupError::UnsupportedVersion { version });
+            }
+            _ => {}
+        }
+        if bytes.len() != Checkpoint::BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: Checkpoint::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut crc = [0u8; 4];
+        crc.copy_from_slice(&bytes[128..]);
+        if crc32(&bytes[..128]) != u32::from_le_bytes(crc) {
+            return Err(BackupError::Checksum);
+        }
+        if bytes[1] != Checkpoint::BACKEND_ID {
+            return Err(BackupError::BackendMismatch {
+                expected: Checkpoint::BACKEND_ID,
+                actual: bytes[1],
+            });
+        }
+
+        let basepoint = u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]);
+        let index = u16::from_le_bytes([bytes[6], bytes[7]]);
+        let mut projective_point = [0u64; 15];
+        for (limb, chunk) in projective_point
+            .iter_mut()
+            .zip(bytes[8..128].chunks_exact(8))
+        {
+            let mut limb_bytes = [0u8; 8];
+            limb_bytes.copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(limb_bytes);
+        }
+
+        Ok(Checkpoint {
+            basepoint,
+            index,
+            projective_point,
+        })
+    }
+}
+
+// Checkpoints serialize as the bytes of `Checkpoint::to_bytes`, so that
+// a checkpoint persisted with serde can also be read with `from_bytes`.
+
+#[cfg(feature = "serde")]
+impl Serialize for Checkpoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_encoding::serialize_chunks(serializer, 1, [self.to_bytes()])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Checkpoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_encoding::deserialize_with::<_, _, _, _, { Checkpoint::BYTE_LEN }>(
+            deserializer,
+            "a step-wise multiplication checkpoint",
+            Checkpoint::from_bytes,
+        )
+    }
+}
+
+/// Compute the CRC-32 (IEEE 802.3, reflected) of `bytes`.
+///
+/// Checkpoints are small, so this is the bitwise form rather than a
+/// table-driven one.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Return the number of nonzero digits in the width-`w` NAF of `scalar`.
+///
+/// Each nonzero digit costs one point addition in the corresponding
+/// variable-time multiplication loop, so this is the addition count
+/// contributed by `scalar` at that width.
+///
+/// # Panics
+///
+/// Panics unless \\(2 \leq w \leq 8\\).
+pub fn naf_weight(scalar: &Scalar, w: usize) -> usize {
+    assert!((2..=8).contains(&w), "NAF width must be in 2..=8");
+    scalar
+        .non_adjacent_form(w)
+        .iter()
+        .filter(|digit| **digit != 0)
+        .count()
+}
+
+/// The expected width-`w` NAF weight of a uniformly random scalar.
+///
+/// For a uniformly random \\(n\\)-bit integer the width-\\(w\\) NAF has
+/// density \\(1/(w+1)\\), so its expected weight is
+/// $$
+/// \frac{n}{w + 1}.
+/// $$
+/// Reduced scalars are uniform modulo \\( \ell \approx 2\^{252} \\), so
+/// this uses \\( n = 253 \\); e.g. about 42.2 additions at \\( w = 5 \\).
+///
+/// # Panics
+///
+/// Panics unless \\(2 \leq w \leq 8\\).
+pub fn expected_naf_weight(w: usize) -> f64 {
+    assert!((2..=8).contains(&w), "NAF width must be in 2..=8");
+    253.0 / (w as f64 + 1.0)
+}
+
+/// An upper bound on the width-`w` NAF weight of any reduced scalar.
+///
+/// A width-\\(w\\) NAF of a scalar below \\(2\^{253}\\) has at most 254
+/// digits, and any two nonzero digits are at least \\(w\\) positions
+/// apart, so at most \\( \lceil 254 / w \rceil \\) of them are nonzero.
+///
+/// # Panics
+///
+/// Panics unless \\(2 \leq w \leq 8\\).
+pub fn max_naf_weight(w: usize) -> usize {
+    assert!((2..=8).contains(&w), "NAF width must be in 2..=8");
+    (254 + w - 1) / w
+}
+
+/// Empirical NAF weight statistics over a sample of scalars, for budgeting
+/// the additions a batch of resumable jobs will perform.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NafWeightStats {
+    /// The NAF width the statistics were computed at.
+    pub width: usize,
+    /// The number of scalars sampled.
+    pub samples: usize,
+    /// The mean weight over the sample, or `0.0` for an empty sample.
+    pub mean: f64,
+    /// The smallest weight in the sample, or `0` for an empty sample.
+    pub min: usize,
+    /// The largest weight in the sample, or `0` for an empty sample.
+    pub max: usize,
+}
+
+impl NafWeightStats {
+    /// Compute the width-`w` NAF weight statistics of `scalars`.
+    ///
+    /// Use [`expected_naf_weight`] and [`max_naf_weight`] instead when no
+    /// sample is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless \\(2 \leq w \leq 8\\).
+    pub fn from_sample<I>(scalars: I, w: usize) -> NafWeightStats
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Scalar>,
+    {
+        let mut stats = NafWeightStats {
+            width: w,
+            samples: 0,
+            mean: 0.0,
+            min: usize::MAX,
+            max: 0,
+        };
+        let mut total = 0usize;
+        for scalar in scalars {
+            let weight = naf_weight(scalar.borrow(), w);
+            stats.samples += 1;
+            stats.min = stats.min.min(weight);
+            stats.max = stats.max.max(weight);
+            total += weight;
+        }
+
+        if stats.samples == 0 {
+            stats.min = 0;
+        } else {
+            stats.mean = total as f64 / stats.samples as f64;
+        }
+        stats
+    }
+}
+
+/// The length of a NAF packed by [`pack_naf`].
+pub const PACKED_NAF_LEN: usize = 33;
+
+/// Pack the width-`w` NAF digits `naf` into [`PACKED_NAF_LEN`] bytes, for
+/// caching alongside a checkpoint instead of 256 bytes of `i8`s.
+///
+/// # Packing
+///
+/// The digits are written from index 0 upwards as a stream of bits, filling
+/// each byte from its least significant bit:
+///
+/// * a zero digit is the single bit `0`;
+/// * a nonzero digit \\(d\\) is the bit `1`, then a sign bit (`1` when
+///   \\(d < 0\\)), then \\( (|d| - 1)/2 \\) as a \\((w-2)\\)-bit integer,
+///   least significant bit first.  The \\(w - 1\\) digits after it are zero
+///   in any width-\\(w\\) NAF, so they are skipped rather than written.
+///
+/// A nonzero digit and the zeros it implies take \\(w\\) bits for \\(w\\)
+/// digits, so 256 digits fit in 256 bits, plus at most \\(w - 1 \leq 7\\)
+/// bits when a nonzero digit sits within \\(w - 1\\) positions of the end.
+/// Unused trailing bits are zero.
+///
+/// # Panics
+///
+/// Panics unless \\(2 \leq w \leq 8\\), or if `naf` is not a width-`w`
+/// NAF: a digit is even, has magnitude at least \\(2\^{w-1}\\), or follows
+/// a nonzero digit by fewer than \\(w\\) positions.
+pub fn pack_naf(naf: &[i8; 256], w: usize) -> [u8; PACKED_NAF_LEN] {
+    assert!((2..=8).contains(&w), "NAF width must be in 2..=8");
+
+    let mut packed = [0u8; PACKED_NAF_LEN];
+    let mut bit = 0usize;
+    let mut push = |value: u8, len: usize| {
+        for j in 0..len {
+            packed[bit / 8] |= ((value >> j) & 1) << (bit % 8);
+            bit += 1;
+        }
+    };
+
+    let mut i = 0;
+    while i < 256 {
+        let digit = naf[i];
+        if digit == 0 {
+            push(0, 1);
+            i += 1;
+            continue;
+        }
+
+        let magnitude = digit.unsigned_abs();
+        assert!(
+            magnitude % 2 == 1 && magnitude < 1 << (w - 1),
+            "digit {} at index {} is not a width-{} NAF digit",
+            digit,
+            i,
+            w
+        );
+        push(1, 1);
+        push((digit < 0) as u8, 1);
+        push((magnitude - 1) / 2, w - 2);
+
+        let end = (i + w).min(256);
+        assert!(
+            naf[i + 1..end].iter().all(|d| *d == 0),
+            "nonzero digits within {} positions of index {}",
+            w,
+            i
+        );
+        i = end;
+    }
+    packed
+}
+
+/// Unpack width-`w` NAF digits packed by [`pack_naf`].
+///
+/// Every bit string decodes to some width-`w` NAF, so this cannot fail;
+/// bits left over after the 256th digit are ignored.
+///
+/// # Panics
+///
+/// Panics unless \\(2 \leq w \leq 8\\).
+pub fn unpack_naf(packed: &[u8; PACKED_NAF_LEN], w: usize) -> [i8; 256] {
+    assert!((2..=8).contains(&w), "NAF width must be in 2..=8");
+
+    let mut bit = 0usize;
+    let mut pull = |len: usize| {
+        let mut value = 0u8;
+        for j in 0..len {
+            value |= ((packed[bit / 8] >> (bit % 8)) & 1) << j;
+            bit += 1;
+        }
+        value
+    };
+
+    let mut naf = [0i8; 256];
+    let mut i = 0;
+    while i < 256 {
+        if pull(1) == 0 {
+            i += 1;
+            continue;
+        }
+        let negative = pull(1) == 1;
+        let magnitude = (2 * pull(w - 2) + 1) as i8;
+        naf[i] = if negative { -magnitude } else { magnitude };
+        i += w;
+    }
+    naf
+}
+
+/// Compare two completed step-wise results as Ristretto points.
+///
+/// Two computations which should agree as Ristretto points may finish on
+/// different Edwards representatives, differing by a point of order
+/// dividing 4, so comparing the `EdwardsPoint`s directly can report a
+/// spurious mismatch.  This applies Ristretto equality instead.
+///
+/// # Return
+///
+/// - `None` if `P` and `Q` are equal as Ristretto points;
+/// - `Some(P - Q)` otherwise, as a Ristretto point, to help track down
+///   which computation went wrong.
+pub fn ristretto_difference(P: &EdwardsPoint, Q: &EdwardsPoint) -> Option<RistrettoPoint> {
+    let (P, Q) = (RistrettoPoint(*P), RistrettoPoint(*Q));
+    if P == Q {
+        None
+    } else {
+        Some(P - Q)
+    }
+}
+
+/// Return the index a step-wise multiplication of \\(aA + bB\\) starts
+/// from: the highest index at which the NAF of \\(a\\) or of \\(b\\) has a
+/// nonzero digit.
+///
+/// Store it alongside the job to report progress with
+/// [`Checkpoint::percent_complete`].
+pub fn start_index(a: &Scalar, b: &Scalar) -> usize {
+    step_start_index(a, b)
+}
+
+/// Return the number of loop iterations a step-wise multiplication of
+/// \\(aA + bB\\) performs, one doubling each.
+///
+/// The count does not depend on \\(A\\) and is exact, so
+/// `(estimated_steps(a, b) + budget - 1) / budget` is the number of calls a
+/// multiplication with a step budget of `budget` takes.  For the additions
+/// as well, see the [`CostModel`] of [`DoubleBaseMul`].
+pub fn estimated_steps(a: &Scalar, b: &Scalar) -> usize {
+    step_start_index(a, b) + 1
+}
+
+/// Clamp `steps` to the steps of a computation taking `total` steps.
+pub(crate) fn clamp_steps(steps: Range<usize>, total: usize) -> Range<usize> {
+    let end = steps.end.min(total);
+    steps.start.min(end)..end
+}
+
+/// Compute \\(aA + bB\\) in variable time in a single call, reporting a
+/// [`Checkpoint`] to `progress` after every `every_n_doublings` doublings.
+///
+/// This is for callers who want to observe a long multiplication, e.g. to
+/// drive a progress bar or an audit log, but have no need to suspend it;
+/// the result is the same as [`EdwardsPoint::vartime_double_scalar_mul_basepoint`].
+/// Each checkpoint reported is one a [`JobHandle`] could resume from.
+///
+/// # Panics
+///
+/// Panics if `every_n_doublings` is zero.
+pub fn mul_with_progress<F: FnMut(Checkpoint)>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    every_n_doublings: usize,
+    mut progress: F,
+) -> EdwardsPoint {
+    serial_mul_with_progress(a, A, b, every_n_doublings, |i, limbs| {
+        progress(Checkpoint::new(i, limbs))
+    })
+}
+
+/// Where a call to [`step_mul`] starts from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StepMulState {
+    /// Start a new multiplication.
+    Fresh,
+    /// Continue a multiplication from the checkpoint an earlier call
+    /// returned in [`StepResult::InProgress`].
+    Resumed(Checkpoint),
+}
+
+impl StepMulState {
+    /// The index and accumulator the NAF-digit loop starts this call from:
+    /// the fresh-start index 300, or the checked contents of the
+    /// checkpoint.
+    pub(crate) fn to_start(&self) -> Result<(usize, ProjectivePoint), StepError> {
+        match self {
+            StepMulState::Fresh => Ok((300, ProjectivePoint::identity())),
+            StepMulState::Resumed(checkpoint) => {
+                check_start_index(checkpoint.index())?;
+                checkpoint.check_basepoint()?;
+                Ok((
+                    checkpoint.index(),
+                    try_deserialize_r_from_backup(checkpoint.projective_point())?,
+                ))
+            }
+        }
+    }
+}
+
+/// The outcome of a call to [`step_mul`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StepResult {
+    /// The call ran out of budget; pass the checkpoint back as
+    /// [`StepMulState::Resumed`] to continue.
+    InProgress(Checkpoint),
+    /// The multiplication is complete.
+    Done(EdwardsPoint),
+}
+
+/// Perform up to [`DEFAULT_STEP_BUDGET`] loop iterations of the variable-time
+/// computation of \\(aA + bB\\), where \\(B\\) is the Ed25519 basepoint.
+///
+/// This is [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`] with
+/// its sentinel values replaced by types: a fresh start is
+/// [`StepMulState::Fresh`] rather than index 300, and the outcome is a
+/// [`StepResult`] rather than a point and a status byte.  Like
+/// [`JobHandle`], it always drives the serial step-wise loop.
+///
+/// # Return
+///
+/// - `Ok` with the outcome of the call;
+/// - `Err(StepError::IndexOutOfRange)` if a resumed checkpoint's index is
+///   not one the step-wise loop can resume from;
+/// - `Err(StepError::BasepointMismatch)` if a resumed checkpoint was written
+///   by a build with a different basepoint;
+/// - `Err(StepError::CheckpointLimbOutOfRange)`,
+///   `Err(StepError::CheckpointZeroZ)`, or
+///   `Err(StepError::CheckpointNotOnCurve)` if a resumed checkpoint's
+///   accumulator is not a valid point.
+pub fn step_mul(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    state: StepMulState,
+) -> Result<StepResult, StepError> {
+    PrecomputedStepMulContext::new(a, A, b).step(state)
+}
+
+/// Check that `checkpoint` holds the accumulator that the step-wise
+/// computation of \\(aA + bB\\) reaches at the checkpoint's index.
+///
+/// The checksum of [`Checkpoint::to_bytes`] catches a blob damaged between
+/// writing and reading it, and the accumulator checks of [`step_mul`]
+/// catch limbs that are not a curve point; neither catches a checkpoint
+/// that is intact but wrong, e.g. one tampered with to hold another point,
+/// corrupted before it was checksummed, or saved by a job with other
+/// inputs.  Resuming from such a checkpoint spends the rest of the budget
+/// computing a wrong result.  This recomputes the accumulator from the NAF
+/// digits above the checkpoint's index and compares it with the stored
+/// one, up to projective equivalence, so that it also accepts a checkpoint
+/// that has been [re-randomized](Checkpoint::rerandomize).
+///
+/// The check costs as many loop iterations as the computation has already
+/// performed, so it pays off for checkpoints taken early, or for jobs whose
+/// remaining work is more expensive to waste than to check.
+///
+/// # Return
+///
+/// - `Ok(())` if the checkpoint matches, or if its index is the fresh-start
+///   value 300, which resumes from scratch whatever its accumulator;
+/// - `Err(StepError::CheckpointMismatch)` if the accumulator is a valid
+///   point other than the expected one;
+/// - the other errors of [`step_mul`] if the checkpoint cannot be resumed
+///   from at all.
+pub fn verify_checkpoint(
+    checkpoint: &Checkpoint,
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+) -> Result<(), StepError> {
+    PrecomputedStepMulContext::new(a, A, b).verify_checkpoint(checkpoint)
+}
+
+/// The number of radix-16 digits a constant-time multiplication processes.
+const CT_DIGITS: usize = 64;
+
+/// Perform up to `budget` steps of the constant-time computation of
+/// \\(sP\\), with the same [`StepMulState`] and [`StepResult`] interface as
+/// [`step_mul`].
+///
+/// Each step processes one of the 64 signed radix-16 digits of \\(s\\), from
+/// the most significant, costing four doublings, a constant-time table
+/// lookup and one addition whatever the digit.  The work done by a call
+/// therefore depends only on `budget` and on the checkpoint's index, both
+/// public, and not on \\(s\\).  The checkpoint's index is that of the next
+/// digit to process.
+///
+/// The checkpointed accumulator is a multiple of \\(P\\) determined by the
+/// leading digits of \\(s\\), so checkpoints of a secret-scalar
+/// multiplication must be stored as carefully as the scalar itself.
+///
+/// Each call also builds the table of multiples of \\(P\\), seven
+/// additions, and the digits of \\(s\\); [`VariableBaseMul::step`] does the
+/// same steps without repeating them.
+///
+/// # Return
+///
+/// - `Ok` with the outcome of the call;
+/// - `Err(StepError::BudgetOutOfRange)` if `budget` is zero or more than
+///   [`MAX_STEP_BUDGET`];
+/// - `Err(StepError::IndexOutOfRange)` if a resumed checkpoint's index is
+///   not below 64;
+/// - `Err(StepError::BasepointMismatch)`, or one of the accumulator errors,
+///   if a resumed checkpoint cannot be resumed from, as for [`step_mul`].
+pub fn step_mul_constant_time(
+    point: &EdwardsPoint,
+    scalar: &Scalar,
+    state: StepMulState,
+    budget: usize,
+) -> Result<StepResult, StepError> {
+    VariableBaseMul::new(point, scalar).step(state, budget)
+}
+
+/// The setup of a step-wise multiplication of \\(aA + bB\\): the NAF digits
+/// of \\(a\\) and \\(b\\), and the table of odd multiples of \\(A\\).
+///
+/// [`step_mul`] recomputes these on every call, which takes a large share
+/// of a small budget.  Keeping a context between calls, in memory or
+/// stored with [`PrecomputedStepMulContext::to_bytes`] next to the
+/// checkpoint, lets each [`PrecomputedStepMulContext::step`] spend its
+/// whole budget on doublings and additions.
+#[derive(Copy, Clone)]
+pub struct PrecomputedStepMulContext {
+    context: StepMulContext,
+}
+
+impl Debug for PrecomputedStepMulContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PrecomputedStepMulContext {{ start_index: {} }}",
+            self.start_index()
+        )
+    }
+}
+
+impl PrecomputedStepMulContext {
+    /// The length of [`PrecomputedStepMulContext::to_bytes`].
+    pub const BYTE_LEN: usize = 4 + 1 + 2 * PACKED_NAF_LEN + 8 * 4 * 32 + 4;
+
+    /// Perform the setup of a step-wise multiplication of \\(aA + bB\\).
+    pub fn new(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> PrecomputedStepMulContext {
+        PrecomputedStepMulContext {
+            context: StepMulContext::new(a, A, b),
+        }
+    }
+
+    /// The index a fresh start processes first; see [`start_index`].
+    pub fn start_index(&self) -> usize {
+        self.context.start_index()
+    }
+
+    /// Perform up to [`DEFAULT_STEP_BUDGET`] loop iterations, as
+    /// [`step_mul`] does, without repeating the setup.
+    ///
+    /// # Return
+    ///
+    /// As for [`step_mul`].
+    pub fn step(&self, state: StepMulState) -> Result<StepResult, StepError> {
+        let (index, r) = state.to_start()?;
+
+        let saved = Cell::new(None);
+        let (point, status, _) = step_mul_with_context(
+            &self.context,
+            |i, limbs| saved.set(Some(Checkpoint::new(i, limbs))),
+            index,
+            r,
+            DEFAULT_STEP_BUDGET,
+        )?;
+        match (status, saved.take()) {
+            (1, Some(checkpoint)) => Ok(StepResult::InProgress(checkpoint)),
+            _ => Ok(StepResult::Done(point)),
+        }
+    }
+
+    /// Check `checkpoint` as [`verify_checkpoint`] does, without repeating
+    /// the setup.
+    ///
+    /// # Return
+    ///
+    /// As for [`verify_checkpoint`].
+    pub fn verify_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), StepError> {
+        check_start_index(checkpoint.index())?;
+        checkpoint.check_basepoint()?;
+        if checkpoint.index() == 300 {
+            return Ok(());
+        }
+        let stored = try_deserialize_r_from_backup(checkpoint.projective_point())?;
+
+        // The digits above the start index are all zero, so the accumulator
+        // stays the identity until the loop reaches it.
+        let steps = self.start_index().saturating_sub(checkpoint.index());
+        let expected = if steps == 0 {
+            ProjectivePoint::identity()
+        } else {
+            let saved = Cell::new(None);
+            step_mul_with_context(
+                &self.context,
+                |_, limbs| saved.set(Some(limbs)),
+                300,
+                ProjectivePoint::identity(),
+                steps,
+            )?;
+            match saved.take() {
+                Some(limbs) => deserialize_r_from_backup(limbs),
+                None => return Err(StepError::CheckpointMismatch),
+            }
+        };
+
+        if &stored.X * &expected.Z == &expected.X * &stored.Z
+            && &stored.Y * &expected.Z == &expected.Y * &stored.Z
+        {
+            Ok(())
+        } else {
+            Err(StepError::CheckpointMismatch)
+        }
+    }
+
+    /// Serialize the context, to store alongside a checkpoint.
+    ///
+    /// # Encoding
+    ///
+    /// * the [`basepoint_fingerprint`] of this build, as a 4-byte
+    ///   little-endian integer;
+    /// * the NAF width used for \\(b\\), which depends on the
+    ///   `precomputed-tables` feature;
+    /// * the NAF digits of \\(a\\) and of \\(b\\), each as [`pack_naf`];
+    /// * the eight table entries, each as its four canonically encoded
+    ///   coordinates;
+    /// * a 4-byte little-endian CRC-32 (IEEE) of everything before it.
+    ///
+    /// As with [`Checkpoint::to_bytes`], the checksum catches truncated or
+    /// partially written blobs, not deliberate tampering: checking that the
+    /// table entries really are odd multiples of one point would cost more
+    /// than rebuilding the table.
+    pub fn to_bytes(&self) -> [u8; PrecomputedStepMulContext::BYTE_LEN] {
+        let mut bytes = [0u8; PrecomputedStepMulContext::BYTE_LEN];
+        bytes[..4].copy_from_slice(&basepoint_fingerprint().to_le_bytes());
+        bytes[4] = STEP_B_NAF_WIDTH as u8;
+        bytes[5..5 + PACKED_NAF_LEN].copy_from_slice(&pack_naf(&self.context.a_naf, 5));
+        bytes[5 + PACKED_NAF_LEN..5 + 2 * PACKED_NAF_LEN]
+            .copy_from_slice(&pack_naf(&self.context.b_naf, STEP_B_NAF_WIDTH));
+
+        let mut offset = 5 + 2 * PACKED_NAF_LEN;
+        for entry in self.context.table_A.0.iter() {
+            for coordinate in [&entry.Y_plus_X, &entry.Y_minus_X, &entry.Z, &entry.T2d] {
+                bytes[offset..offset + 32].copy_from_slice(&coordinate.as_bytes());
+                offset += 32;
+            }
+        }
+
+        let body_len = PrecomputedStepMulContext::BYTE_LEN - 4;
+        let crc = crc32(&bytes[..body_len]);
+        bytes[body_len..].copy_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Load a context written by [`PrecomputedStepMulContext::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::Length)` if `bytes` is not exactly
+    ///   [`PrecomputedStepMulContext::BYTE_LEN`] bytes long;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match;
+    /// - `Err(BackupError::BasepointMismatch)` if the context was written by
+    ///   a build with a different basepoint;
+    /// - `Err(BackupError::Malformed)` if it was written by a build using a
+    ///   different NAF width for \\(b\\);
+    /// - `Err(BackupError::NonCanonicalField)` if a table coordinate is not
+    ///   a canonical field element encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<PrecomputedStepMulContext, BackupError> {
+        if bytes.len() != PrecomputedStepMulContext::BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: PrecomputedStepMulContext::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+        let (body, crc) = bytes.split_at(bytes.len() - 4);
+        if crc32(body) != u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]) {
+            return Err(BackupError::Checksum);
+        }
+        if check_basepoint_fingerprint(u32::from_le_bytes([body[0], body[1], body[2], body[3]]))
+            .is_err()
+        {
+            return Err(BackupError::BasepointMismatch);
+        }
+        if body[4] as usize != STEP_B_NAF_WIDTH {
+            return Err(BackupError::Malformed);
+        }
+
+        let mut a_packed = [0u8; PACKED_NAF_LEN];
+        a_packed.copy_from_slice(&body[5..5 + PACKED_NAF_LEN]);
+        let mut b_packed = [0u8; PACKED_NAF_LEN];
+        b_packed.copy_from_slice(&body[5 + PACKED_NAF_LEN..5 + 2 * PACKED_NAF_LEN]);
+
+        let mut coordinates = [FieldElement::ZERO; 32];
+        for (coordinate, chunk) in coordinates
+            .iter_mut()
+            .zip(body[5 + 2 * PACKED_NAF_LEN..].chunks_exact(32))
+        {
+            let mut encoding = [0u8; 32];
+            encoding.copy_from_slice(chunk);
+            *coordinate = FieldElement::from_bytes(&encoding);
+            if coordinate.as_bytes() != encoding {
+                return Err(BackupError::NonCanonicalField);
+            }
+        }
+        let mut entries = [ProjectiveNielsPoint::identity(); 8];
+        for (entry, c) in entries.iter_mut().zip(coordinates.chunks_exact(4)) {
+            *entry = ProjectiveNielsPoint {
+                Y_plus_X: c[0],
+                Y_minus_X: c[1],
+                Z: c[2],
+                T2d: c[3],
+            };
+        }
+
+        Ok(PrecomputedStepMulContext {
+            context: StepMulContext {
+                a_naf: unpack_naf(&a_packed, 5),
+                b_naf: unpack_naf(&b_packed, STEP_B_NAF_WIDTH),
+                table_A: NafLookupTable5(entries),
+            },
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PrecomputedStepMulContext {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serde_encoding::serialize_chunks(serializer, 1, [self.to_bytes()])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PrecomputedStepMulContext {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        serde_encoding::deserialize_with::<_, _, _, _, { PrecomputedStepMulContext::BYTE_LEN }>(
+            deserializer,
+            "a step-wise multiplication context",
+            PrecomputedStepMulContext::from_bytes,
+        )
+    }
+}
+
+/// The work performed by one chunk of a step-wise multiplication.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct WorkDone {
+    /// The number of point doublings, one per loop iteration.
+    pub doublings: usize,
+    /// The number of point additions, one per nonzero NAF digit processed.
+    pub additions: usize,
+}
+
+/// A summary of a whole step-wise multiplication, reported by
+/// [`JobHandle::step`] when the job completes.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct JobStats {
+    /// The total number of point doublings.
+    pub doublings: usize,
+    /// The total number of point additions.
+    pub additions: usize,
+    /// The number of chunks the job was split into.
+    pub chunks: usize,
+    /// The number of times the job was resumed from a checkpoint, i.e. one
+    /// less than the number of chunks.
+    pub resumes: usize,
+    /// The fewest doublings performed by a single chunk.
+    pub min_chunk_doublings: usize,
+    /// The most doublings performed by a single chunk.
+    pub max_chunk_doublings: usize,
+    /// The total wall time reported through [`JobHandle::add_wall_time`],
+    /// or `None` if none was reported.  This crate has no clock of its own.
+    pub wall_time: Option<Duration>,
+}
+
+impl JobStats {
+    fn record(&mut self, work: &WorkDone) {
+        if self.chunks == 0 {
+            self.min_chunk_doublings = work.doublings;
+        } else {
+            self.resumes += 1;
+        }
+        self.chunks += 1;
+        self.doublings += work.doublings;
+        self.additions += work.additions;
+        self.min_chunk_doublings = self.min_chunk_doublings.min(work.doublings);
+        self.max_chunk_doublings = self.max_chunk_doublings.max(work.doublings);
+    }
+}
+
+/// The outcome of one call to [`JobHandle::step`].
+#[derive(Clone, Debug)]
+pub enum JobStatus {
+    /// The chunk ran out of budget; `checkpoint` is where the job will
+    /// resume from.
+    InProgress {
+        /// The job's new checkpoint.
+        checkpoint: Checkpoint,
+        /// The work this chunk performed.
+        work: WorkDone,
+    },
+    /// The job is complete.
+    Done {
+        /// The result \\(aA + bB\\).
+        point: EdwardsPoint,
+        /// The work this chunk performed.
+        work: WorkDone,
+        /// A summary of the whole job, or `None` if the handle was created
+        /// with [`JobHandle::resume`] and so did not see the earlier chunks.
+        stats: Option<JobStats>,
+    },
+}
+
+/// A step-wise computation of \\(aA + bB\\), where \\(B\\) is the Ed25519
+/// basepoint, which keeps its own checkpoint and statistics between chunks.
+///
+/// This always drives the serial step-wise loop, so a job is split into
+/// chunks on every backend.
+#[derive(Clone, Debug)]
+pub struct JobHandle {
+    a: Scalar,
+    A: EdwardsPoint,
+    b: Scalar,
+    checkpoint: Option<Checkpoint>,
+    result: Option<EdwardsPoint>,
+    stats: Option<JobStats>,
+    budget: usize,
+}
+
+impl JobHandle {
+    /// Start a new job computing \\(aA + bB\\).
+    pub fn new(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> JobHandle {
+        JobHandle {
+            a: *a,
+            A: *A,
+            b: *b,
+            checkpoint: None,
+            result: None,
+            stats: Some(JobStats::default()),
+            budget: DEFAULT_STEP_BUDGET,
+        }
+    }
+
+    /// Set the number of loop iterations each [`JobHandle::step`] performs,
+    /// [`DEFAULT_STEP_BUDGET`] unless set.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the updated handle;
+    /// - `Err(StepError::BudgetOutOfRange)` if `budget` is zero or more
+    ///   than [`MAX_STEP_BUDGET`].
+    pub fn with_budget(mut self, budget: usize) -> Result<JobHandle, StepError> {
+        check_step_budget(budget)?;
+        self.budget = budget;
+        Ok(self)
+    }
+
+    /// The number of loop iterations each [`JobHandle::step`] performs.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Continue a job computing \\(aA + bB\\) from a stored checkpoint.
+    ///
+    /// The chunks before `checkpoint` are unknown to the new handle, so it
+    /// reports no [`JobStats`] on completion.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the new handle;
+    /// - `Err(StepError::IndexOutOfRange)` if the checkpoint's index is not
+    ///   one the step-wise loop can resume from.
+    /// - `Err(StepError::BasepointMismatch)` if the checkpoint was written
+    ///   by a build with a different basepoint;
+    /// - `Err(StepError::CheckpointLimbOutOfRange)`,
+    ///   `Err(StepError::CheckpointZeroZ)`, or
+    ///   `Err(StepError::CheckpointNotOnCurve)` if the checkpoint's
+    ///   accumulator is not a valid point; see
+    ///   [`EdwardsPoint::try_step_vartime_double_scalar_mul_basepoint`].
+    pub fn resume(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        checkpoint: Checkpoint,
+    ) -> Result<JobHandle, StepError> {
+        JobHandle::check_checkpoint(&checkpoint)?;
+        Ok(JobHandle {
+            a: *a,
+            A: *A,
+            b: *b,
+            checkpoint: Some(checkpoint),
+            result: None,
+            stats: None,
+            budget: DEFAULT_STEP_BUDGET,
+        })
+    }
+
+    /// Check that a handle can resume from `checkpoint`, so that
+    /// [`JobHandle::step`] can load its accumulator without checking it
+    /// again.
+    fn check_checkpoint(checkpoint: &Checkpoint) -> Result<(), StepError> {
+        check_start_index(checkpoint.index())?;
+        checkpoint.check_basepoint()?;
+        if checkpoint.index() != 300 {
+            try_deserialize_r_from_backup(checkpoint.projective_point())?;
+        }
+        Ok(())
+    }
+
+    /// The checkpoint the next chunk will resume from, or `None` if the job
+    /// has not started or is complete.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.checkpoint.clone()
+    }
+
+    /// Add `elapsed` to the wall time reported in the job's [`JobStats`].
+    pub fn add_wall_time(&mut self, elapsed: Duration) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.wall_time = Some(stats.wall_time.unwrap_or_default() + elapsed);
+        }
+    }
+
+    /// Run the next chunk of the job.
+    ///
+    /// Once the job is complete, further calls do no work and report
+    /// [`JobStatus::Done`] again.
+    pub fn step(&mut self) -> JobStatus {
+        if let Some(point) = self.result {
+            return JobStatus::Done {
+                point,
+                work: WorkDone::default(),
+                stats: self.stats,
+            };
+        }
+
+        let (index, limbs) = match &self.checkpoint {
+            Some(checkpoint) => (checkpoint.index(), checkpoint.projective_point()),
+            None => (300, [0u64; 15]),
+        };
+        let saved = Cell::new(None);
+        let (point, status, work) = step_mul_counted(
+            &self.a,
+            &self.A,
+            &self.b,
+            |i, limbs| saved.set(Some(Checkpoint::new(i, limbs))),
+            index,
+            deserialize_r_from_backup(limbs),
+            self.budget,
+        )
+        .expect("the handle's budget and checkpoint are validated");
+        if let Some(stats) = self.stats.as_mut() {
+            stats.record(&work);
+        }
+
+        match (status, saved.take()) {
+            (1, Some(checkpoint)) => {
+                self.checkpoint = Some(checkpoint.clone());
+                JobStatus::InProgress { checkpoint, work }
+            }
+            _ => {
+                self.checkpoint = None;
+                self.result = Some(point);
+                JobStatus::Done {
+                    point,
+                    work,
+                    stats: self.stats,
+                }
+            }
+        }
+    }
+}
+
+// Blinding, for jobs whose checkpoints or timing are observable by other
+// tenants of the host.  A checkpoint's limbs are exactly the projective
+// coordinates the loop computed, and the variable-time loop's additions
+// follow the NAF digits of its scalars: `Checkpoint::rerandomize` addresses
+// the first and `ScalarSplit` the second.
+#[cfg(feature = "rand_core")]
+impl Checkpoint {
+    /// Replace the accumulator \\((X:Y:Z)\\) by \\((\lambda X : \lambda Y :
+    /// \lambda Z)\\) for a uniformly random nonzero \\(\lambda\\).
+    ///
+    /// The checkpoint still holds the same group element, and a job
+    /// resumed from it produces the same result, but its limbs are no
+    /// longer the representation the loop computed.  This does not hide
+    /// the point itself, which anyone holding the checkpoint can still
+    /// recover as \\((X/Z, Y/Z)\\).  Two checkpoints holding the same point
+    /// generally compare unequal after re-randomization.
+    ///
+    /// This costs three field multiplications.
+    ///
+    /// Returns an error, leaving the checkpoint unchanged, if the
+    /// accumulator is not one the step-wise loop could have produced, as
+    /// for [`compress_checkpoint`].
+    pub fn rerandomize<R: CryptoRngCore + ?Sized>(&mut self, rng: &mut R) -> Result<(), StepError> {
+        let R = try_deserialize_r_from_backup(self.projective_point)?;
+        self.rerandomize_point(&R, rng);
+        Ok(())
+    }
+
+    /// Store \\((\lambda X : \lambda Y : \lambda Z)\\) as the accumulator,
+    /// where `point` is \\((X:Y:Z)\\), for a uniformly random nonzero
+    /// \\(\lambda\\).
+    fn rerandomize_point<R: CryptoRngCore + ?Sized>(
+        &mut self,
+        point: &ProjectivePoint,
+        rng: &mut R,
+    ) {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        let mut lambda = FieldElement::from_bytes(&bytes);
+        // A zero λ would leave no point at all; it occurs with probability
+        // about 2^-255, so replacing it by one costs no randomness.
+        let is_zero = lambda.is_zero();
+        lambda.conditional_assign(&FieldElement::ONE, is_zero);
+
+        self.projective_point = serialize_r_for_backup(&ProjectivePoint {
+            X: &point.X * &lambda,
+            Y: &point.Y * &lambda,
+            Z: &point.Z * &lambda,
+        });
+
+        #[cfg(feature = "zeroize")]
+        {
+            bytes.zeroize();
+            lambda.zeroize();
+        }
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl JobHandle {
+    /// Run the next chunk of the job like [`JobHandle::step`], then
+    /// [re-randomize](Checkpoint::rerandomize) the checkpoint it returns
+    /// and keeps for the next chunk.
+    ///
+    /// Blinded and unblinded steps can be mixed freely within a job.
+    pub fn step_blinded<R: CryptoRngCore + ?Sized>(&mut self, rng: &mut R) -> JobStatus {
+        match self.step() {
+            JobStatus::InProgress {
+                mut checkpoint,
+                work,
+            } => {
+                // The loop just produced this accumulator, so there is
+                // nothing to check.
+                let point = deserialize_r_from_backup(checkpoint.projective_point());
+                checkpoint.rerandomize_point(&point, rng);
+                self.checkpoint = Some(checkpoint.clone());
+                JobStatus::InProgress { checkpoint, work }
+            }
+            done => done,
+        }
+    }
+}
+
+/// The scalars \\(a\\) and \\(b\\) of a job computing \\(aA + bB\\), split
+/// into random shares so that the variable-time loop only ever sees shares
+/// that are independent of them.
+///
+/// [`ScalarSplit::new`] picks uniformly random \\(a_2, b_2\\) and computes
+/// the correction \\(C = a_2 A + b_2 B\\) with the constant-time
+/// multiplications.  The step-wise loop is then run on
+/// \\(a_1 = a - a_2\\) and \\(b_1 = b - b_2\\), which are uniformly random
+/// and independent of \\(a\\) and \\(b\\), so their NAF pattern reveals
+/// nothing about them; [`ScalarSplit::finish`] adds \\(C\\) to the loop's
+/// result.  The price is one constant-time variable-base multiplication and
+/// one basepoint multiplication per job.
+///
+/// The shares and the correction are as secret as \\(a\\) and \\(b\\)
+/// themselves, and a job resumed from a checkpoint must use the same
+/// split: store [`ScalarSplit::to_bytes`] wherever the scalars would have
+/// been stored.
+#[cfg(feature = "rand_core")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScalarSplit {
+    a: Scalar,
+    b: Scalar,
+    correction: EdwardsPoint,
+}
+
+#[cfg(feature = "rand_core")]
+impl ScalarSplit {
+    /// The length of [`ScalarSplit::to_bytes`].
+    pub const BYTE_LEN: usize = 3 * 32;
+
+    /// Split \\(a\\) and \\(b\\) for a job computing \\(aA + bB\\).
+    pub fn new<R: CryptoRngCore + ?Sized>(
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+        rng: &mut R,
+    ) -> ScalarSplit {
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut a2 = Scalar::random(rng);
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut b2 = Scalar::random(rng);
+        let split = ScalarSplit {
+            a: a - a2,
+            b: b - b2,
+            correction: A * a2 + EdwardsPoint::mul_base(&b2),
+        };
+
+        #[cfg(feature = "zeroize")]
+        {
+            a2.zeroize();
+            b2.zeroize();
+        }
+
+        split
+    }
+
+    /// The share \\(a_1\\) to pass to the step-wise loop in place of
+    /// \\(a\\).
+    pub fn a(&self) -> &Scalar {
+        &self.a
+    }
+
+    /// The share \\(b_1\\) to pass to the step-wise loop in place of
+    /// \\(b\\).
+    pub fn b(&self) -> &Scalar {
+        &self.b
+    }
+
+    /// Start a [`JobHandle`] computing \\(a_1 A + b_1 B\\).
+    pub fn job(&self, A: &EdwardsPoint) -> JobHandle {
+        JobHandle::new(&self.a, A, &self.b)
+    }
+
+    /// Turn the loop's result \\(a_1 A + b_1 B\\) into \\(aA + bB\\).
+    pub fn finish(&self, partial: &EdwardsPoint) -> EdwardsPoint {
+        partial + self.correction
+    }
+
+    /// Encode the split as \\(a_1\\), \\(b_1\\) and the compressed
+    /// correction, 32 bytes each.
+    pub fn to_bytes(&self) -> [u8; ScalarSplit::BYTE_LEN] {
+        let mut bytes = [0u8; ScalarSplit::BYTE_LEN];
+        bytes[..32].copy_from_slice(self.a.as_bytes());
+        bytes[32..64].copy_from_slice(self.b.as_bytes());
+        bytes[64..].copy_from_slice(self.correction.compress().as_bytes());
+        bytes
+    }
+
+    /// Decode a split written by [`ScalarSplit::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the split;
+    /// - `Err(BackupError::Length)` if `bytes` is not
+    ///   [`ScalarSplit::BYTE_LEN`] long;
+    /// - `Err(BackupError::Malformed)` if either share is not a
+    ///   canonical scalar or the correction does not decompress.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ScalarSplit, BackupError> {
+        if bytes.len() != ScalarSplit::BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: ScalarSplit::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&bytes[..32]);
+        let a = Option::<Scalar>::from(Scalar::from_canonical_bytes(scalar));
+        scalar.copy_from_slice(&bytes[32..64]);
+        let b = Option::<Scalar>::from(Scalar::from_canonical_bytes(scalar));
+        let correction = CompressedEdwardsY::from_slice(&bytes[64..])
+            .ok()
+            .and_then(|c| c.decompress());
+        match (a, b, correction) {
+            (Some(a), Some(b), Some(correction)) => Ok(ScalarSplit { a, b, correction }),
+            _ => Err(BackupError::Malformed),
+        }
+    }
+}
+
+#[cfg(all(feature = "rand_core", feature = "zeroize"))]
+impl Zeroize for ScalarSplit {
+    fn zeroize(&mut self) {
+        self.a.zeroize();
+        self.b.zeroize();
+        self.correction.zeroize();
+    }
+}
+
+#[cfg(all(feature = "rand_core", feature = "zeroize"))]
+impl Drop for ScalarSplit {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(all(feature = "rand_core", feature = "zeroize"))]
+impl ZeroizeOnDrop for ScalarSplit {}
+
+/// The state of an [`Interruptible`] scalar multiplication: the number of
+/// digits still to process, and the partial accumulator.
+#[derive(Copy, Clone, Debug)]
+pub struct MulState {
+    remaining: usize,
+    accumulator: EdwardsPoint,
+}
+
+impl MulState {
+    /// Rebuild a state of `job` from its parts, e.g. after loading them
+    /// from storage.  The parts must come from a state of the same
+    /// computation; only the digit count and that the accumulator is a
+    /// point can be checked.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the state;
+    /// - `Err(StepError::IndexOutOfRange)` if `remaining` is more than the
+    ///   [`CostModel::total_steps`] of `job`;
+    /// - `Err(StepError::CheckpointZeroZ)` if the accumulator's \\(Z\\)
+    ///   coordinate is zero;
+    /// - `Err(StepError::CheckpointNotOnCurve)` if the accumulator is not on
+    ///   the curve.
+    pub fn from_parts<J>(
+        job: &J,
+        remaining: usize,
+        accumulator: EdwardsPoint,
+    ) -> Result<MulState, StepError>
+    where
+        J: Interruptible<State = MulState> + CostModel,
+    {
+        if remaining > job.total_steps() {
+            return Err(StepError::IndexOutOfRange { index: remaining });
+        }
+        if bool::from(accumulator.Z.is_zero()) {
+            return Err(StepError::CheckpointZeroZ);
+        }
+        if !accumulator.is_valid() {
+            return Err(StepError::CheckpointNotOnCurve);
+        }
+        Ok(MulState {
+            remaining,
+            accumulator,
+        })
+    }
+
+    /// The number of digits still to process; zero once the computation
+    /// is complete.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// The partial accumulator, or the result once the computation is
+    /// complete.
+    pub fn accumulator(&self) -> EdwardsPoint {
+        self.accumulator
+    }
+
+    fn output(&self) -> Option<EdwardsPoint> {
+        if self.remaining == 0 {
+            Some(self.accumulator)
+        } else {
+            None
+        }
+    }
+}
+
+/// An [`Interruptible`] constant-time computation of \\(sP\\).
+///
+/// Each step processes one of the 64 signed radix-16 digits of \\(s\\),
+/// costing four doublings and one addition whatever the digit.
+#[derive(Copy, Clone, Debug)]
+pub struct VariableBaseMul {
+    table: LookupTable<ProjectiveNielsPoint>,
+    digits: [i8; CT_DIGITS],
+}
+
+impl VariableBaseMul {
+    /// Set up the computation of `scalar * point`.
+    ///
+    /// This builds the table of multiples of `point`, seven additions
+    /// which no step counts, and the radix-16 digits of `scalar`.
+    pub fn new(point: &EdwardsPoint, scalar: &Scalar) -> VariableBaseMul {
+        VariableBaseMul {
+            table: LookupTable::<ProjectiveNielsPoint>::from(point),
+            digits: scalar.as_radix_16(),
+        }
+    }
+
+    /// Perform up to `budget` steps, as [`step_mul_constant_time`] does,
+    /// without repeating the setup.
+    ///
+    /// # Return
+    ///
+    /// As for [`step_mul_constant_time`].
+    pub fn step(&self, state: StepMulState, budget: usize) -> Result<StepResult, StepError> {
+        check_step_budget(budget)?;
+        let (remaining, r) = match state {
+            StepMulState::Fresh => (CT_DIGITS, ProjectivePoint::identity()),
+            StepMulState::Resumed(checkpoint) => {
+                let index = checkpoint.index();
+                if index >= CT_DIGITS {
+                    return Err(StepError::IndexOutOfRange { index });
+                }
+                checkpoint.check_basepoint()?;
+                (
+                    index + 1,
+                    try_deserialize_r_from_backup(checkpoint.projective_point())?,
+                )
+            }
+        };
+
+        let (remaining, r) =
+            variable_base::step_mul(&self.table, &self.digits, remaining, r, budget);
+        if remaining == 0 {
+            Ok(StepResult::Done(r.as_extended()))
+        } else {
+            Ok(StepResult::InProgress(Checkpoint::new(
+                remaining - 1,
+                serialize_r_for_backup(&r),
+            )))
+        }
+    }
+}
+
+impl Interruptible for VariableBaseMul {
+    type State = MulState;
+    type Output = EdwardsPoint;
+
+    fn start(&self) -> MulState {
+        MulState {
+            remaining: CT_DIGITS,
+            accumulator: EdwardsPoint::identity(),
+        }
+    }
+
+    fn advance(&self, state: &mut MulState, steps: usize) -> bool {
+        for _ in 0..steps.min(state.remaining) {
+            state.remaining -= 1;
+            let Q = state.accumulator.mul_by_pow_2(4);
+            state.accumulator =
+                (&Q + &self.table.select(self.digits[state.remaining])).as_extended();
+        }
+        state.remaining == 0
+    }
+
+    fn finish(&self, state: &MulState) -> Option<EdwardsPoint> {
+        state.output()
+    }
+
+    fn progress(&self, state: &MulState) -> Option<(usize, usize)> {
+        Some((64 - state.remaining, 64))
+    }
+}
+
+/// Each step costs four doublings and one addition; the table built by
+/// [`VariableBaseMul::new`] is not counted.
+impl CostModel for VariableBaseMul {
+    fn total_steps(&self) -> usize {
+        64
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        let steps = clamp_steps(steps, self.total_steps()).len();
+        WorkDone {
+            doublings: 4 * steps,
+            additions: steps,
+        }
+    }
+}
+
+/// An [`Interruptible`] constant-time computation of \\(sB\\), where
+/// \\(B\\) is the Ed25519 basepoint.
+///
+/// This runs the same schedule as [`VariableBaseMul`] with \\(P = B\\),
+/// rather than using the precomputed basepoint tables, so each step again
+/// processes one radix-16 digit.
+#[derive(Copy, Clone, Debug)]
+pub struct BasepointMul {
+    inner: VariableBaseMul,
+}
+
+impl BasepointMul {
+    /// Set up the computation of `scalar * B`.
+    pub fn new(scalar: &Scalar) -> BasepointMul {
+        BasepointMul {
+            inner: VariableBaseMul::new(&constants::ED25519_BASEPOINT_POINT, scalar),
+        }
+    }
+}
+
+impl Interruptible for BasepointMul {
+    type State = MulState;
+    type Output = EdwardsPoint;
+
+    fn start(&self) -> MulState {
+        self.inner.start()
+    }
+
+    fn advance(&self, state: &mut MulState, steps: usize) -> bool {
+        self.inner.advance(state, steps)
+    }
+
+    fn finish(&self, state: &MulState) -> Option<EdwardsPoint> {
+        state.output()
+    }
+
+    fn progress(&self, state: &MulState) -> Option<(usize, usize)> {
+        self.inner.progress(state)
+    }
+}
+
+impl CostModel for BasepointMul {
+    fn total_steps(&self) -> usize {
+        self.inner.total_steps()
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        self.inner.work(steps)
+    }
+}
+
+/// An [`Interruptible`] variable-time computation of \\(aA + bB\\), where
+/// \\(B\\) is the Ed25519 basepoint.
+///
+/// Each step processes one NAF digit, as one iteration of the loop behind
+/// [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`].
+#[derive(Copy, Clone, Debug)]
+pub struct DoubleBaseMul {
+    a: Scalar,
+    A: EdwardsPoint,
+    b: Scalar,
+}
+
+impl DoubleBaseMul {
+    /// Set up the computation of \\(aA + bB\\).
+    pub fn new(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> DoubleBaseMul {
+        DoubleBaseMul {
+            a: *a,
+            A: *A,
+            b: *b,
+        }
+    }
+}
+
+impl Interruptible for DoubleBaseMul {
+    type State = MulState;
+    type Output = EdwardsPoint;
+
+    fn start(&self) -> MulState {
+        MulState {
+            remaining: step_start_index(&self.a, &self.b) + 1,
+            accumulator: EdwardsPoint::identity(),
+        }
+    }
+
+    fn advance(&self, state: &mut MulState, steps: usize) -> bool {
+        if state.remaining == 0 || steps == 0 {
+            return state.remaining == 0;
+        }
+
+        let saved = Cell::new(None);
+        let (point, status, _) = step_mul_counted(
+            &self.a,
+            &self.A,
+            &self.b,
+            |i, limbs| saved.set(Some((i, limbs))),
+            state.remaining - 1,
+            state.accumulator.as_projective(),
+            steps.min(MAX_STEP_BUDGET),
+        )
+        .expect("from_parts bounds a state's digit count by the total");
+        match (status, saved.take()) {
+            (1, Some((i, limbs))) => {
+                // The loop just produced these limbs from a valid state.
+                *state = MulState {
+                    remaining: i + 1,
+                    accumulator: deserialize_r_from_backup(limbs).as_extended(),
+                };
+            }
+            _ => {
+                *state = MulState {
+                    remaining: 0,
+                    accumulator: point,
+                }
+            }
+        }
+        state.remaining == 0
+    }
+
+    fn finish(&self, state: &MulState) -> Option<EdwardsPoint> {
+        state.output()
+    }
+
+    fn progress(&self, state: &MulState) -> Option<(usize, usize)> {
+        let total = self.total_steps();
+        Some((total - state.remaining, total))
+    }
+}
+
+/// Each step costs one doubling, and one addition per nonzero NAF digit of
+/// \\(a\\) and of \\(b\\) at that step's index; a chunk of
+/// [`JobHandle::with_budget`] steps performs exactly the [`WorkDone`] it
+/// reports.
+impl CostModel for DoubleBaseMul {
+    fn total_steps(&self) -> usize {
+        estimated_steps(&self.a, &self.b)
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        let start = self.total_steps() - 1;
+        let a_naf = self.a.non_adjacent_form(5);
+        let b_naf = self.b.non_adjacent_form(STEP_B_NAF_WIDTH);
+        let mut work = WorkDone::default();
+        for i in clamp_steps(steps, start + 1).map(|step| start - step) {
+            work.doublings += 1;
+            work.additions += (a_naf[i] != 0) as usize + (b_naf[i] != 0) as usize;
+        }
+        work
+    }
+}
+
+/// An [`Interruptible`] computation which reports its progress after every
+/// chunk; see [`Interruptible::with_progress`].
+///
+/// The state is that of the wrapped computation, so a stored state can be
+/// resumed with or without the wrapper.  Computations which do not track
+/// their progress never call the hook.
+#[derive(Clone)]
+pub struct WithProgress<J, F> {
+    job: J,
+    progress: F,
+}
+
+impl<J, F> WithProgress<J, F> {
+    pub(crate) fn new(job: J, progress: F) -> WithProgress<J, F> {
+        WithProgress { job, progress }
+    }
+
+    /// The wrapped computation.
+    pub fn inner(&self) -> &J {
+        &self.job
+    }
+}
+
+impl<J: Debug, F> Debug for WithProgress<J, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithProgress")
+            .field("job", &self.job)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<J: Interruptible, F: Fn(usize, usize)> Interruptible for WithProgress<J, F> {
+    type State = J::State;
+    type Output = J::Output;
+
+    fn start(&self) -> J::State {
+        self.job.start()
+    }
+
+    fn advance(&self, state: &mut J::State, steps: usize) -> bool {
+        let done = self.job.advance(state, steps);
+        if let Some((steps_done, total)) = self.job.progress(state) {
+            (self.progress)(steps_done, total);
+        }
+        done
+    }
+
+    fn finish(&self, state: &J::State) -> Option<J::Output> {
+        self.job.finish(state)
+    }
+
+    fn progress(&self, state: &J::State) -> Option<(usize, usize)> {
+        self.job.progress(state)
+    }
+}
+
+/// One record of the audit log of a scalar multiplication; see
+/// [`WithAuditLog`].
+///
+/// The accumulators are recorded by their compressed encodings, which are
+/// canonical and determine the point, so a log which replays cleanly
+/// records exactly the accumulators of a faithful run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecord {
+    /// The number of steps completed before the call, which identifies the
+    /// chunk.
+    pub start: usize,
+    /// The number of steps completed after the call.
+    pub end: usize,
+    /// The accumulator before the call.
+    pub accumulator_in: CompressedEdwardsY,
+    /// The accumulator after the call.
+    pub accumulator_out: CompressedEdwardsY,
+}
+
+impl AuditRecord {
+    /// The length of [`AuditRecord::to_bytes`].
+    pub const BYTE_LEN: usize = 4 + 4 + 32 + 32;
+
+    fn new(total: usize, before: &MulState, after: &MulState) -> AuditRecord {
+        AuditRecord {
+            start: total - before.remaining,
+            end: total - after.remaining,
+            accumulator_in: before.accumulator.compress(),
+            accumulator_out: after.accumulator.compress(),
+        }
+    }
+
+    /// Serialize this record as its start and end steps, each as a 4-byte
+    /// little-endian integer, followed by the two accumulators.
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the encoding;
+    /// - `Err(StepError::IndexOutOfRange)` if the start or end step does
+    ///   not fit in four bytes.
+    pub fn to_bytes(&self) -> Result<[u8; AuditRecord::BYTE_LEN], StepError> {
+        let step =
+            |index: usize| u32::try_from(index).map_err(|_| StepError::IndexOutOfRange { index });
+        let mut bytes = [0u8; AuditRecord::BYTE_LEN];
+        bytes[..4].copy_from_slice(&step(self.start)?.to_le_bytes());
+        bytes[4..8].copy_from_slice(&step(self.end)?.to_le_bytes());
+        bytes[8..40].copy_from_slice(self.accumulator_in.as_bytes());
+        bytes[40..].copy_from_slice(self.accumulator_out.as_bytes());
+        Ok(bytes)
+    }
+
+    /// Load a record written by [`AuditRecord::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the record;
+    /// - `Err(BackupError::Length)` if `bytes` is not
+    ///   [`AuditRecord::BYTE_LEN`] long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AuditRecord, BackupError> {
+        if bytes.len() != AuditRecord::BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: AuditRecord::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+        let mut accumulator_in = [0u8; 32];
+        let mut accumulator_out = [0u8; 32];
+        accumulator_in.copy_from_slice(&bytes[8..40]);
+        accumulator_out.copy_from_slice(&bytes[40..]);
+        Ok(AuditRecord {
+            start: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+            end: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize,
+            accumulator_in: CompressedEdwardsY(accumulator_in),
+            accumulator_out: CompressedEdwardsY(accumulator_out),
+        })
+    }
+}
+
+/// An [`Interruptible`] scalar multiplication which passes an
+/// [`AuditRecord`] to `log` after every
+/// [`advance`](Interruptible::advance), for [`replay_and_verify`] to check
+/// later.
+///
+/// Like [`WithProgress`], the state is that of the wrapped computation, so
+/// a computation can be stored and resumed between records: a resumed
+/// state which differs from the stored one shows up as a record whose
+/// input accumulator the replay does not reach.  Each record costs two
+/// point compressions.
+///
+/// The log records the accumulators, so the log of a secret-scalar
+/// multiplication must be stored as carefully as its checkpoints.
+///
+/// ```
+/// use core::cell::RefCell;
+/// use curve25519_dalek::constants;
+/// use curve25519_dalek::resumable::{replay_and_verify, VariableBaseMul, WithAuditLog};
+/// use curve25519_dalek::scalar::Scalar;
+/// use curve25519_dalek::traits::Interruptible;
+///
+/// let P = constants::ED25519_BASEPOINT_POINT;
+/// let s = Scalar::from(1234567u64);
+///
+/// let log = RefCell::new(Vec::new());
+/// let job = WithAuditLog::new(VariableBaseMul::new(&P, &s), |record| {
+///     log.borrow_mut().push(record)
+/// });
+/// let mut state = job.start();
+/// while !job.advance(&mut state, 10) {}
+///
+/// let log = log.into_inner();
+/// assert_eq!(log.len(), 7);
+/// assert_eq!(
+///     replay_and_verify(&log, &VariableBaseMul::new(&P, &s)),
+///     Ok(s * P)
+/// );
+/// ```
+#[derive(Clone)]
+pub struct WithAuditLog<J, F> {
+    job: J,
+    log: F,
+}
+
+impl<J, F> WithAuditLog<J, F>
+where
+    J: Interruptible<State = MulState>,
+    F: Fn(AuditRecord),
+{
+    /// Wrap `job` so that every call to
+    /// [`advance`](Interruptible::advance) is recorded with `log`.
+    pub fn new(job: J, log: F) -> WithAuditLog<J, F> {
+        WithAuditLog { job, log }
+    }
+}
+
+impl<J, F> WithAuditLog<J, F> {
+    /// The wrapped computation.
+    pub fn inner(&self) -> &J {
+        &self.job
+    }
+}
+
+impl<J: Debug, F> Debug for WithAuditLog<J, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithAuditLog")
+            .field("job", &self.job)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<J, F> Interruptible for WithAuditLog<J, F>
+where
+    J: Interruptible<State = MulState>,
+    F: Fn(AuditRecord),
+{
+    type State = MulState;
+    type Output = J::Output;
+
+    fn start(&self) -> MulState {
+        self.job.start()
+    }
+
+    fn advance(&self, state: &mut MulState, steps: usize) -> bool {
+        let before = *state;
+        let done = self.job.advance(state, steps);
+        let total = self.job.start().remaining;
+        (self.log)(AuditRecord::new(total, &before, state));
+        done
+    }
+
+    fn finish(&self, state: &MulState) -> Option<J::Output> {
+        self.job.finish(state)
+    }
+
+    fn progress(&self, state: &MulState) -> Option<(usize, usize)> {
+        self.job.progress(state)
+    }
+}
+
+/// Re-execute `job` chunk by chunk as recorded in `transcript`, a log
+/// written by [`WithAuditLog`], and check each record against the replay.
+///
+/// The records must be in order, from the start of the computation to its
+/// end, each starting at the step the previous one ended at.  Replaying
+/// costs as much as the original computation, plus two point compressions
+/// per record.
+///
+/// # Return
+///
+/// - `Ok` with the result of the computation if every record matches;
+/// - `Err(AuditError::OutOfOrder)` if a record does not start where the
+///   previous one ended, or claims more steps than the computation has;
+/// - `Err(AuditError::AccumulatorMismatch)` if a record's accumulator
+///   differs from the replay's;
+/// - `Err(AuditError::Incomplete)` if the computation is not complete
+///   after the last record.
+pub fn replay_and_verify<J>(transcript: &[AuditRecord], job: &J) -> Result<J::Output, AuditError>
+where
+    J: Interruptible<State = MulState>,
+{
+    let mut state = job.start();
+    let total = state.remaining;
+    for (record, entry) in transcript.iter().enumerate() {
+        if entry.start != total - state.remaining || entry.end < entry.start {
+            return Err(AuditError::OutOfOrder { record });
+        }
+        if entry.accumulator_in != state.accumulator.compress() {
+            return Err(AuditError::AccumulatorMismatch { record });
+        }
+        job.advance(&mut state, entry.end - entry.start);
+        if entry.end != total - state.remaining {
+            return Err(AuditError::OutOfOrder { record });
+        }
+        if entry.accumulator_out != state.accumulator.compress() {
+            return Err(AuditError::AccumulatorMismatch { record });
+        }
+    }
+    job.finish(&state).ok_or(AuditError::Incomplete)
+}
+
+/// A [`Future`](core::future::Future) driving an [`Interruptible`]
+/// computation for async hosts.
+///
+/// Each poll advances the computation by up to `steps_per_poll` steps.  If
+/// that does not complete it, the future wakes its task and returns
+/// `Pending`, yielding to the executor so that other tasks run between
+/// chunks instead of the whole computation blocking one poll.
+#[cfg(feature = "future")]
+#[derive(Clone, Debug)]
+pub struct StepFuture<J: Interruptible> {
+    job: J,
+    state: J::State,
+    steps_per_poll: usize,
+}
+
+#[cfg(feature = "future")]
+impl<J: Interruptible> StepFuture<J> {
+    /// Drive `job` from its start, `steps_per_poll` steps per poll.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps_per_poll` is zero.
+    pub fn new(job: J, steps_per_poll: usize) -> StepFuture<J> {
+        let state = job.start();
+        StepFuture::resume(job, state, steps_per_poll)
+    }
+
+    /// Drive `job` from `state`, e.g. a state stored by an earlier run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps_per_poll` is zero.
+    pub fn resume(job: J, state: J::State, steps_per_poll: usize) -> StepFuture<J> {
+        assert!(steps_per_poll > 0, "steps_per_poll must be nonzero");
+        StepFuture {
+            job,
+            state,
+            steps_per_poll,
+        }
+    }
+
+    /// The state reached so far, to store if the future is dropped before
+    /// it completes.
+    pub fn state(&self) -> &J::State {
+        &self.state
+    }
+}
+
+#[cfg(feature = "future")]
+impl<J> core::future::Future for StepFuture<J>
+where
+    J: Interruptible + Unpin,
+    J::State: Unpin,
+{
+    type Output = J::Output;
+
+    fn poll(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<J::Output> {
+        let this = self.get_mut();
+        if this.job.advance(&mut this.state, this.steps_per_poll) {
+            if let Some(output) = this.job.finish(&this.state) {
+                return core::task::Poll::Ready(output);
+            }
+        }
+        cx.waker().wake_by_ref();
+        core::task::Poll::Pending
+    }
+}
+
+/// An [`Interruptible`] construction of an [`EdwardsBasepointTable`] for
+/// an arbitrary point, for when
+/// [`BasepointTable::create`](crate::traits::BasepointTable::create) is too
+/// expensive for a single call.
+///
+/// Each step computes one of the table's 256 entries \\(j \cdot 16^{2i}
+/// B\\): the first entry of each of the 32 subtables costs eight doublings
+/// of the previous subtable's first entry, and every other entry one
+/// addition.  Each entry is also normalized to affine coordinates, which
+/// costs a field inversion.  The [`BasepointTableState`] between steps is
+/// the partially filled table, which can be saved with
+/// [`BasepointTableState::to_bytes`].
+#[cfg(feature = "precomputed-tables")]
+#[derive(Copy, Clone, Debug)]
+pub struct BasepointTableBuild {
+    basepoint: EdwardsPoint,
+}
+
+/// The partially filled table of a [`BasepointTableBuild`].
+#[cfg(feature = "precomputed-tables")]
+#[derive(Clone, Debug)]
+pub struct BasepointTableState {
+    table: EdwardsBasepointTable,
+    done: usize,
+}
+
+#[cfg(feature = "precomputed-tables")]
+impl BasepointTableBuild {
+    /// The number of entries, and so of steps, in a table.
+    const ENTRIES: usize = 32 * 8;
+
+    /// Set up the construction of the table of multiples of `basepoint`.
+    pub fn new(basepoint: &EdwardsPoint) -> BasepointTableBuild {
+        BasepointTableBuild {
+            basepoint: *basepoint,
+        }
+    }
+}
+
+#[cfg(feature = "precomputed-tables")]
+impl Interruptible for BasepointTableBuild {
+    type State = BasepointTableState;
+    type Output = EdwardsBasepointTable;
+
+    fn start(&self) -> BasepointTableState {
+        BasepointTableState {
+            table: EdwardsBasepointTable([LookupTableRadix16::default(); 32]),
+            done: 0,
+        }
+    }
+
+    fn advance(&self, state: &mut BasepointTableState, steps: usize) -> bool {
+        let identity = EdwardsPoint::identity();
+        let tables = &mut state.table.0;
+        for _ in 0..steps.min(Self::ENTRIES - state.done) {
+            let (i, j) = (state.done / 8, state.done % 8);
+            let entry = if state.done == 0 {
+                self.basepoint
+            } else if j == 0 {
+                // 16^{2i} B = 256 * 16^{2(i-1)} B
+                (&identity + &tables[i - 1].0[0])
+                    .as_extended()
+                    .mul_by_pow_2(8)
+            } else {
+                // (j+1) * 16^{2i} B = 16^{2i} B + j * 16^{2i} B
+                let base = (&identity + &tables[i].0[0]).as_extended();
+                (&base + &tables[i].0[j - 1]).as_extended()
+            };
+            tables[i].0[j] = entry.as_affine_niels();
+            state.done += 1;
+        }
+        state.done == Self::ENTRIES
+    }
+
+    fn finish(&self, state: &BasepointTableState) -> Option<EdwardsBasepointTable> {
+        if state.done == Self::ENTRIES {
+            Some(state.table.clone())
+        } else {
+            None
+        }
+    }
+
+    fn progress(&self, state: &BasepointTableState) -> Option<(usize, usize)> {
+        Some((state.done, Self::ENTRIES))
+    }
+}
+
+/// The first entry of each subtable after the first costs eight doublings,
+/// and every other entry one addition; the first entry is free.
+#[cfg(feature = "precomputed-tables")]
+impl CostModel for BasepointTableBuild {
+    fn total_steps(&self) -> usize {
+        Self::ENTRIES
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        let steps = clamp_steps(steps, self.total_steps());
+        let firsts = steps.clone().filter(|&k| k != 0 && k % 8 == 0).count();
+        let additions = steps.filter(|&k| k % 8 != 0).count();
+        WorkDone {
+            doublings: 8 * firsts,
+            additions,
+        }
+    }
+}
+
+#[cfg(feature = "precomputed-tables")]
+impl BasepointTableState {
+    /// The version byte [`BasepointTableState::to_bytes`] writes.
+    pub const FORMAT_VERSION: u8 = 1;
+
+    /// The length of the encoding produced by
+    /// [`BasepointTableState::to_bytes`]: a version byte, the number of
+    /// entries computed, the table and a CRC-32.
+    pub const BYTE_LEN: usize = 1 + 2 + EdwardsBasepointTable::BYTE_LEN + 4;
+
+    /// The number of table entries computed so far.
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    /// Encode this state as `version || done || table || crc32`, with
+    /// `done` as a little-endian `u16` and the table as
+    /// [`EdwardsBasepointTable::to_bytes`] encodes it.
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::BYTE_LEN);
+        bytes.push(Self::FORMAT_VERSION);
+        let done = u16::try_from(self.done).expect("a state has at most ENTRIES entries done");
+        bytes.extend_from_slice(&done.to_le_bytes());
+        bytes.extend_from_slice(&self.table.to_bytes());
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Decode a state encoded by [`BasepointTableState::to_bytes`].
+    ///
+    /// Every entry must be a valid point, as for
+    /// [`EdwardsBasepointTable::from_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<BasepointTableState, BackupError> {
+        if let Some(&version) = bytes.first() {
+            if version != Self::FORMAT_VERSION {
+                return Err(BackupError::UnsupportedVersion { version });
+            }
+        }
+        if bytes.len() != Self::BYTE_LEN {
+            return Err(BackupError::Length {
+                expected: Self::BYTE_LEN,
+                actual: bytes.len(),
+            });
+        }
+        let (body, crc) = bytes.split_at(Self::BYTE_LEN - 4);
+        if crc32(body).to_le_bytes() != crc {
+            return Err(BackupError::Checksum);
+        }
+
+        let done = u16::from_le_bytes([body[1], body[2]]) as usize;
+        if done > BasepointTableBuild::ENTRIES {
+            return Err(BackupError::Malformed);
+        }
+        let table =
+            EdwardsBasepointTable::from_bytes(&body[3..]).map_err(|_| BackupError::Malformed)?;
+        Ok(BasepointTableState { table, done })
+    }
+}
+
+/// An [`Interruptible`] variable-time Pippenger multiscalar
+/// multiplication, for sums of thousands of terms which must be spread
+/// across many calls.
+///
+/// Each step either adds one term into its bucket for the current digit
+/// column, or closes the column: sums its buckets and folds the sum into
+/// the running total.  The [`PippengerState`] between steps holds the
+/// bucket accumulators and the position within the current column.
+///
+/// The scalars' digits are computed once, by [`PippengerMul::new`], so
+/// chunks of any length cost only their steps.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PippengerMul {
+    digits: Vec<[i8; 64]>,
+    points: Vec<EdwardsPoint>,
+}
+
+/// The state of a [`PippengerMul`] between chunks.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PippengerState {
+    columns_remaining: usize,
+    term: usize,
+    buckets: Vec<EdwardsPoint>,
+    total: EdwardsPoint,
+}
+
+#[cfg(feature = "alloc")]
+impl PippengerMul {
+    /// The most terms a computation can have, so that
+    /// [`PippengerState::to_bytes`] can store the position within a
+    /// column in four bytes.
+    pub const MAX_TERMS: usize = u32::MAX as usize;
+
+    /// Set up the computation of \\(\sum\_i s\_i P\_i\\).
+    ///
+    /// # Return
+    ///
+    /// - `Err(MultiscalarError::LengthMismatch)` if there is not exactly
+    ///   one point per scalar;
+    /// - `Err(MultiscalarError::TooManyTerms)` if there are more than
+    ///   [`PippengerMul::MAX_TERMS`] terms.
+    pub fn new(
+        scalars: &[Scalar],
+        points: &[EdwardsPoint],
+    ) -> Result<PippengerMul, MultiscalarError> {
+        if scalars.len() != points.len() {
+            return Err(MultiscalarError::LengthMismatch {
+                scalars: scalars.len(),
+                points: points.len(),
+            });
+        }
+        if points.len() > PippengerMul::MAX_TERMS {
+            return Err(MultiscalarError::TooManyTerms {
+                terms: points.len(),
+            });
+        }
+        let w = Pippenger::digit_width(points.len());
+        Ok(PippengerMul {
+            digits: scalars.iter().map(|s| s.as_radix_2w(w)).collect(),
+            points: points.to_vec(),
+        })
+    }
+
+    fn digit_width(&self) -> usize {
+        Pippenger::digit_width(self.points.len())
+    }
+
+    /// Whether `state` can be a state of this computation, as
+    /// [`PippengerState::from_bytes`] checks for a stored one.
+    fn fits(&self, state: &PippengerState) -> bool {
+        let w = self.digit_width();
+        state.buckets.len() == (1 << w) / 2
+            && state.columns_remaining <= Scalar::to_radix_2w_size_hint(w)
+            && state.term <= self.points.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Interruptible for PippengerMul {
+    type State = PippengerState;
+    type Output = EdwardsPoint;
+
+    fn start(&self) -> PippengerState {
+        let w = self.digit_width();
+        PippengerState {
+            columns_remaining: Scalar::to_radix_2w_size_hint(w),
+            term: 0,
+            buckets: (0..(1 << w) / 2)
+                .map(|_| EdwardsPoint::identity())
+                .collect(),
+            total: EdwardsPoint::identity(),
+        }
+    }
+
+    /// A `state` that does not belong to this computation, e.g. because
+    /// it has a different number of buckets, is left unchanged and never
+    /// completes; [`progress`](Interruptible::progress) returns `None` for
+    /// it.
+    fn advance(&self, state: &mut PippengerState, steps: usize) -> bool {
+        if !self.fits(state) {
+            return false;
+        }
+        if state.columns_remaining == 0 || steps == 0 {
+            return state.columns_remaining == 0;
+        }
+
+        let w = self.digit_width();
+        let mut steps_left = steps;
+        while steps_left > 0 && state.columns_remaining > 0 {
+            steps_left -= 1;
+            let column = state.columns_remaining - 1;
+
+            if state.term < self.points.len() {
+                // Widen digit so that we don't run into edge cases when w=8.
+                let digit = self.digits[state.term][column] as i16;
+                let point = self.points[state.term].as_projective_niels();
+                match digit.cmp(&0) {
+                    Ordering::Greater => {
+                        let b = (digit - 1) as usize;
+                        state.buckets[b] = (&state.buckets[b] + &point).as_extended();
+                    }
+                    Ordering::Less => {
+                        let b = (-digit - 1) as usize;
+                        state.buckets[b] = (&state.buckets[b] - &point).as_extended();
+                    }
+                    Ordering::Equal => {}
+                }
+                state.term += 1;
+                continue;
+            }
+
+            // Sum the buckets with their multiplication factors, as in
+            // the one-shot Pippenger implementation.
+            let last = state.buckets.len() - 1;
+            let mut intermediate_sum = state.buckets[last];
+            let mut column_sum = state.buckets[last];
+            for bucket in state.buckets[..last].iter().rev() {
+                intermediate_sum += bucket;
+                column_sum += intermediate_sum;
+            }
+            state.total = state.total.mul_by_pow_2(w as u32) + column_sum;
+
+            for bucket in state.buckets.iter_mut() {
+                *bucket = EdwardsPoint::identity();
+            }
+            state.term = 0;
+            state.columns_remaining -= 1;
+        }
+        state.columns_remaining == 0
+    }
+
+    fn finish(&self, state: &PippengerState) -> Option<EdwardsPoint> {
+        if self.fits(state) && state.columns_remaining == 0 {
+            Some(state.total)
+        } else {
+            None
+        }
+    }
+
+    fn progress(&self, state: &PippengerState) -> Option<(usize, usize)> {
+        if !self.fits(state) {
+            return None;
+        }
+        let columns = Scalar::to_radix_2w_size_hint(self.digit_width());
+        let closed = columns - state.columns_remaining;
+        let done = closed * (self.points.len() + 1) + state.term;
+        Some((done, self.total_steps()))
+    }
+}
+
+/// A step adding a term into its bucket costs one addition if the term's
+/// digit is nonzero.  A step closing a column of \\(2\^{w-1}\\) buckets
+/// costs \\(2 (2\^{w-1} - 1)\\) additions to sum the buckets, one more
+/// to fold the sum into the total, and \\(w\\) doublings.
+#[cfg(feature = "alloc")]
+impl CostModel for PippengerMul {
+    fn total_steps(&self) -> usize {
+        Scalar::to_radix_2w_size_hint(self.digit_width()) * (self.points.len() + 1)
+    }
+
+    fn work(&self, steps: Range<usize>) -> WorkDone {
+        let w = self.digit_width();
+        let columns = Scalar::to_radix_2w_size_hint(w);
+        let per_column = self.points.len() + 1;
+        let steps = clamp_steps(steps, columns * per_column);
+        if steps.is_empty() {
+            return WorkDone::default();
+        }
+
+        let mut work = WorkDone::default();
+        for step in steps {
+            let column = columns - 1 - step / per_column;
+            match self.digits.get(step % per_column) {
+                Some(digits) => work.additions += (digits[column] != 0) as usize,
+                None => {
+                    work.additions += 2 * ((1 << w) / 2 - 1) + 1;
+                    work.doublings += w;
+                }
+            }
+        }
+        work
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PippengerState {
+    /// The number of digit columns not yet closed; zero once the
+    /// computation is complete.
+    pub fn columns_remaining(&self) -> usize {
+        self.columns_remaining
+    }
+
+    /// Serialize this state for storage between invocations.
+    ///
+    /// # Encoding
+    ///
+    /// * the number of columns remaining, as a 2-byte little-endian
+    ///   integer;
+    /// * the index of the next term in the current column, as a 4-byte
+    ///   little-endian integer;
+    /// * the number of buckets, as a 2-byte little-endian integer;
+    /// * the compressed running total, then each compressed bucket;
+    /// * a 4-byte little-endian CRC-32 (IEEE) of everything before it.
+    ///
+    /// Compressing costs one inversion per bucket, and loading one square
+    /// root per bucket.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // A state comes from `PippengerMul::start` or `from_bytes`, so it
+        // has at most 64 columns and 128 buckets, and `PippengerMul::new`
+        // bounds the terms.
+        let columns_remaining =
+            u16::try_from(self.columns_remaining).expect("a state has at most 64 columns");
+        let term = u32::try_from(self.term).expect("a job has at most MAX_TERMS terms");
+        let bucket_count =
+            u16::try_from(self.buckets.len()).expect("a state has at most 128 buckets");
+        let mut bytes = Vec::with_capacity(8 + 32 * (self.buckets.len() + 1) + 4);
+        bytes.extend_from_slice(&columns_remaining.to_le_bytes());
+        bytes.extend_from_slice(&term.to_le_bytes());
+        bytes.extend_from_slice(&bucket_count.to_le_bytes());
+        bytes.extend_from_slice(self.total.compress().as_bytes());
+        for bucket in self.buckets.iter() {
+            bytes.extend_from_slice(bucket.compress().as_bytes());
+        }
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Load a state of `job` written by [`PippengerState::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::Length)` if `bytes` does not have the length its
+    ///   bucket count calls for;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match;
+    /// - `Err(BackupError::Malformed)` if the bucket count is not the one
+    ///   the digit width of `job` calls for, if more columns remain than
+    ///   `job` has, if the next term is past the last of `job`, or if a
+    ///   point does not decompress.
+    pub fn from_bytes(job: &PippengerMul, bytes: &[u8]) -> Result<PippengerState, BackupError> {
+        if bytes.len() < 8 + 32 + 4 {
+            return Err(BackupError::Length {
+                expected: 8 + 32 + 4,
+                actual: bytes.len(),
+            });
+        }
+        let (body, crc) = bytes.split_at(bytes.len() - 4);
+        if crc32(body) != u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]) {
+            return Err(BackupError::Checksum);
+        }
+
+        let mut reader = JobReader(body);
+        let header = reader.take(8);
+        let columns_remaining = u16::from_le_bytes([header[0], header[1]]) as usize;
+        let term = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+        let bucket_count = u16::from_le_bytes([header[6], header[7]]) as usize;
+        let w = job.digit_width();
+        if bucket_count != (1 << w) / 2
+            || columns_remaining > Scalar::to_radix_2w_size_hint(w)
+            || term > job.points.len()
+        {
+            return Err(BackupError::Malformed);
+        }
+        let expected = 8 + 32 * (bucket_count + 1) + 4;
+        if bytes.len() != expected {
+            return Err(BackupError::Length {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let total = reader.point()?;
+        let buckets = (0..bucket_count)
+            .map(|_| reader.point())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PippengerState {
+            columns_remaining,
+            term,
+            buckets,
+            total,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+const JOB_HAS_CHECKPOINT: u8 = 1;
+#[cfg(feature = "alloc")]
+const JOB_HAS_RESULT: u8 = 2;
+#[cfg(feature = "alloc")]
+const JOB_HAS_STATS: u8 = 4;
+#[cfg(feature = "alloc")]
+const JOB_HAS_WALL_TIME: u8 = 8;
+
+#[cfg(feature = "alloc")]
+/// The length of a serialized [`JobHandle`]'s fixed fields: the version and
+/// flag bytes, the basepoint fingerprint, the step budget, \\(a\\), \\(A\\),
+/// and \\(b\\).
+const JOB_HEADER_LEN: usize = 1 + 1 + 4 + 2 + 3 * 32;
+
+#[cfg(feature = "alloc")]
+/// The length of a serialized [`JobStats`] without its wall time.
+const JOB_STATS_LEN: usize = 6 * 8;
+
+#[cfg(feature = "alloc")]
+/// The length of a serialized wall time: seconds and nanoseconds.
+const JOB_WALL_TIME_LEN: usize = 8 + 4;
+
+/// Reads the fields of a serialized [`JobHandle`] or [`PippengerState`] in
+/// order.
+#[cfg(feature = "alloc")]
+struct JobReader<'a>(&'a [u8]);
+
+#[cfg(feature = "alloc")]
+impl<'a> JobReader<'a> {
+    fn take(&mut self, n: usize) -> &'a [u8] {
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        head
+    }
+
+    fn bytes32(&mut self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.take(32));
+        bytes
+    }
+
+    fn u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8));
+        u64::from_le_bytes(bytes)
+    }
+
+    fn u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4));
+        u32::from_le_bytes(bytes)
+    }
+
+    fn usize(&mut self) -> Result<usize, BackupError> {
+        usize::try_from(self.u64()).map_err(|_| BackupError::Malformed)
+    }
+
+    fn scalar(&mut self) -> Result<Scalar, BackupError> {
+        Option::from(Scalar::from_canonical_bytes(self.bytes32())).ok_or(BackupError::Malformed)
+    }
+
+    fn point(&mut self) -> Result<EdwardsPoint, BackupError> {
+        CompressedEdwardsY(self.bytes32())
+            .decompress()
+            .ok_or(BackupError::Malformed)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl JobHandle {
+    /// The version written at the start of [`JobHandle::serialize`].
+    pub const FORMAT_VERSION: u8 = 2;
+
+    /// Serialize the whole job, so that it can be stored or moved as one
+    /// self-contained value and picked up with [`JobHandle::deserialize`].
+    ///
+    /// # Encoding
+    ///
+    /// * the [`JobHandle::FORMAT_VERSION`] byte;
+    /// * a flags byte recording which of the optional parts follow;
+    /// * the [`basepoint_fingerprint`] of this build, as a 4-byte
+    ///   little-endian integer;
+    /// * the [`JobHandle::budget`], as a 2-byte little-endian integer;
+    /// * \\(a\\), the compressed \\(A\\), and \\(b\\), 32 bytes each;
+    /// * the checkpoint, as [`Checkpoint::to_bytes`], if the job is under way;
+    /// * the compressed result, if the job is complete;
+    /// * the [`JobStats`] counters, each as an 8-byte little-endian
+    ///   integer, if the job keeps statistics, followed by the wall time as
+    ///   8 bytes of seconds and 4 bytes of nanoseconds if any was reported;
+    /// * a 4-byte little-endian CRC-32 (IEEE) of everything before it.
+    ///
+    /// As with [`Checkpoint::to_bytes`], the checksum catches truncated or
+    /// partially written blobs, not deliberate tampering.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.checkpoint.is_some() {
+            flags |= JOB_HAS_CHECKPOINT;
+        }
+        if self.result.is_some() {
+            flags |= JOB_HAS_RESULT;
+        }
+        if let Some(stats) = self.stats {
+            flags |= JOB_HAS_STATS;
+            if stats.wall_time.is_some() {
+                flags |= JOB_HAS_WALL_TIME;
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(JobHandle::serialized_len(flags));
+        bytes.push(JobHandle::FORMAT_VERSION);
+        bytes.push(flags);
+        bytes.extend_from_slice(&basepoint_fingerprint().to_le_bytes());
+        bytes.extend_from_slice(&(self.budget as u16).to_le_bytes());
+        bytes.extend_from_slice(self.a.as_bytes());
+        bytes.extend_from_slice(self.A.compress().as_bytes());
+        bytes.extend_from_slice(self.b.as_bytes());
+        if let Some(checkpoint) = &self.checkpoint {
+            bytes.extend_from_slice(&checkpoint.to_bytes());
+        }
+        if let Some(result) = self.result {
+            bytes.extend_from_slice(result.compress().as_bytes());
+        }
+        if let Some(stats) = self.stats {
+            for count in [
+                stats.doublings,
+                stats.additions,
+                stats.chunks,
+                stats.resumes,
+                stats.min_chunk_doublings,
+                stats.max_chunk_doublings,
+            ] {
+                bytes.extend_from_slice(&(count as u64).to_le_bytes());
+            }
+            if let Some(wall_time) = stats.wall_time {
+                bytes.extend_from_slice(&wall_time.as_secs().to_le_bytes());
+                bytes.extend_from_slice(&wall_time.subsec_nanos().to_le_bytes());
+            }
+        }
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes
+    }
+
+    /// Load a job written by [`JobHandle::serialize`].
+    ///
+    /// # Return
+    ///
+    /// - `Err(BackupError::Length)` if `bytes` is too short, or does not
+    ///   have the length its flags call for;
+    /// - `Err(BackupError::Checksum)` if the trailing checksum does not
+    ///   match;
+    /// - `Err(BackupError::UnsupportedVersion)` if the job was written in
+    ///   another format version;
+    /// - `Err(BackupError::BasepointMismatch)` if the job was written by a
+    ///   build with a different basepoint;
+    /// - `Err(BackupError::Malformed)` if a field does not decode, e.g. a
+    ///   non-canonical scalar, a step budget out of range, or a checkpoint
+    ///   the step-wise loop cannot resume from.
+    pub fn deserialize(bytes: &[u8]) -> Result<JobHandle, BackupError> {
+        let min_len = JobHandle::serialized_len(0);
+        if bytes.len() < min_len {
+            return Err(BackupError::Length {
+                expected: min_len,
+                actual: bytes.len(),
+            });
+        }
+        let (body, crc) = bytes.split_at(bytes.len() - 4);
+        if crc32(body) != u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]) {
+            return Err(BackupError::Checksum);
+        }
+
+        let mut reader = JobReader(body);
+        let header = reader.take(8);
+        if header[0] != JobHandle::FORMAT_VERSION {
+            return Err(BackupError::UnsupportedVersion { version: header[0] });
+        }
+        let flags = header[1];
+        let known = JOB_HAS_CHECKPOINT | JOB_HAS_RESULT | JOB_HAS_STATS | JOB_HAS_WALL_TIME;
+        if flags & !known != 0
+            || flags & JOB_HAS_CHECKPOINT != 0 && flags & JOB_HAS_RESULT != 0
+            || flags & JOB_HAS_WALL_TIME != 0 && flags & JOB_HAS_STATS == 0
+        {
+            return Err(BackupError::Malformed);
+        }
+        if bytes.len() != JobHandle::serialized_len(flags) {
+            return Err(BackupError::Length {
+                expected: JobHandle::serialized_len(flags),
+                actual: bytes.len(),
+            });
+        }
+        if check_basepoint_fingerprint(u32::from_le_bytes([
+            header[2], header[3], header[4], header[5],
+        ]))
+        .is_err()
+        {
+            return Err(BackupError::BasepointMismatch);
+        }
+        let budget = u16::from_le_bytes([header[6], header[7]]) as usize;
+        check_step_budget(budget).map_err(|_| BackupError::Malformed)?;
+
+        let a = reader.scalar()?;
+        let A = reader.point()?;
+        let b = reader.scalar()?;
+
+        let mut checkpoint = None;
+        if flags & JOB_HAS_CHECKPOINT != 0 {
+            let loaded = Checkpoint::from_bytes(reader.take(Checkpoint::BYTE_LEN))?;
+            match JobHandle::check_checkpoint(&loaded) {
+                Ok(()) => {}
+                Err(StepError::BasepointMismatch) => return Err(BackupError::BasepointMismatch),
+                Err(_) => return Err(BackupError::Malformed),
+            }
+            checkpoint = Some(loaded);
+        }
+
+        let mut result = None;
+        if flags & JOB_HAS_RESULT != 0 {
+            result = Some(reader.point()?);
+        }
+
+        let mut stats = None;
+        if flags & JOB_HAS_STATS != 0 {
+            let mut loaded = JobStats {
+                doublings: reader.usize()?,
+                additions: reader.usize()?,
+                chunks: reader.usize()?,
+                resumes: reader.usize()?,
+                min_chunk_doublings: reader.usize()?,
+                max_chunk_doublings: reader.usize()?,
+                wall_time: None,
+            };
+            if flags & JOB_HAS_WALL_TIME != 0 {
+                let secs = reader.u64();
+                let nanos = reader.u32();
+                if nanos >= 1_000_000_000 {
+                    return Err(BackupError::Malformed);
+                }
+                loaded.wall_time = Some(Duration::new(secs, nanos));
+            }
+            stats = Some(loaded);
+        }
+
+        Ok(JobHandle {
+            a,
+            A,
+            b,
+            checkpoint,
+            result,
+            stats,
+            budget,
+        })
+    }
+
+    /// The length of a serialized job with the given flags.
+    fn serialized_len(flags: u8) -> usize {
+        let mut len = JOB_HEADER_LEN + 4;
+        if flags & JOB_HAS_CHECKPOINT != 0 {
+            len += Checkpoint::BYTE_LEN;
+        }
+        if flags & JOB_HAS_RESULT != 0 {
+            len += 32;
+        }
+        if flags & JOB_HAS_STATS != 0 {
+            len += JOB_STATS_LEN;
+        }
+        if flags & JOB_HAS_WALL_TIME != 0 {
+            len += JOB_WALL_TIME_LEN;
+        }
+        len
+    }
+}
+
+/// Persistence for the checkpoints of step-wise multiplications, keyed by a
+/// caller-chosen job `id`.
+///
+/// [`step_mul_with_store`] loads the checkpoint for its job, runs one chunk,
+/// and saves the checkpoint the chunk ends at, so that the same loop can be
+/// backed by memory, a file, or a host's key-value storage by swapping the
+/// store.
+pub trait CheckpointStore {
+    /// The error the store reports when it cannot save or load.
+    type Error;
+
+    /// Persist `checkpoint` as the latest checkpoint of job `id`, replacing
+    /// any earlier one.
+    fn save(&mut self, id: u64, checkpoint: &Checkpoint) -> Result<(), Self::Error>;
+
+    /// The latest checkpoint saved for job `id`, or `None` if there is none.
+    fn load(&mut self, id: u64) -> Result<Option<Checkpoint>, Self::Error>;
+}
+
+/// A [`CheckpointStore`] holding the latest checkpoint of each job in memory.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoints: BTreeMap<u64, Checkpoint>,
+}
+
+#[cfg(feature = "alloc")]
+impl MemoryCheckpointStore {
+    /// An empty store.
+    pub fn new() -> MemoryCheckpointStore {
+        MemoryCheckpointStore::default()
+    }
+
+    /// Forget the checkpoint of job `id`, e.g. once its result is in.
+    pub fn remove(&mut self, id: u64) -> Option<Checkpoint> {
+        self.checkpoints.remove(&id)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CheckpointStore for MemoryCheckpointStore {
+    type Error = Infallible;
+
+    fn save(&mut self, id: u64, checkpoint: &Checkpoint) -> Result<(), Infallible> {
+        self.checkpoints.insert(id, checkpoint.clone());
+        Ok(())
+    }
+
+    fn load(&mut self, id: u64) -> Result<Option<Checkpoint>, Infallible> {
+        Ok(self.checkpoints.get(&id).cloned())
+    }
+}
+
+/// A [`CheckpointStore`] which appends every saved checkpoint to a
+/// [`std::io::Write`], and serves loads from the latest checkpoints kept in
+/// memory.
+///
+/// Each save writes one record of [`WriterCheckpointStore::RECORD_LEN`]
+/// bytes: the job id as an 8-byte little-endian integer, followed by
+/// [`Checkpoint::to_bytes`].  After a restart, the last record for a job
+/// is the checkpoint to resume from; since the checkpoint encoding ends in
+/// its checksum, a record cut short by a crash is rejected by
+/// [`Checkpoint::from_bytes`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct WriterCheckpointStore<W: std::io::Write> {
+    writer: W,
+    latest: MemoryCheckpointStore,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> WriterCheckpointStore<W> {
+    /// The length of the record written by each save.
+    pub const RECORD_LEN: usize = 8 + Checkpoint::BYTE_LEN;
+
+    /// A store appending to `writer`.
+    pub fn new(writer: W) -> WriterCheckpointStore<W> {
+        WriterCheckpointStore {
+            writer,
+            latest: MemoryCheckpointStore::new(),
+        }
+    }
+
+    /// Give back the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> CheckpointStore for WriterCheckpointStore<W> {
+    type Error = std::io::Error;
+
+    fn save(&mut self, id: u64, checkpoint: &Checkpoint) -> Result<(), std::io::Error> {
+        let mut record = [0u8; 8 + Checkpoint::BYTE_LEN];
+        record[..8].copy_from_slice(&id.to_le_bytes());
+        record[8..].copy_from_slice(&checkpoint.to_bytes());
+        self.writer.write_all(&record)?;
+        self.writer.flush()?;
+        self.latest.checkpoints.insert(id, checkpoint.clone());
+        Ok(())
+    }
+
+    fn load(&mut self, id: u64) -> Result<Option<Checkpoint>, std::io::Error> {
+        Ok(self.latest.checkpoints.get(&id).cloned())
+    }
+}
+
+/// Run one chunk of up to [`DEFAULT_STEP_BUDGET`] loop iterations of the
+/// variable-time computation of \\(aA + bB\\), resuming from and saving to
+/// job `id` in `store`.
+///
+/// A job with no stored checkpoint starts fresh.  When the chunk runs out
+/// of budget its checkpoint is saved and `Ok(None)` is returned; call again
+/// to continue.  When the multiplication completes, the result is returned
+/// and nothing is saved, so the job's last stored checkpoint is stale: use
+/// a fresh `id` for the next multiplication.
+///
+/// # Return
+///
+/// - `Ok(Some(point))` once the multiplication is complete;
+/// - `Ok(None)` if a checkpoint was saved and the job needs more calls;
+/// - `Err(StoreError::Store)` if the store failed to load or save;
+/// - `Err(StoreError::Step)` if the stored checkpoint cannot be resumed
+///   from, as for [`step_mul`].
+pub fn step_mul_with_store<S: CheckpointStore>(
+    a: &Scalar,
+    A: &EdwardsPoint,
+    b: &Scalar,
+    store: &mut S,
+    id: u64,
+) -> Result<Option<EdwardsPoint>, StoreError<S::Error>> {
+    let state = match store.load(id).map_err(StoreError::Store)? {
+        Some(checkpoint) => StepMulState::Resumed(checkpoint),
+        None => StepMulState::Fresh,
+    };
+    match step_mul(a, A, b, state).map_err(StoreError::Step)? {
+        StepResult::InProgress(checkpoint) => {
+            store.save(id, &checkpoint).map_err(StoreError::Store)?;
+            Ok(None)
+        }
+        StepResult::Done(point) => Ok(Some(point)),
+    }
+}
+
+/// The progress of a job tracked by a [`CheckpointManager`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ManagedJobStatus {
+    /// The job has been started but has not yet completed.
+    Pending,
+    /// The job has completed with this result.
+    Complete(EdwardsPoint),
+}
+
+/// Runs many step-wise multiplications through one [`CheckpointStore`],
+/// binding each job's checkpoints to its inputs.
+///
+/// A job is started with [`CheckpointManager::start`], which records a
+/// digest of its inputs \\((a, A, b)\\) under the caller's `id`.  Each
+/// [`CheckpointManager::step`] recomputes the digest of the inputs it is
+/// given and refuses to touch the job's checkpoint unless they match, so
+/// that the checkpoint of one computation is never resumed with the inputs
+/// of another; a resume with the wrong inputs would otherwise complete and
+/// return a point that is neither computation's result.
+///
+/// The digest is computed with `D`, e.g. `sha2::Sha512`, over a domain
+/// separator and the encodings of \\(a\\), \\(A\\) and \\(b\\).  Only the
+/// digests are kept, not the inputs.  Checkpoints are saved in the store
+/// under a key taken from the digest rather than under `id`, so a stale
+/// checkpoint left by a removed job, or one saved by another manager
+/// sharing the store, is never loaded for different inputs.
+///
+/// The bindings and the results of completed jobs are held in memory.  A
+/// service that persists its store across restarts starts its pending jobs
+/// again with [`CheckpointManager::start`] and the same inputs, and they
+/// pick up from their stored checkpoints.
+#[cfg(all(feature = "alloc", feature = "digest"))]
+pub struct CheckpointManager<S, D> {
+    store: S,
+    jobs: BTreeMap<u64, ManagedJob>,
+    digest: PhantomData<D>,
+}
+
+/// The binding and progress of one job of a [`CheckpointManager`].
+#[cfg(all(feature = "alloc", feature = "digest"))]
+#[derive(Clone, Debug)]
+struct ManagedJob {
+    inputs: [u8; 64],
+    result: Option<EdwardsPoint>,
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl ManagedJob {
+    /// The key of the job's checkpoint in the store.
+    fn store_key(&self) -> u64 {
+        let mut key = [0u8; 8];
+        key.copy_from_slice(&self.inputs[..8]);
+        u64::from_le_bytes(key)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl<S: Debug, D> Debug for CheckpointManager<S, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckpointManager")
+            .field("store", &self.store)
+            .field("jobs", &self.jobs)
+            .finish()
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "digest"))]
+impl<S, D> CheckpointManager<S, D>
+where
+    S: CheckpointStore,
+    D: Digest<OutputSize = U64>,
+{
+    /// A manager with no jobs, keeping checkpoints in `store`.
+    pub fn new(store: S) -> CheckpointManager<S, D> {
+        CheckpointManager {
+            store,
+            jobs: BTreeMap::new(),
+            digest: PhantomData,
+        }
+    }
+
+    /// The digest binding a job to its inputs \\((a, A, b)\\).
+    pub fn inputs_digest(a: &Scalar, A: &EdwardsPoint, b: &Scalar) -> [u8; 64] {
+        let mut output = [0u8; 64];
+        output.copy_from_slice(
+            &D::new()
+                .chain_update(b"curve25519-dalek checkpoint inputs")
+                .chain_update(a.as_bytes())
+                .chain_update(A.compress().as_bytes())
+                .chain_update(b.as_bytes())
+                .finalize(),
+        );
+        output
+    }
+
+    /// Start job `id`, computing \\(aA + bB\\).
+    ///
+    /// # Return
+    ///
+    /// - `Ok(())` once the job is bound to its inputs;
+    /// - `Err(ManagerError::DuplicateJob)` if a job with this id has already
+    ///   been started and not [removed](CheckpointManager::remove).
+    pub fn start(
+        &mut self,
+        id: u64,
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+    ) -> Result<(), ManagerError<S::Error>> {
+        if self.jobs.contains_key(&id) {
+            return Err(ManagerError::DuplicateJob { id });
+        }
+        self.jobs.insert(
+            id,
+            ManagedJob {
+                inputs: Self::inputs_digest(a, A, b),
+                result: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Run one chunk of job `id`, as [`step_mul_with_store`] does, after
+    /// checking that \\((a, A, b)\\) are the inputs it was started with.
+    ///
+    /// Once the job completes, further calls return its result again
+    /// without doing any work.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(Some(point))` once the multiplication is complete;
+    /// - `Ok(None)` if a checkpoint was saved and the job needs more calls;
+    /// - `Err(ManagerError::UnknownJob)` if no job was started with `id`;
+    /// - `Err(ManagerError::InputMismatch)` if the inputs differ from those
+    ///   the job was started with;
+    /// - `Err(ManagerError::Store)` or `Err(ManagerError::Step)` as for the
+    ///   corresponding [`StoreError`] of [`step_mul_with_store`].
+    pub fn step(
+        &mut self,
+        id: u64,
+        a: &Scalar,
+        A: &EdwardsPoint,
+        b: &Scalar,
+    ) -> Result<Option<EdwardsPoint>, ManagerError<S::Error>> {
+        let job = self
+            .jobs
+            .get_mut(&id)
+            .ok_or(ManagerError::UnknownJob { id })?;
+        if job.inputs != Self::inputs_digest(a, A, b) {
+            return Err(ManagerError::InputMismatch { id });
+        }
+        if let Some(point) = job.result {
+            return Ok(Some(point));
+        }
+        let key = job.store_key();
+        let result =
+            step_mul_with_store(a, A, b, &mut self.store, key).map_err(|err| match err {
+                StoreError::Store(err) => ManagerError::Store(err),
+                StoreError::Step(err) => ManagerError::Step(err),
+            })?;
+        job.result = result;
+        Ok(result)
+    }
+
+    /// The progress of job `id`, or `None` if no job was started with it.
+    pub fn status(&self, id: u64) -> Option<ManagedJobStatus> {
+        self.jobs.get(&id).map(|job| match job.result {
+            Some(point) => ManagedJobStatus::Complete(point),
+            None => ManagedJobStatus::Pending,
+        })
+    }
+
+    /// The ids of the jobs which have been started but have not completed,
+    /// in increasing order.
+    pub fn pending(&self) -> impl Iterator<Item = u64> + '_ {
+        self.jobs
+            .iter()
+            .filter(|(_, job)| job.result.is_none())
+            .map(|(id, _)| *id)
+    }
+
+    /// The ids of the jobs which have completed, in increasing order.
+    pub fn completed(&self) -> impl Iterator<Item = u64> + '_ {
+        self.jobs
+            .iter()
+            .filter(|(_, job)| job.result.is_some())
+            .map(|(id, _)| *id)
+    }
+
+    /// The key under which job `id` saves its checkpoints in the store, or
+    /// `None` if no job was started with it.
+    ///
+    /// Jobs with the same inputs share a key, and so share their progress.
+    pub fn store_key(&self, id: u64) -> Option<u64> {
+        self.jobs.get(&id).map(ManagedJob::store_key)
+    }
+
+    /// Forget job `id`, so that its id can be reused, returning its
+    /// progress.
+    ///
+    /// The job's last checkpoint is left in the store under its
+    /// [`CheckpointManager::store_key`], for the caller to delete (e.g.
+    /// with [`MemoryCheckpointStore::remove`]) if it no longer needs it.
+    pub fn remove(&mut self, id: u64) -> Option<ManagedJobStatus> {
+        let status = self.status(id);
+        self.jobs.remove(&id);
+        status
+    }
+
+    /// The underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Give back the underlying store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn checkpoints_to_affine_matches_compress_checkpoint() {
+        let k = FieldElement::from_bytes(&[7u8; 32]);
+        let limbs: Vec<[u64; 15]> = (1..6u64)
+            .map(|i| {
+                let mut r = (constants::ED25519_BASEPOINT_POINT * Scalar::from(i)).as_projective();
+                r.X = &r.X * &k;
+                r.Y = &r.Y * &k;
+                r.Z = &r.Z * &k;
+                serialize_r_for_backup(&r)
+            })
+            .collect();
+
+        let affine = checkpoints_to_affine(&limbs).unwrap();
+        for (i, (limbs, point)) in limbs.iter().zip(affine.iter()).enumerate() {
+            assert_eq!(point.compress(), compress_checkpoint(*limbs).unwrap());
+            assert_eq!(
+                point.to_edwards(),
+                constants::ED25519_BASEPOINT_POINT * Scalar::from(i as u64 + 1)
+            );
+        }
+
+        // A corrupted accumulator is rejected rather than compressed
+        let mut corrupted = limbs.clone();
+        corrupted[2][3] ^= 1;
+        assert_eq!(
+            compress_checkpoint(corrupted[2]),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+        assert_eq!(
+            checkpoints_to_affine(&corrupted).map(|_| ()),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn checkpoint_bytes_round_trip() {
+        let mut limbs = [0u64; 15];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = (i as u64 + 1) * 0x0007_ffff_ffff_fff1;
+        }
+        let checkpoint = Checkpoint::new(137, limbs);
+        let bytes = checkpoint.to_bytes();
+        assert_eq!(Checkpoint::from_bytes(&bytes), Ok(checkpoint.clone()));
+
+        // A blob truncated before its checksum is rejected ...
+        assert_eq!(
+            Checkpoint::from_bytes(&bytes[..126]),
+            Err(BackupError::Length {
+                expected: Checkpoint::BYTE_LEN,
+                actual: 126,
+            })
+        );
+
+        // ... as is a full-length one whose checksum was never written.
+        let mut partial = [0u8; Checkpoint::BYTE_LEN];
+        partial[..126].copy_from_slice(&bytes[..126]);
+        assert_eq!(Checkpoint::from_bytes(&partial), Err(BackupError::Checksum));
+
+        // A checkpoint from another format version or field backend is
+        // rejected with a typed error, even under a valid checksum.
+        let mut future = bytes;
+        future[0] = Checkpoint::FORMAT_VERSION + 1;
+        assert_eq!(
+            Checkpoint::from_bytes(&future[..100]),
+            Err(BackupError::UnsupportedVersion {
+                version: Checkpoint::FORMAT_VERSION + 1
+            })
+        );
+        let mut foreign = bytes;
+        foreign[1] = Checkpoint::BACKEND_ID % 4 + 1;
+        let crc = crc32(&foreign[..128]);
+        foreign[128..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            Checkpoint::from_bytes(&foreign),
+            Err(BackupError::BackendMismatch {
+                expected: Checkpoint::BACKEND_ID,
+                actual: Checkpoint::BACKEND_ID % 4 + 1,
+            })
+        );
+
+        // Writing into a short buffer leaves no partial checkpoint behind.
+        let mut short = [0xffu8; 100];
+        assert!(checkpoint.write_to(&mut short).is_err());
+        assert_eq!(short, [0u8; 100]);
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn checkpoint_zeroize() {
+        let mut checkpoint = Checkpoint::new(137, [0x0007_ffff_ffff_fff1; 15]);
+        checkpoint.zeroize();
+        assert_eq!(checkpoint.index(), 0);
+        assert_eq!(checkpoint.projective_point(), [0u64; 15]);
+    }
+
+    #[test]
+    fn checkpoint_canonical_bytes_are_portable() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let mut job = JobHandle::new(&a, &A, &b);
+        let checkpoint = match job.step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+
+        // Adding p to every coordinate changes the limbs but not the point.
+        const P_LIMBS: [u64; 5] = [
+            0x0007_ffff_ffff_ffed,
+            0x0007_ffff_ffff_ffff,
+            0x0007_ffff_ffff_ffff,
+            0x0007_ffff_ffff_ffff,
+            0x0007_ffff_ffff_ffff,
+        ];
+        let mut limbs = checkpoint.projective_point();
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb += P_LIMBS[i % 5];
+        }
+        let unreduced = Checkpoint::new(checkpoint.index(), limbs);
+        assert_ne!(unreduced.to_bytes(), checkpoint.to_bytes());
+
+        let bytes = checkpoint.to_canonical_bytes().unwrap();
+        assert_eq!(unreduced.to_canonical_bytes().unwrap(), bytes);
+
+        let loaded = Checkpoint::from_canonical_bytes(&bytes).unwrap();
+        assert_eq!(loaded.index(), checkpoint.index());
+        assert_eq!(loaded.to_canonical_bytes().unwrap(), bytes);
+        let run = |mut job: JobHandle| loop {
+            if let JobStatus::Done { point, .. } = job.step() {
+                break point;
+            }
+        };
+        assert_eq!(
+            run(JobHandle::resume(&a, &A, &b, loaded).unwrap()),
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+        );
+
+        // An encoding of p in place of Y is rejected, even with a valid
+        // checksum.
+        let mut non_canonical = bytes;
+        non_canonical[38..70].copy_from_slice(&[0xff; 32]);
+        non_canonical[38] = 0xed;
+        non_canonical[69] = 0x7f;
+        let crc = crc32(&non_canonical[..102]);
+        non_canonical[102..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            Checkpoint::from_canonical_bytes(&non_canonical),
+            Err(BackupError::NonCanonicalField)
+        );
+        assert_eq!(
+            Checkpoint::from_canonical_bytes(&bytes[..100]),
+            Err(BackupError::Length {
+                expected: Checkpoint::CANONICAL_BYTE_LEN,
+                actual: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn naf_weight_matches_expectation() {
+        let mut rng = rand::thread_rng();
+        let scalars: [Scalar; 1000] = [(); 1000].map(|_| Scalar::random(&mut rng));
+
+        for w in 2..=8 {
+            let stats = NafWeightStats::from_sample(scalars.iter(), w);
+            assert_eq!(stats.samples, 1000);
+            assert!((stats.mean - expected_naf_weight(w)).abs() < 1.0);
+            assert!(stats.min <= stats.max);
+            assert!(stats.max <= max_naf_weight(w));
+        }
+
+        assert_eq!(naf_weight(&Scalar::ZERO, 5), 0);
+        assert_eq!(naf_weight(&Scalar::ONE, 5), 1);
+        assert_eq!(NafWeightStats::from_sample(&[] as &[Scalar], 5).max, 0);
+    }
+
+    #[test]
+    fn packed_naf_round_trip() {
+        // An unreduced scalar whose top digit can land at index 255, so its
+        // packing spills past 32 bytes.
+        let mut top = [0xff; 32];
+        top[31] = 0x7f;
+        let scalars = [
+            Scalar::ZERO,
+            Scalar::ONE,
+            -Scalar::ONE,
+            Scalar::from(0xdead_beef_u64),
+            Scalar::from_bytes_mod_order([0xff; 32]),
+            Scalar { bytes: top },
+        ];
+        for w in 2..=8 {
+            for scalar in scalars.iter() {
+                let naf = scalar.non_adjacent_form(w);
+                assert_eq!(unpack_naf(&pack_naf(&naf, w), w), naf);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn pack_naf_rejects_adjacent_digits() {
+        let mut naf = [0i8; 256];
+        naf[10] = 1;
+        naf[12] = -3;
+        pack_naf(&naf, 5);
+    }
+
+    #[test]
+    fn job_stats_sum_chunk_work() {
+        let a = Scalar::from_bytes_mod_order([0x5a; 32]);
+        let b = Scalar::from_bytes_mod_order([0xc3; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(77u64));
+
+        let mut job = JobHandle::new(&a, &A, &b);
+        let mut total = WorkDone::default();
+        let mut chunks = 0;
+        let (point, stats) = loop {
+            chunks += 1;
+            job.add_wall_time(Duration::from_millis(2));
+            match job.step() {
+                JobStatus::InProgress { work, .. } => {
+                    total.doublings += work.doublings;
+                    total.additions += work.additions;
+                }
+                JobStatus::Done { point, work, stats } => {
+                    total.doublings += work.doublings;
+                    total.additions += work.additions;
+                    break (point, stats.unwrap());
+                }
+            }
+        };
+
+        assert_eq!(
+            point,
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+        );
+        assert_eq!(stats.doublings, total.doublings);
+        assert_eq!(stats.additions, total.additions);
+        assert_eq!(stats.chunks, chunks);
+        assert_eq!(stats.resumes, chunks - 1);
+        assert_eq!(stats.max_chunk_doublings, 30);
+        assert!(stats.min_chunk_doublings <= 30);
+        assert_eq!(
+            stats.wall_time,
+            Some(Duration::from_millis(2 * chunks as u64))
+        );
+
+        // A handle resumed mid-job finishes with the same point but no summary.
+        let mut job = JobHandle::new(&a, &A, &b);
+        let checkpoint = match job.step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+        let mut job = JobHandle::resume(&a, &A, &b, checkpoint.clone()).unwrap();
+        loop {
+            if let JobStatus::Done {
+                point: p, stats, ..
+            } = job.step()
+            {
+                assert_eq!(p, point);
+                assert_eq!(stats, None);
+                break;
+            }
+        }
+
+        // A corrupted index is rejected before any work is done.
+        let corrupted = Checkpoint::new(1000, checkpoint.projective_point());
+        assert_eq!(
+            JobHandle::resume(&a, &A, &b, corrupted).map(|_| ()),
+            Err(StepError::IndexOutOfRange { index: 1000 })
+        );
+
+        // So is a corrupted accumulator.
+        let mut limbs = checkpoint.projective_point();
+        limbs[3] ^= 1;
+        let corrupted = Checkpoint::new(checkpoint.index(), limbs);
+        assert_eq!(
+            JobHandle::resume(&a, &A, &b, corrupted).map(|_| ()),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn mul_with_progress_reports_periodically() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+        let doublings = start_index(&a, &b) + 1;
+
+        for every in [1, 7, 30, doublings - 1, doublings, 1000] {
+            let calls = Cell::new(0);
+            let first = Cell::new(None);
+            let last = Cell::new(None);
+            let point = mul_with_progress(&a, &A, &b, every, |checkpoint| {
+                calls.set(calls.get() + 1);
+                if calls.get() == 1 {
+                    first.set(Some(checkpoint.clone()));
+                }
+                last.set(Some(checkpoint));
+            });
+            assert_eq!(point, expected);
+            assert_eq!(calls.get(), (doublings - 1) / every);
+
+            // The first report is the checkpoint a job with that budget saves.
+            if let Some(checkpoint) = first.take() {
+                let mut job = JobHandle::new(&a, &A, &b).with_budget(every).unwrap();
+                match job.step() {
+                    JobStatus::InProgress {
+                        checkpoint: saved, ..
+                    } => {
+                        assert_eq!(saved, checkpoint)
+                    }
+                    JobStatus::Done { .. } => panic!("job finished before the first report"),
+                }
+            }
+
+            // Every reported checkpoint can be resumed from.
+            if let Some(checkpoint) = last.take() {
+                assert_eq!(checkpoint.index(), doublings - 1 - calls.get() * every);
+                let mut job = JobHandle::resume(&a, &A, &b, checkpoint).unwrap();
+                let resumed = loop {
+                    if let JobStatus::Done { point, .. } = job.step() {
+                        break point;
+                    }
+                };
+                assert_eq!(resumed, expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_with_progress_rejects_zero_interval() {
+        let one = Scalar::ONE;
+        mul_with_progress(&one, &constants::ED25519_BASEPOINT_POINT, &one, 0, |_| {});
+    }
+
+    #[test]
+    fn ristretto_equality_of_results() {
+        use constants::EIGHT_TORSION;
+
+        let P = EdwardsPoint::mul_base(&Scalar::from(1234u64));
+        assert_eq!(ristretto_difference(&P, &P), None);
+
+        // Differing by 4-torsion is the same Ristretto point ...
+        let Q = P + EIGHT_TORSION[2];
+        assert_ne!(P, Q);
+        assert_eq!(ristretto_difference(&P, &Q), None);
+
+        // ... but differing by 8-torsion, or by the basepoint, is not.
+        let Q = P + EIGHT_TORSION[1];
+        assert_eq!(
+            ristretto_difference(&P, &Q),
+            Some(RistrettoPoint(-EIGHT_TORSION[1]))
+        );
+        let Q = P + constants::ED25519_BASEPOINT_POINT;
+        assert_eq!(
+            ristretto_difference(&Q, &P),
+            Some(constants::RISTRETTO_BASEPOINT_POINT)
+        );
+    }
+
+    #[test]
+    fn checkpoint_wire_len_matches_encoding() {
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let a = Scalar::from(0x1234_5678_u64);
+        let b = Scalar::from(0x9abc_def0_u64);
+        let mut job = JobHandle::new(&a, &A, &b);
+        let checkpoint = match job.step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+
+        assert_eq!(
+            Checkpoint::wire_len(CheckpointFormat::Limbs),
+            checkpoint.to_bytes().len()
+        );
+        let compressed = checkpoint.to_compressed_bytes().unwrap();
+        assert_eq!(
+            Checkpoint::wire_len(CheckpointFormat::Compressed),
+            compressed.len()
+        );
+
+        assert_eq!(
+            Checkpoint::wire_len(CheckpointFormat::Canonical),
+            checkpoint.to_canonical_bytes().unwrap().len()
+        );
+
+        let loaded = Checkpoint::compressed_from_bytes(&compressed).unwrap();
+        assert_eq!(loaded.index(), checkpoint.index());
+        assert_eq!(
+            loaded.point(),
+            compress_checkpoint(checkpoint.projective_point()).unwrap()
+        );
+        assert_eq!(loaded.check_basepoint(), Ok(()));
+        assert_eq!(
+            Checkpoint::compressed_from_bytes(&compressed[..38]),
+            Err(BackupError::Length {
+                expected: Checkpoint::COMPRESSED_BYTE_LEN,
+                actual: 38,
+            })
+        );
+    }
+
+    #[test]
+    fn percent_complete_is_monotonic() {
+        let a = Scalar::from_bytes_mod_order([0x37; 32]);
+        let b = Scalar::from_bytes_mod_order([0x81; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(5u64));
+        let start = start_index(&a, &b);
+
+        let mut job = JobHandle::new(&a, &A, &b);
+        let mut percents = [0f32; 16];
+        let mut n = 0;
+        while let JobStatus::InProgress { checkpoint, .. } = job.step() {
+            percents[n] = checkpoint.percent_complete(start);
+            n += 1;
+        }
+
+        // 30 doublings per chunk out of about 253.
+        assert!(n > 1);
+        assert!(percents[0] > 0.0 && percents[0] < 15.0);
+        assert!(percents[..n].windows(2).all(|w| w[0] < w[1]));
+        assert!(percents[n - 1] < 100.0);
+
+        // At index 0 only the final iteration remains.
+        let last = Checkpoint::new(0, [0u64; 15]);
+        assert_eq!(
+            last.percent_complete(start),
+            100.0 * start as f32 / (start + 1) as f32
+        );
+        assert_eq!(
+            Checkpoint::new(start, [0u64; 15]).percent_complete(start),
+            0.0
+        );
+        assert_eq!(
+            Checkpoint::new(start + 5, [0u64; 15]).percent_complete(start),
+            0.0
+        );
+    }
+
+    #[test]
+    fn checkpoint_basepoint_mismatch() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x24; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(3u64));
+        let checkpoint = match JobHandle::new(&a, &A, &b).step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+        assert_eq!(checkpoint.check_basepoint(), Ok(()));
+
+        // Simulate a checkpoint written by a build with another basepoint:
+        // a different header under a valid checksum.
+        let mut bytes = checkpoint.to_bytes();
+        let foreign = basepoint_fingerprint() ^ 1;
+        bytes[2..6].copy_from_slice(&foreign.to_le_bytes());
+        let crc = crc32(&bytes[..128]);
+        bytes[128..].copy_from_slice(&crc.to_le_bytes());
+
+        let foreign = Checkpoint::from_bytes(&bytes).unwrap();
+        assert_eq!(foreign.index(), checkpoint.index());
+        assert_eq!(foreign.check_basepoint(), Err(StepError::BasepointMismatch));
+        assert_eq!(
+            JobHandle::resume(&a, &A, &b, foreign).map(|_| ()),
+            Err(StepError::BasepointMismatch)
+        );
+        assert!(JobHandle::resume(&a, &A, &b, checkpoint.clone()).is_ok());
+
+        // The compressed form carries the same fingerprint.
+        let mut bytes = checkpoint.to_compressed_bytes().unwrap();
+        bytes[0..4].copy_from_slice(&(basepoint_fingerprint() ^ 1).to_le_bytes());
+        let crc = crc32(&bytes[..38]);
+        bytes[38..].copy_from_slice(&crc.to_le_bytes());
+        let foreign = Checkpoint::compressed_from_bytes(&bytes).unwrap();
+        assert_eq!(
+            EdwardsPoint::step_vartime_double_scalar_mul_basepoint_compressed(
+                &a,
+                &A,
+                &b,
+                |_, _| {},
+                &foreign,
+            ),
+            Err(StepError::BasepointMismatch)
+        );
+    }
+
+    /// Golden values guarding in-flight checkpoints across crate upgrades;
+    /// see the module docs before changing any of them.
+    #[test]
+    fn golden_checkpoint_values() {
+        assert_eq!(basepoint_fingerprint(), 0xfdd3_f641);
+
+        let a = Scalar::from_bytes_mod_order([0x11; 32]);
+        let b = Scalar::from_bytes_mod_order([0x22; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(9u64));
+        let mut job = JobHandle::new(&a, &A, &b);
+        let checkpoint = match job.step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+
+        // The partial accumulator depends on the NAF width used for `b`.
+        #[cfg(feature = "precomputed-tables")]
+        {
+            assert_eq!(checkpoint.index(), 219);
+            assert_eq!(crc32(&checkpoint.to_bytes()), 0x2144_df1c);
+            assert_eq!(
+                checkpoint.to_compressed_bytes().unwrap(),
+                [
+                    0x41, 0xf6, 0xd3, 0xfd, 0xdb, 0x00, 0x31, 0x30, 0x52, 0x8d, 0xaf, 0xb0, 0x1b,
+                    0xcd, 0x2c, 0x3e, 0x15, 0x72, 0x48, 0x85, 0x3b, 0xef, 0xc3, 0x7c, 0x11, 0x15,
+                    0xf8, 0xa7, 0xfb, 0xee, 0xe9, 0xa3, 0x55, 0x54, 0xda, 0xe9, 0xcd, 0x3b, 0xe9,
+                    0xe4, 0x96, 0xd4,
+                ]
+            );
+        }
+
+        // A serialized checkpoint still resumes to the pinned result.
+        let restored = Checkpoint::from_bytes(&checkpoint.to_bytes()).unwrap();
+        let mut job = JobHandle::resume(&a, &A, &b, restored).unwrap();
+        let result = loop {
+            if let JobStatus::Done { point, .. } = job.step() {
+                break point;
+            }
+        };
+        assert_eq!(
+            result.compress(),
+            CompressedEdwardsY([
+                0xde, 0x9b, 0x35, 0x48, 0xd4, 0x54, 0x8e, 0xff, 0x84, 0xb7, 0xa5, 0x9c, 0x39, 0x0d,
+                0x1d, 0xe1, 0x2a, 0xd7, 0x89, 0xba, 0xed, 0x7a, 0xb6, 0x91, 0x16, 0xa9, 0x8f, 0x7d,
+                0xa3, 0x7e, 0x71, 0xb4,
+            ])
+        );
+        assert_eq!(result, a * A + EdwardsPoint::mul_base(&b));
+    }
+
+    /// The first entries of the basepoint tables, which the step-wise loop
+    /// and fixed-base multiplication read from.
+    #[test]
+    #[cfg(feature = "precomputed-tables")]
+    fn golden_basepoint_table_entries() {
+        use crate::traits::Identity;
+
+        let B = constants::ED25519_BASEPOINT_COMPRESSED;
+        let B2 = CompressedEdwardsY([
+            0xc9, 0xa3, 0xf8, 0x6a, 0xae, 0x46, 0x5f, 0x0e, 0x56, 0x51, 0x38, 0x64, 0x51, 0x0f,
+            0x39, 0x97, 0x56, 0x1f, 0xa2, 0xc9, 0xe8, 0x5e, 0xa2, 0x1d, 0xc2, 0x29, 0x23, 0x09,
+            0xf3, 0xcd, 0x60, 0x22,
+        ]);
+        let B3 = CompressedEdwardsY([
+            0xd4, 0xb4, 0xf5, 0x78, 0x48, 0x68, 0xc3, 0x02, 0x04, 0x03, 0x24, 0x67, 0x17, 0xec,
+            0x16, 0x9f, 0xf7, 0x9e, 0x26, 0x60, 0x8e, 0xa1, 0x26, 0xa1, 0xab, 0x69, 0xee, 0x77,
+            0xd1, 0xb1, 0x67, 0x12,
+        ]);
+        let B5 = CompressedEdwardsY([
+            0xed, 0xc8, 0x76, 0xd6, 0x83, 0x1f, 0xd2, 0x10, 0x5d, 0x0b, 0x43, 0x89, 0xca, 0x2e,
+            0x28, 0x31, 0x66, 0x46, 0x92, 0x89, 0x14, 0x6e, 0x2c, 0xe0, 0x6f, 0xae, 0xfe, 0x98,
+            0xb2, 0x25, 0x48, 0xdf,
+        ]);
+
+        let identity = EdwardsPoint::identity();
+        let table = &constants::ED25519_BASEPOINT_TABLE.0[0];
+        for (j, expected) in [B, B2, B3].iter().enumerate() {
+            let P = (&identity + &table.0[j]).as_extended();
+            assert_eq!(P.compress(), *expected);
+        }
+        let odd = &constants::AFFINE_ODD_MULTIPLES_OF_BASEPOINT;
+        for (j, expected) in [(1, B), (3, B3), (5, B5)] {
+            let P = (&identity + &odd.select(j)).as_extended();
+            assert_eq!(P.compress(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn job_handle_serialize_round_trip() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+
+        let run = |mut job: JobHandle| loop {
+            if let JobStatus::Done { point, stats, .. } = job.step() {
+                break (point, stats);
+            }
+        };
+
+        let fresh = JobHandle::new(&a, &A, &b);
+        let (point, _) = run(JobHandle::deserialize(&fresh.serialize()).unwrap());
+        assert_eq!(point, expected);
+
+        let mut job = JobHandle::new(&a, &A, &b);
+        job.add_wall_time(Duration::from_millis(5));
+        assert!(matches!(job.step(), JobStatus::InProgress { .. }));
+        assert!(matches!(job.step(), JobStatus::InProgress { .. }));
+        let bytes = job.serialize();
+
+        let restored = JobHandle::deserialize(&bytes).unwrap();
+        assert_eq!(restored.checkpoint(), job.checkpoint());
+        let (point, stats) = run(restored);
+        let (_, uninterrupted_stats) = run(job);
+        assert_eq!(point, expected);
+        assert_eq!(stats, uninterrupted_stats);
+        assert_eq!(stats.unwrap().wall_time, Some(Duration::from_millis(5)));
+
+        // A finished job keeps its result.
+        let mut done = JobHandle::new(&a, &A, &b);
+        while let JobStatus::InProgress { .. } = done.step() {}
+        let (point, _) = run(JobHandle::deserialize(&done.serialize()).unwrap());
+        assert_eq!(point, expected);
+
+        // Damaged blobs are rejected.
+        assert_eq!(
+            JobHandle::deserialize(&bytes[..bytes.len() - 1]).map(|_| ()),
+            Err(BackupError::Checksum)
+        );
+        assert!(matches!(
+            JobHandle::deserialize(&bytes[..10]),
+            Err(BackupError::Length { actual: 10, .. })
+        ));
+        let mut flipped = bytes.clone();
+        flipped[50] ^= 1;
+        assert_eq!(
+            JobHandle::deserialize(&flipped).map(|_| ()),
+            Err(BackupError::Checksum)
+        );
+        // A checkpoint with a corrupted accumulator, but intact checksums.
+        let mut off_curve = bytes.clone();
+        let at = 8 + 3 * 32;
+        off_curve[at + 8] ^= 1;
+        let crc = crc32(&off_curve[at..at + 128]);
+        off_curve[at + 128..at + Checkpoint::BYTE_LEN].copy_from_slice(&crc.to_le_bytes());
+        let crc = crc32(&off_curve[..off_curve.len() - 4]);
+        let len = off_curve.len();
+        off_curve[len - 4..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            JobHandle::deserialize(&off_curve).map(|_| ()),
+            Err(BackupError::Malformed)
+        );
+        let mut future = bytes.clone();
+        future[0] = JobHandle::FORMAT_VERSION + 1;
+        let crc = crc32(&future[..future.len() - 4]);
+        let len = future.len();
+        future[len - 4..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            JobHandle::deserialize(&future).map(|_| ()),
+            Err(BackupError::UnsupportedVersion {
+                version: JobHandle::FORMAT_VERSION + 1
+            })
+        );
+    }
+
+    #[test]
+    fn step_mul_typed_states() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        let mut job = JobHandle::new(&a, &A, &b);
+        let mut state = StepMulState::Fresh;
+        let point = loop {
+            let expected = job.step();
+            match step_mul(&a, &A, &b, state).unwrap() {
+                StepResult::InProgress(checkpoint) => {
+                    assert_eq!(Some(checkpoint.clone()), job.checkpoint());
+                    state = StepMulState::Resumed(checkpoint);
+                }
+                StepResult::Done(point) => {
+                    assert!(matches!(expected, JobStatus::Done { .. }));
+                    break point;
+                }
+            }
+        };
+        assert_eq!(
+            point,
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+        );
+
+        let checkpoint = match step_mul(&a, &A, &b, StepMulState::Fresh).unwrap() {
+            StepResult::InProgress(checkpoint) => checkpoint,
+            StepResult::Done(_) => panic!("one call should not finish"),
+        };
+        let mut limbs = checkpoint.projective_point();
+        limbs[0] ^= 1;
+        let corrupted = Checkpoint::new(checkpoint.index(), limbs);
+        assert_eq!(
+            step_mul(&a, &A, &b, StepMulState::Resumed(corrupted)),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+        let out_of_range = Checkpoint::new(256, checkpoint.projective_point());
+        assert_eq!(
+            step_mul(&a, &A, &b, StepMulState::Resumed(out_of_range)),
+            Err(StepError::IndexOutOfRange { index: 256 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn step_mul_with_memory_store() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+
+        let mut store = MemoryCheckpointStore::new();
+        let mut calls = 0;
+        let point = loop {
+            calls += 1;
+            if let Some(point) = step_mul_with_store(&a, &A, &b, &mut store, 7).unwrap() {
+                break point;
+            }
+            // Interleaving another job does not disturb this one.
+            assert_eq!(
+                step_mul_with_store(&b, &A, &a, &mut store, 8).unwrap(),
+                None
+            );
+            store.remove(8);
+        };
+        assert_eq!(point, expected);
+        assert!(calls > 1);
+
+        let mut limbs = store.load(7).unwrap().unwrap().projective_point();
+        limbs[0] ^= 1;
+        store.save(9, &Checkpoint::new(10, limbs)).unwrap();
+        assert_eq!(
+            step_mul_with_store(&a, &A, &b, &mut store, 9),
+            Err(StoreError::Step(StepError::CheckpointNotOnCurve))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn writer_store_appends_records() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        let mut store = WriterCheckpointStore::new(Vec::new());
+        let mut saves = 0;
+        while step_mul_with_store(&a, &A, &b, &mut store, 3)
+            .unwrap()
+            .is_none()
+        {
+            saves += 1;
+        }
+        let last = store.load(3).unwrap().unwrap();
+
+        let log = store.into_inner();
+        let record_len = WriterCheckpointStore::<Vec<u8>>::RECORD_LEN;
+        assert_eq!(log.len(), saves * record_len);
+        let record = &log[log.len() - record_len..];
+        assert_eq!(record[..8], 3u64.to_le_bytes());
+        assert_eq!(Checkpoint::from_bytes(&record[8..]), Ok(last));
+    }
+
+    #[test]
+    fn step_mul_constant_time_matches_mul() {
+        let P = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let s = Scalar::from_bytes_mod_order([0x42; 32]);
+
+        for budget in [1, 7, 64] {
+            let mut state = StepMulState::Fresh;
+            let mut chunks = 0;
+            let point = loop {
+                chunks += 1;
+                match step_mul_constant_time(&P, &s, state, budget).unwrap() {
+                    StepResult::InProgress(checkpoint) => {
+                        assert_eq!(checkpoint.index(), 64 - chunks * budget - 1);
+                        let stored = Checkpoint::from_bytes(&checkpoint.to_bytes()).unwrap();
+                        state = StepMulState::Resumed(stored);
+                    }
+                    StepResult::Done(point) => break point,
+                }
+            };
+            assert_eq!(point, s * P);
+            assert_eq!(chunks, (64 + budget - 1) / budget);
+        }
+
+        // A kept `VariableBaseMul` takes the same steps without the setup.
+        let job = VariableBaseMul::new(&P, &s);
+        let mut state = StepMulState::Fresh;
+        let point = loop {
+            match job.step(state, 7).unwrap() {
+                StepResult::InProgress(checkpoint) => state = StepMulState::Resumed(checkpoint),
+                StepResult::Done(point) => break point,
+            }
+        };
+        assert_eq!(point, s * P);
+
+        let checkpoint = match step_mul_constant_time(&P, &s, StepMulState::Fresh, 10).unwrap() {
+            StepResult::InProgress(checkpoint) => checkpoint,
+            StepResult::Done(_) => panic!("ten digits should not finish"),
+        };
+        let out_of_range = Checkpoint::new(64, checkpoint.projective_point());
+        assert_eq!(
+            step_mul_constant_time(&P, &s, StepMulState::Resumed(out_of_range), 10),
+            Err(StepError::IndexOutOfRange { index: 64 })
+        );
+        assert_eq!(
+            step_mul_constant_time(&P, &s, StepMulState::Fresh, 0),
+            Err(StepError::BudgetOutOfRange { budget: 0 })
+        );
+    }
+
+    #[test]
+    fn precomputed_step_mul_context() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+
+        let context = PrecomputedStepMulContext::new(&a, &A, &b);
+        assert_eq!(context.start_index(), start_index(&a, &b));
+        let first = context.step(StepMulState::Fresh).unwrap();
+        assert_eq!(first, step_mul(&a, &A, &b, StepMulState::Fresh).unwrap());
+
+        // Store the context next to each checkpoint and reload both.
+        let mut stored = (context.to_bytes(), first);
+        let point = loop {
+            let checkpoint = match stored.1 {
+                StepResult::InProgress(checkpoint) => checkpoint,
+                StepResult::Done(point) => break point,
+            };
+            let context = PrecomputedStepMulContext::from_bytes(&stored.0).unwrap();
+            let next = context.step(StepMulState::Resumed(checkpoint)).unwrap();
+            stored = (context.to_bytes(), next);
+        };
+        assert_eq!(point, expected);
+
+        let bytes = context.to_bytes();
+        assert!(matches!(
+            PrecomputedStepMulContext::from_bytes(&bytes[1..]),
+            Err(BackupError::Length { .. })
+        ));
+        let mut flipped = bytes;
+        flipped[100] ^= 1;
+        assert!(matches!(
+            PrecomputedStepMulContext::from_bytes(&flipped),
+            Err(BackupError::Checksum)
+        ));
+        let mut width = bytes;
+        width[4] ^= 0x0d;
+        let body_len = PrecomputedStepMulContext::BYTE_LEN - 4;
+        let crc = crc32(&width[..body_len]);
+        width[body_len..].copy_from_slice(&crc.to_le_bytes());
+        assert!(matches!(
+            PrecomputedStepMulContext::from_bytes(&width),
+            Err(BackupError::Malformed)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_bincode_checkpoint_and_context() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let context = PrecomputedStepMulContext::new(&a, &A, &b);
+        let checkpoint = match context.step(StepMulState::Fresh).unwrap() {
+            StepResult::InProgress(checkpoint) => checkpoint,
+            StepResult::Done(_) => panic!("a single step finished the multiplication"),
+        };
+
+        // The encodings are exactly those of `to_bytes`, with no prefix
+        let encoded = bincode::serialize(&checkpoint).unwrap();
+        assert_eq!(encoded, checkpoint.to_bytes());
+        let decoded: Checkpoint = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, checkpoint);
+
+        let encoded = bincode::serialize(&context).unwrap();
+        assert_eq!(encoded[..], context.to_bytes()[..]);
+        let decoded: PrecomputedStepMulContext = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(
+            decoded.step(StepMulState::Resumed(checkpoint.clone())),
+            context.step(StepMulState::Resumed(checkpoint.clone()))
+        );
+
+        // Decoding applies the checks of `from_bytes`
+        let mut corrupt = checkpoint.to_bytes();
+        corrupt[10] ^= 1;
+        assert!(bincode::deserialize::<Checkpoint>(&corrupt).is_err());
+        assert!(bincode::deserialize::<Checkpoint>(&corrupt[..100]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand_core")]
+    fn blinded_job_matches_unblinded() {
+        let mut rng = rand::thread_rng();
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+
+        // Re-randomizing changes the limbs but not the point
+        let mut plain = JobHandle::new(&a, &A, &b);
+        let checkpoint = match plain.step() {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+        let mut blinded = checkpoint.clone();
+        blinded.rerandomize(&mut rng).unwrap();
+        assert_ne!(blinded, checkpoint);
+        assert_eq!(blinded.index(), checkpoint.index());
+        assert_eq!(
+            compress_checkpoint(blinded.projective_point()).unwrap(),
+            compress_checkpoint(checkpoint.projective_point()).unwrap()
+        );
+
+        // A corrupted accumulator is rejected and left as it was
+        let mut limbs = checkpoint.projective_point();
+        limbs[3] ^= 1;
+        let mut corrupted = Checkpoint::new(checkpoint.index(), limbs);
+        assert_eq!(
+            corrupted.rerandomize(&mut rng),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+        assert_eq!(corrupted.projective_point(), limbs);
+        assert_eq!(
+            corrupted.to_canonical_bytes().map(|_| ()),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+
+        // A split job, stepped with re-randomization and resumed from its
+        // stored split, still computes aA + bB
+        let split = ScalarSplit::new(&a, &A, &b, &mut rng);
+        assert_ne!(split.a(), &a);
+        let mut job = split.job(&A).with_budget(40).unwrap();
+        let checkpoint = match job.step_blinded(&mut rng) {
+            JobStatus::InProgress { checkpoint, .. } => checkpoint,
+            JobStatus::Done { .. } => panic!("job finished in one chunk"),
+        };
+        assert_eq!(job.checkpoint(), Some(checkpoint.clone()));
+
+        let stored = split.to_bytes();
+        let split = ScalarSplit::from_bytes(&stored).unwrap();
+        let mut job = JobHandle::resume(split.a(), &A, split.b(), checkpoint).unwrap();
+        let partial = loop {
+            if let JobStatus::Done { point, .. } = job.step_blinded(&mut rng) {
+                break point;
+            }
+        };
+        assert_eq!(split.finish(&partial), expected);
+
+        assert_eq!(
+            ScalarSplit::from_bytes(&stored[1..]),
+            Err(BackupError::Length {
+                expected: ScalarSplit::BYTE_LEN,
+                actual: ScalarSplit::BYTE_LEN - 1
+            })
+        );
+        let mut corrupt = stored;
+        corrupt[31] = 0xff;
+        assert_eq!(
+            ScalarSplit::from_bytes(&corrupt),
+            Err(BackupError::Malformed)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", feature = "digest"))]
+    fn checkpoint_manager_binds_inputs() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let c = Scalar::from_bytes_mod_order([0x23; 32]);
+
+        let mut manager = CheckpointManager::<_, sha2::Sha512>::new(MemoryCheckpointStore::new());
+        manager.start(1, &a, &A, &b).unwrap();
+        manager.start(2, &c, &A, &b).unwrap();
+        assert_eq!(
+            manager.start(1, &c, &A, &b),
+            Err(ManagerError::DuplicateJob { id: 1 })
+        );
+        assert_eq!(
+            manager.step(3, &a, &A, &b),
+            Err(ManagerError::UnknownJob { id: 3 })
+        );
+
+        assert_eq!(manager.step(1, &a, &A, &b), Ok(None));
+        assert_eq!(manager.step(2, &c, &A, &b), Ok(None));
+        // Job 2's inputs with job 1's id are refused, and job 1 is untouched
+        let key = manager.store_key(1).unwrap();
+        let saved = manager.store().clone().load(key).unwrap();
+        assert_eq!(
+            manager.step(1, &c, &A, &b),
+            Err(ManagerError::InputMismatch { id: 1 })
+        );
+        assert_eq!(manager.store().clone().load(key).unwrap(), saved);
+        assert_eq!(manager.pending().collect::<Vec<_>>(), [1, 2]);
+
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+        let point = loop {
+            if let Some(point) = manager.step(1, &a, &A, &b).unwrap() {
+                break point;
+            }
+        };
+        assert_eq!(point, expected);
+        assert_eq!(manager.step(1, &a, &A, &b), Ok(Some(expected)));
+        assert_eq!(
+            manager.status(1),
+            Some(ManagedJobStatus::Complete(expected))
+        );
+        assert_eq!(manager.status(2), Some(ManagedJobStatus::Pending));
+        assert_eq!(manager.completed().collect::<Vec<_>>(), [1]);
+
+        // After a restart, job 2 picks up from its stored checkpoint, and
+        // a job reusing id 1 with other inputs does not see job 1's
+        let mut manager = CheckpointManager::<_, sha2::Sha512>::new(manager.into_store());
+        manager.start(2, &c, &A, &b).unwrap();
+        manager.start(1, &b, &A, &a).unwrap();
+        let (mut first, mut second) = (None, None);
+        while first.is_none() || second.is_none() {
+            first = manager.step(1, &b, &A, &a).unwrap();
+            second = manager.step(2, &c, &A, &b).unwrap();
+        }
+        assert_eq!(
+            first,
+            Some(EdwardsPoint::vartime_double_scalar_mul_basepoint(
+                &b, &A, &a
+            ))
+        );
+        assert_eq!(
+            second,
+            Some(EdwardsPoint::vartime_double_scalar_mul_basepoint(
+                &c, &A, &b
+            ))
+        );
+        assert_eq!(
+            manager.remove(2),
+            Some(ManagedJobStatus::Complete(second.unwrap()))
+        );
+        assert_eq!(manager.status(2), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn verify_checkpoint_detects_wrong_accumulator() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        let mut state = StepMulState::Fresh;
+        let mut checkpoints = Vec::new();
+        while let StepResult::InProgress(checkpoint) = step_mul(&a, &A, &b, state).unwrap() {
+            checkpoints.push(checkpoint.clone());
+            state = StepMulState::Resumed(checkpoint);
+        }
+        assert!(checkpoints.len() > 2);
+        for checkpoint in checkpoints.iter() {
+            assert_eq!(verify_checkpoint(checkpoint, &a, &A, &b), Ok(()));
+        }
+
+        // Another valid point at the same index
+        let checkpoint = &checkpoints[1];
+        let other = EdwardsPoint::mul_base(&Scalar::from(5u64)).as_projective();
+        let tampered = Checkpoint::new(checkpoint.index(), serialize_r_for_backup(&other));
+        assert_eq!(
+            verify_checkpoint(&tampered, &a, &A, &b),
+            Err(StepError::CheckpointMismatch)
+        );
+        // The right point at the wrong index, or for other inputs
+        let shifted = Checkpoint::new(checkpoint.index() + 1, checkpoint.projective_point());
+        assert_eq!(
+            verify_checkpoint(&shifted, &a, &A, &b),
+            Err(StepError::CheckpointMismatch)
+        );
+        assert_eq!(
+            verify_checkpoint(checkpoint, &b, &A, &a),
+            Err(StepError::CheckpointMismatch)
+        );
+        // An accumulator that is not a point at all
+        let mut limbs = checkpoint.projective_point();
+        limbs[0] ^= 1;
+        assert_eq!(
+            verify_checkpoint(&Checkpoint::new(checkpoint.index(), limbs), &a, &A, &b),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+
+        #[cfg(feature = "rand_core")]
+        {
+            let mut rerandomized = checkpoint.clone();
+            rerandomized.rerandomize(&mut rand::thread_rng()).unwrap();
+            assert_eq!(verify_checkpoint(&rerandomized, &a, &A, &b), Ok(()));
+        }
+    }
+
+    #[test]
+    fn job_handle_with_budget() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        assert_eq!(
+            JobHandle::new(&a, &A, &b).with_budget(0).map(|_| ()),
+            Err(StepError::BudgetOutOfRange { budget: 0 })
+        );
+        assert_eq!(
+            JobHandle::new(&a, &A, &b)
+                .with_budget(MAX_STEP_BUDGET + 1)
+                .map(|_| ()),
+            Err(StepError::BudgetOutOfRange {
+                budget: MAX_STEP_BUDGET + 1
+            })
+        );
+
+        let mut job = JobHandle::new(&a, &A, &b).with_budget(7).unwrap();
+        assert_eq!(job.budget(), 7);
+        let (point, stats) = loop {
+            if let JobStatus::Done { point, stats, .. } = job.step() {
+                break (point, stats.unwrap());
+            }
+        };
+        assert_eq!(
+            point,
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+        );
+        assert_eq!(stats.max_chunk_doublings, 7);
+
+        #[cfg(feature = "alloc")]
+        {
+            let job = JobHandle::new(&a, &A, &b).with_budget(7).unwrap();
+            let restored = JobHandle::deserialize(&job.serialize()).unwrap();
+            assert_eq!(restored.budget(), 7);
+        }
+    }
+
+    /// Drive `job` in chunks of `chunk` steps, rebuilding its state from
+    /// its parts between chunks as a caller storing it would.
+    fn run_in_chunks<J>(job: &J, chunk: usize) -> J::Output
+    where
+        J: Interruptible<State = MulState> + CostModel,
+    {
+        let mut state = job.start();
+        assert!(job.finish(&state).is_none());
+        while !job.advance(&mut state, chunk) {
+            assert!(job.finish(&state).is_none());
+            state = MulState::from_parts(job, state.remaining(), state.accumulator()).unwrap();
+        }
+        job.finish(&state).unwrap()
+    }
+
+    #[test]
+    fn interruptible_muls_match_direct_muls() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        let variable = VariableBaseMul::new(&A, &a);
+        let basepoint = BasepointMul::new(&b);
+        let double = DoubleBaseMul::new(&a, &A, &b);
+        for chunk in [1, 7, 64, 1000] {
+            assert_eq!(run_in_chunks(&variable, chunk), a * A);
+            assert_eq!(run_in_chunks(&basepoint, chunk), EdwardsPoint::mul_base(&b));
+            assert_eq!(
+                run_in_chunks(&double, chunk),
+                EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+            );
+        }
+        assert_eq!(variable.run(), Some(a * A));
+        assert_eq!(basepoint.run(), Some(EdwardsPoint::mul_base(&b)));
+
+        // A stored digit count beyond the computation's is rejected.
+        let identity = variable.start().accumulator();
+        assert!(MulState::from_parts(&variable, 64, identity).is_ok());
+        assert_eq!(
+            MulState::from_parts(&variable, 65, identity).map(|_| ()),
+            Err(StepError::IndexOutOfRange { index: 65 })
+        );
+        assert_eq!(
+            MulState::from_parts(&double, double.total_steps() + 1, identity).map(|_| ()),
+            Err(StepError::IndexOutOfRange {
+                index: double.total_steps() + 1
+            })
+        );
+
+        // So is an accumulator that is not a point.
+        let mut off_curve = A;
+        off_curve.Y = &off_curve.Y + &FieldElement::ONE;
+        assert_eq!(
+            MulState::from_parts(&double, 10, off_curve).map(|_| ()),
+            Err(StepError::CheckpointNotOnCurve)
+        );
+        let mut zero_z = identity;
+        zero_z.Z = FieldElement::ZERO;
+        assert_eq!(
+            MulState::from_parts(&double, 10, zero_z).map(|_| ()),
+            Err(StepError::CheckpointZeroZ)
+        );
+
+        // A zero-step advance does no work.
+        let mut state = double.start();
+        assert!(!double.advance(&mut state, 0));
+        assert_eq!(state.remaining(), double.start().remaining());
+
+        let zero = Scalar::ZERO;
+        assert_eq!(
+            DoubleBaseMul::new(&zero, &A, &zero).run(),
+            Some(EdwardsPoint::identity())
+        );
+        assert_eq!(
+            VariableBaseMul::new(&A, &zero).run(),
+            Some(EdwardsPoint::identity())
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "precomputed-tables", feature = "alloc"))]
+    fn basepoint_table_build_resumes_from_bytes() {
+        use crate::traits::BasepointTable;
+
+        let P = constants::ED25519_BASEPOINT_POINT * Scalar::from(1234567u64);
+        let job = BasepointTableBuild::new(&P);
+        let mut state = job.start();
+        let mut calls = 0;
+        while !job.advance(&mut state, 37) {
+            assert!(job.finish(&state).is_none());
+            let bytes = state.to_bytes();
+            state = BasepointTableState::from_bytes(&bytes).unwrap();
+            calls += 1;
+        }
+        assert_eq!(calls, job.calls(37) - 1);
+        assert_eq!(job.progress(&state), Some((256, 256)));
+
+        let table = job.finish(&state).unwrap();
+        assert_eq!(
+            table.to_bytes(),
+            EdwardsBasepointTable::create(&P).to_bytes()
+        );
+        assert_eq!(
+            table.mul_base(&Scalar::from(99u64)),
+            P * Scalar::from(99u64)
+        );
+
+        let work = job.work(0..256);
+        assert_eq!(work.doublings, 31 * 8);
+        assert_eq!(work.additions, 32 * 7);
+
+        // More entries done than the table has.
+        let mut bytes = job.start().to_bytes();
+        bytes[1..3].copy_from_slice(&257u16.to_le_bytes());
+        let crc = crc32(&bytes[..bytes.len() - 4]);
+        let len = bytes.len();
+        bytes[len - 4..].copy_from_slice(&crc.to_le_bytes());
+        assert_eq!(
+            BasepointTableState::from_bytes(&bytes).unwrap_err(),
+            BackupError::Malformed
+        );
+        bytes[1] = 0;
+        assert_eq!(
+            BasepointTableState::from_bytes(&bytes).unwrap_err(),
+            BackupError::Checksum
+        );
+    }
+
+    #[test]
+    fn with_progress_reports_steps() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        let total = estimated_steps(&a, &b);
+        let reports = Cell::new(0);
+        let last = Cell::new((0, 0));
+        let job = DoubleBaseMul::new(&a, &A, &b).with_progress(|done, total| {
+            assert!(done > last.get().0);
+            reports.set(reports.get() + 1);
+            last.set((done, total));
+        });
+        let mut state = job.start();
+        assert_eq!(job.progress(&state), Some((0, total)));
+        while !job.advance(&mut state, 10) {}
+        assert_eq!(reports.get(), (total + 9) / 10);
+        assert_eq!(last.get(), (total, total));
+        assert_eq!(
+            job.finish(&state),
+            Some(EdwardsPoint::vartime_double_scalar_mul_basepoint(
+                &a, &A, &b
+            ))
+        );
+
+        let job = VariableBaseMul::new(&A, &a);
+        let mut state = job.start();
+        job.advance(&mut state, 10);
+        assert_eq!(job.progress(&state), Some((10, 64)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn audit_log_replays() {
+        use core::cell::RefCell;
+
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+
+        // Drive the computation in uneven chunks, storing and reloading the
+        // state between them as a resumed computation would.
+        let log = RefCell::new(Vec::new());
+        let job = WithAuditLog::new(DoubleBaseMul::new(&a, &A, &b), |record| {
+            log.borrow_mut().push(record)
+        });
+        let mut state = job.start();
+        for steps in [7, 0, 100].iter().cycle() {
+            let stored = (state.remaining(), state.accumulator());
+            state = MulState::from_parts(job.inner(), stored.0, stored.1).unwrap();
+            if job.advance(&mut state, *steps) {
+                break;
+            }
+        }
+        assert_eq!(job.finish(&state), Some(expected));
+
+        let log = log.into_inner();
+        let job = DoubleBaseMul::new(&a, &A, &b);
+        assert_eq!(log[0].start, 0);
+        assert_eq!(log.last().unwrap().end, job.total_steps());
+        assert_eq!(replay_and_verify(&log, &job), Ok(expected));
+
+        // The byte encoding round trips
+        for record in log.iter() {
+            assert_eq!(
+                AuditRecord::from_bytes(&record.to_bytes().unwrap()),
+                Ok(*record)
+            );
+        }
+        #[cfg(target_pointer_width = "64")]
+        {
+            let mut huge = log[0];
+            huge.end = 1 << 32;
+            assert_eq!(
+                huge.to_bytes(),
+                Err(StepError::IndexOutOfRange { index: 1 << 32 })
+            );
+        }
+        assert_eq!(
+            AuditRecord::from_bytes(&[0u8; 10]),
+            Err(BackupError::Length {
+                expected: AuditRecord::BYTE_LEN,
+                actual: 10
+            })
+        );
+
+        // A state tampered with between chunks breaks the chain
+        let mut tampered = log.clone();
+        tampered[3].accumulator_in = EdwardsPoint::identity().compress();
+        assert_eq!(
+            replay_and_verify(&tampered, &job),
+            Err(AuditError::AccumulatorMismatch { record: 3 })
+        );
+        let mut tampered = log.clone();
+        tampered[5].accumulator_out = tampered[5].accumulator_in;
+        assert_eq!(
+            replay_and_verify(&tampered, &job),
+            Err(AuditError::AccumulatorMismatch { record: 5 })
+        );
+
+        // So does a missing or reordered chunk, or a truncated log
+        let mut gap = log.clone();
+        gap.remove(2);
+        assert_eq!(
+            replay_and_verify(&gap, &job),
+            Err(AuditError::OutOfOrder { record: 2 })
+        );
+        let mut overlong = log.clone();
+        overlong.last_mut().unwrap().end += 1;
+        assert_eq!(
+            replay_and_verify(&overlong, &job),
+            Err(AuditError::OutOfOrder {
+                record: log.len() - 1
+            })
+        );
+        assert_eq!(
+            replay_and_verify(&log[..log.len() - 1], &job),
+            Err(AuditError::Incomplete)
+        );
+
+        // A log only replays against the inputs it was written for
+        assert_eq!(
+            replay_and_verify(&log, &DoubleBaseMul::new(&b, &A, &a)),
+            Err(AuditError::AccumulatorMismatch { record: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "future")]
+    fn step_future_yields_between_chunks() {
+        use core::future::Future;
+        use core::pin::Pin;
+        use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn noop_raw_waker() -> RawWaker {
+            fn clone(_: *const ()) -> RawWaker {
+                noop_raw_waker()
+            }
+            fn noop(_: *const ()) {}
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(core::ptr::null(), &VTABLE)
+        }
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+        let job = VariableBaseMul::new(&A, &a);
+        let mut future = StepFuture::new(job, 10);
+        let mut polls = 0;
+        let point = loop {
+            polls += 1;
+            if let Poll::Ready(point) = Pin::new(&mut future).poll(&mut cx) {
+                break point;
+            }
+        };
+        assert_eq!(point, a * A);
+        assert_eq!(polls, job.calls(10));
+    }
+
+    #[test]
+    fn cost_models_predict_work() {
+        let a = Scalar::from_bytes_mod_order([0x42; 32]);
+        let b = Scalar::from_bytes_mod_order([0x17; 32]);
+        let A = EdwardsPoint::mul_base(&Scalar::from(99u64));
+
+        // The double-base model predicts each chunk of a job exactly.
+        let model = DoubleBaseMul::new(&a, &A, &b);
+        assert_eq!(model.total_steps(), estimated_steps(&a, &b));
+        assert_eq!(model.total_steps(), start_index(&a, &b) + 1);
+        let mut job = JobHandle::new(&a, &A, &b).with_budget(7).unwrap();
+        let mut call = 0;
+        loop {
+            let (work, done) = match job.step() {
+                JobStatus::InProgress { work, .. } => (work, false),
+                JobStatus::Done { work, .. } => (work, true),
+            };
+            assert_eq!(work, model.call_work(call, 7));
+            call += 1;
+            if done {
+                break;
+            }
+        }
+        assert_eq!(call, model.calls(7));
+        assert_eq!(model.call_work(call, 7), WorkDone::default());
+
+        let variable = VariableBaseMul::new(&A, &a);
+        assert_eq!(variable.calls(10), 7);
+        assert_eq!(
+            variable.work(0..usize::MAX),
+            WorkDone {
+                doublings: 256,
+                additions: 64,
+            }
+        );
+        assert_eq!(BasepointMul::new(&b).work(60..70).additions, 4);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pippenger_cost_model_counts_calls() {
+        let scalars: Vec<Scalar> = (0..50u64).map(|i| Scalar::from(i * 0x1234_5678)).collect();
+        let points: Vec<EdwardsPoint> = (0..50u64)
+            .map(|i| EdwardsPoint::mul_base(&Scalar::from(i + 1)))
+            .collect();
+        let job = PippengerMul::new(&scalars, &points).unwrap();
+
+        let mut state = job.start();
+        let mut calls = 0;
+        while !job.advance(&mut state, 100) {
+            calls += 1;
+            assert_eq!(job.progress(&state), Some((100 * calls, job.total_steps())));
+        }
+        assert_eq!(calls + 1, job.calls(100));
+        assert_eq!(
+            job.progress(&state),
+            Some((job.total_steps(), job.total_steps()))
+        );
+
+        let total = job.work(0..job.total_steps());
+        let mut summed = WorkDone::default();
+        for call in 0..job.calls(100) {
+            let work = job.call_work(call, 100);
+            summed.doublings += work.doublings;
+            summed.additions += work.additions;
+        }
+        assert_eq!(summed, total);
+        let w = Pippenger::digit_width(50);
+        assert_eq!(total.doublings, w * Scalar::to_radix_2w_size_hint(w));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pippenger_mul_resumes_from_stored_state() {
+        use crate::traits::VartimeMultiscalarMul;
+
+        let n = 100;
+        let x = Scalar::from(2128506u64).invert();
+        let y = Scalar::from(4443282u64).invert();
+        let scalars: Vec<Scalar> = (0..n).map(|i| x + Scalar::from(i as u64) * y).collect();
+        let points: Vec<EdwardsPoint> = (0..n)
+            .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(1 + i as u64))
+            .collect();
+        let expected = EdwardsPoint::vartime_multiscalar_mul(&scalars, &points);
+
+        let job = PippengerMul::new(&scalars, &points).unwrap();
+        let mut bytes = job.start().to_bytes();
+        let mut chunks = 0;
+        let result = loop {
+            chunks += 1;
+            let mut state = PippengerState::from_bytes(&job, &bytes).unwrap();
+            if job.advance(&mut state, 700) {
+                break job.finish(&state).unwrap();
+            }
+            assert!(job.finish(&state).is_none());
+            bytes = state.to_bytes();
+        };
+        assert_eq!(result, expected);
+        assert!(chunks > 1);
+        assert_eq!(job.run(), Some(expected));
+
+        assert_eq!(
+            PippengerMul::new(&[], &[]).unwrap().run(),
+            Some(EdwardsPoint::identity())
+        );
+        assert_eq!(
+            PippengerMul::new(&scalars, &points[1..]).map(|_| ()),
+            Err(MultiscalarError::LengthMismatch {
+                scalars: n,
+                points: n - 1
+            })
+        );
+
+        let mut damaged = job.start().to_bytes();
+        damaged[3] ^= 1;
+        assert_eq!(
+            PippengerState::from_bytes(&job, &damaged).map(|_| ()),
+            Err(BackupError::Checksum)
+        );
+    }
+
+    /// A job of `n` terms, and its start state serialized with the header
+    /// field at `offset` replaced by `value` under a valid checksum.
+    #[cfg(feature = "alloc")]
+    fn pippenger_state_with_header(
+        n: usize,
+        offset: usize,
+        value: &[u8],
+    ) -> (PippengerMul, Vec<u8>) {
+        let scalars: Vec<Scalar> = (0..n).map(|i| Scalar::from(7 + i as u64)).collect();
+        let points: Vec<EdwardsPoint> = (0..n)
+            .map(|i| constants::ED25519_BASEPOINT_POINT * Scalar::from(1 + i as u64))
+            .collect();
+        let job = PippengerMul::new(&scalars, &points).unwrap();
+        let mut bytes = job.start().to_bytes();
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+        let body = bytes.len() - 4;
+        let crc = crc32(&bytes[..body]);
+        bytes[body..].copy_from_slice(&crc.to_le_bytes());
+        (job, bytes)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pippenger_state_rejects_extra_columns() {
+        let (job, bytes) = pippenger_state_with_header(10, 0, &[0; 2]);
+        assert!(PippengerState::from_bytes(&job, &bytes).is_ok());
+
+        let columns = Scalar::to_radix_2w_size_hint(job.digit_width()) as u16;
+        let (job, bytes) = pippenger_state_with_header(10, 0, &(columns + 1).to_le_bytes());
+        assert_eq!(
+            PippengerState::from_bytes(&job, &bytes).map(|_| ()),
+            Err(BackupError::Malformed)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pippenger_state_rejects_term_past_the_end() {
+        let (job, bytes) = pippenger_state_with_header(10, 2, &10u32.to_le_bytes());
+        assert!(PippengerState::from_bytes(&job, &bytes).is_ok());
+
+        let (job, bytes) = pippenger_state_with_header(10, 2, &11u32.to_le_bytes());
+        assert_eq!(
+            PippengerState::from_bytes(&job, &bytes).map(|_| ()),
+            Err(BackupError::Malformed)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn pippenger_state_rejects_other_bucket_count() {
+        // A state of a job whose size calls for another digit width.
+        let small =
+            PippengerMul::new(&[Scalar::ONE], &[constants::ED25519_BASEPOINT_POINT]).unwrap();
+        let (job, _) = pippenger_state_with_header(1000, 0, &[]);
+        assert_ne!(small.digit_width(), job.digit_width());
+        assert_eq!(
+            PippengerState::from_bytes(&job, &small.start().to_bytes()).map(|_| ()),
+            Err(BackupError::Malformed)
+        );
+
+        // Held in memory, it is left alone rather than advanced.
+        let mut state = small.start();
+        assert!(!job.advance(&mut state, 10));
+        assert_eq!(state.to_bytes(), small.start().to_bytes());
+        assert_eq!(job.progress(&state), None);
+        assert_eq!(job.finish(&state), None);
+    }
+}
@@ -172,6 +172,8 @@ use core::ops::{Mul, MulAssign};
 #[cfg(any(test, feature = "rand_core"))]
 use rand_core::CryptoRngCore;
 
+#[cfg(feature = "digest")]
+use digest::crypto_common::BlockSizeUser;
 #[cfg(feature = "digest")]
 use digest::generic_array::typenum::U64;
 #[cfg(feature = "digest")]
@@ -197,7 +199,11 @@ use zeroize::Zeroize;
 
 #[cfg(feature = "precomputed-tables")]
 use crate::edwards::EdwardsBasepointTable;
-use crate::edwards::EdwardsPoint;
+use crate::edwards::{DoubleBaseInput, EdwardsPoint};
+#[cfg(feature = "alloc")]
+use crate::errors::MultiscalarError;
+use crate::errors::{BackupError, StepError};
+use crate::resumable::{self, Checkpoint, StepMulState, StepResult};
 
 use crate::scalar::Scalar;
 
@@ -755,6 +761,27 @@ impl RistrettoPoint {
         RistrettoPoint::from_hash(hash)
     }
 
+    #[cfg(feature = "digest")]
+    /// Hash `msg` to a `RistrettoPoint`, using the
+    /// `ristretto255_XMD:SHA-512_R255MAP_RO_` suite of [RFC 9380] with `D`
+    /// as the hash function and `dst` as the domain separation tag.
+    ///
+    /// The message is expanded to 64 bytes with `expand_message_xmd` and
+    /// passed to [`RistrettoPoint::from_uniform_bytes`], the one-way map of
+    /// [RFC 9496].  Unlike [`RistrettoPoint::hash_from_bytes`], the input
+    /// is bound to `dst`, as the standard requires.
+    ///
+    /// [RFC 9380]: https://www.rfc-editor.org/rfc/rfc9380.html
+    /// [RFC 9496]: https://www.rfc-editor.org/rfc/rfc9496.html
+    pub fn hash_to_group<D>(msg: &[u8], dst: &[u8]) -> RistrettoPoint
+    where
+        D: Digest<OutputSize = U64> + BlockSizeUser + Default,
+    {
+        let mut uniform_bytes = [0u8; 64];
+        crate::hash_to_curve::expand_message_xmd::<D>(msg, dst, &mut uniform_bytes);
+        RistrettoPoint::from_uniform_bytes(&uniform_bytes)
+    }
+
     #[cfg(feature = "digest")]
     /// Construct a `RistrettoPoint` from an existing `Digest` instance.
     ///
@@ -773,6 +800,46 @@ impl RistrettoPoint {
         RistrettoPoint::from_uniform_bytes(&output_bytes)
     }
 
+    #[cfg(feature = "digest")]
+    /// Derive the generator \\(H\_i\\) numbered `index` in the family named
+    /// by `domain`, by hashing `domain` followed by the 8-byte little-endian
+    /// `index` with [`RistrettoPoint::from_hash`].
+    ///
+    /// Distinct `(domain, index)` pairs give independent generators whose
+    /// discrete logs with respect to each other, and to the basepoint, are
+    /// unknown.
+    pub fn hashed_generator<D>(domain: &[u8], index: u64) -> RistrettoPoint
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let mut hash = D::default();
+        hash.update(domain);
+        hash.update(index.to_le_bytes());
+        RistrettoPoint::from_hash(hash)
+    }
+
+    #[cfg(feature = "digest")]
+    /// Compute \\(aA + bH\_i\\), where \\(H\_i\\) is
+    /// [`RistrettoPoint::hashed_generator`]`(domain, index)`, in place of the
+    /// basepoint in [`RistrettoPoint::vartime_double_scalar_mul_basepoint`].
+    ///
+    /// This suits commitment schemes which use a fresh generator for each
+    /// call.  Unlike the basepoint variant, there is no precomputed table
+    /// for \\(H\_i\\), and the multiplication runs in constant time.
+    pub fn double_scalar_mul_hashed_generator<D>(
+        a: &Scalar,
+        A: &RistrettoPoint,
+        b: &Scalar,
+        domain: &[u8],
+        index: u64,
+    ) -> RistrettoPoint
+    where
+        D: Digest<OutputSize = U64> + Default,
+    {
+        let H = RistrettoPoint::hashed_generator::<D>(domain, index);
+        a * A + b * H
+    }
+
     /// Construct a `RistrettoPoint` from 64 bytes of data.
     ///
     /// If the input bytes are uniformly distributed, the resulting
@@ -960,6 +1027,19 @@ impl RistrettoPoint {
             scalar * constants::RISTRETTO_BASEPOINT_TABLE
         }
     }
+
+    /// Multiply this point by a secret scalar read from `chunks` a few bytes
+    /// at a time; see [`EdwardsPoint::mul_streaming`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not divide 32.
+    pub fn mul_streaming<const N: usize, E, F>(&self, chunks: F) -> Result<RistrettoPoint, E>
+    where
+        F: FnMut(core::ops::Range<usize>, &mut [u8; N]) -> Result<(), E>,
+    {
+        self.0.mul_streaming(chunks).map(RistrettoPoint)
+    }
 }
 
 define_mul_assign_variants!(LHS = RistrettoPoint, RHS = Scalar);
@@ -1061,6 +1141,40 @@ impl VartimePrecomputedMultiscalarMul for VartimeRistrettoPrecomputation {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl RistrettoPoint {
+    /// Compute \\(\sum\_i s\_i P\_i\\) in variable time, reading the terms
+    /// as `(scalar, compressed point)` pairs from an iterator, in bounded
+    /// memory.
+    ///
+    /// See [`EdwardsPoint::vartime_multiscalar_mul_compressed`].
+    ///
+    /// # Return
+    ///
+    /// - `Ok` with the sum, which is the identity for an empty input;
+    /// - `Err(MultiscalarError::InvalidPoint { index })` for the first term
+    ///   whose point does not decompress.  No terms after it are read.
+    pub fn vartime_multiscalar_mul_compressed<I>(
+        terms: I,
+    ) -> Result<RistrettoPoint, MultiscalarError>
+    where
+        I: IntoIterator<Item = (Scalar, CompressedRistretto)>,
+    {
+        EdwardsPoint::vartime_multiscalar_mul_stream(
+            terms
+                .into_iter()
+                .map(|(scalar, compressed)| (scalar, compressed.decompress().map(|P| P.0))),
+        )
+        .map(RistrettoPoint)
+    }
+}
+
+impl DoubleBaseInput for RistrettoPoint {
+    fn to_extended(&self) -> EdwardsPoint {
+        self.0
+    }
+}
+
 impl RistrettoPoint {
     /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the
     /// Ristretto basepoint.
@@ -1076,14 +1190,17 @@ impl RistrettoPoint {
 
     /// Compute \\(aA + bB\\) in variable time, where \\(B\\) is the
     /// Ristretto basepoint.
-    pub fn step_vartime_double_scalar_mul_basepoint<F: Fn(usize, [u64; 15]) -> ()>(
+    ///
+    /// Returns an error for the same resume indices as
+    /// [`EdwardsPoint::step_vartime_double_scalar_mul_basepoint`].
+    pub fn step_vartime_double_scalar_mul_basepoint<F: FnMut(usize, [u64; 15])>(
         a: &Scalar,
         A: &RistrettoPoint,
         b: &Scalar,
         progress_saving_handle: F,
         i: usize,
         projective_point: [u64; 15],
-    ) -> (RistrettoPoint, u8) {
+    ) -> Result<(RistrettoPoint, u8), StepError> {
         let (edPoint, res) = EdwardsPoint::step_vartime_double_scalar_mul_basepoint(
             a,
             &A.0,
@@ -1091,11 +1208,103 @@ impl RistrettoPoint {
             progress_saving_handle,
             i,
             projective_point,
-        );
-        (RistrettoPoint(edPoint), res)
+        )?;
+        Ok((RistrettoPoint(edPoint), res))
+    }
+
+    /// Perform the next chunk of the variable-time computation of
+    /// \\(aA + bB\\), where \\(B\\) is the Ristretto basepoint, starting
+    /// afresh if `checkpoint` is `None` and resuming from it otherwise.
+    ///
+    /// This is [`resumable::step_mul`] with Ristretto-typed inputs, checkpoint
+    /// and result.
+    ///
+    /// # Return
+    ///
+    /// - `Ok(RistrettoStepResult::InProgress(checkpoint))` if the chunk ran out
+    ///   of budget, to pass back on the next call;
+    /// - `Ok(RistrettoStepResult::Done(point))` with \\(aA + bB\\) once complete;
+    /// - `Err` if `checkpoint` cannot be resumed from, as for
+    ///   [`resumable::step_mul`].
+    ///
+    /// [`resumable::step_mul`]: crate::resumable::step_mul
+    pub fn vartime_double_scalar_mul_basepoint_chunk(
+        a: &Scalar,
+        A: &RistrettoPoint,
+        b: &Scalar,
+        checkpoint: Option<RistrettoCheckpoint>,
+    ) -> Result<RistrettoStepResult, StepError> {
+        let state = match checkpoint {
+            Some(checkpoint) => StepMulState::Resumed(checkpoint.0),
+            None => StepMulState::Fresh,
+        };
+        Ok(match resumable::step_mul(a, &A.0, b, state)? {
+            StepResult::InProgress(checkpoint) => {
+                RistrettoStepResult::InProgress(RistrettoCheckpoint(checkpoint))
+            }
+            StepResult::Done(point) => RistrettoStepResult::Done(RistrettoPoint(point)),
+        })
+    }
+}
+
+/// A checkpoint of [`RistrettoPoint::vartime_double_scalar_mul_basepoint_chunk`]:
+/// the index of the next digit to process and the partial accumulator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RistrettoCheckpoint(Checkpoint);
+
+impl RistrettoCheckpoint {
+    /// The length of [`RistrettoCheckpoint::to_bytes`].
+    pub const BYTE_LEN: usize = Checkpoint::BYTE_LEN;
+
+    /// The index of the next digit to process.
+    pub fn index(&self) -> usize {
+        self.0.index()
+    }
+
+    /// Serialize this checkpoint, in the encoding of [`Checkpoint::to_bytes`].
+    pub fn to_bytes(&self) -> [u8; RistrettoCheckpoint::BYTE_LEN] {
+        self.0.to_bytes()
+    }
+
+    /// Load a checkpoint written by [`RistrettoCheckpoint::to_bytes`].
+    ///
+    /// # Return
+    ///
+    /// As for [`Checkpoint::from_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<RistrettoCheckpoint, BackupError> {
+        Checkpoint::from_bytes(bytes).map(RistrettoCheckpoint)
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for RistrettoCheckpoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RistrettoCheckpoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Checkpoint::deserialize(deserializer).map(RistrettoCheckpoint)
+    }
+}
+
+/// The outcome of [`RistrettoPoint::vartime_double_scalar_mul_basepoint_chunk`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RistrettoStepResult {
+    /// The chunk ran out of budget; resume from the checkpoint.
+    InProgress(RistrettoCheckpoint),
+    /// The computation is complete.
+    Done(RistrettoPoint),
+}
+
 /// A precomputed table of multiples of a basepoint, used to accelerate
 /// scalar multiplication.
 ///
@@ -1113,6 +1322,26 @@ impl RistrettoPoint {
 #[repr(transparent)]
 pub struct RistrettoBasepointTable(pub(crate) EdwardsBasepointTable);
 
+#[cfg(all(feature = "precomputed-tables", feature = "serde"))]
+impl Serialize for RistrettoBasepointTable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(all(feature = "precomputed-tables", feature = "serde"))]
+impl<'de> Deserialize<'de> for RistrettoBasepointTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        EdwardsBasepointTable::deserialize(deserializer).map(RistrettoBasepointTable)
+    }
+}
+
 #[cfg(feature = "precomputed-tables")]
 impl<'a, 'b> Mul<&'b Scalar> for &'a RistrettoBasepointTable {
     type Output = RistrettoPoint;
@@ -1312,6 +1541,107 @@ mod test {
 
     use rand_core::OsRng;
 
+    #[cfg(feature = "group")]
+    #[test]
+    fn group_impls() {
+        crate::test_support::check_group_impl::<RistrettoPoint>();
+        let B = constants::RISTRETTO_BASEPOINT_POINT;
+        assert_eq!(CofactorGroup::clear_cofactor(&B), B);
+    }
+
+    #[test]
+    #[cfg(feature = "digest")]
+    fn hash_to_group_expands_message() {
+        use sha2::Sha512;
+
+        let dst = b"QUUX-V01-CS02-with-ristretto255_XMD:SHA-512_R255MAP_RO_";
+        // expand_message_xmd(b"abc", dst, 64) with SHA-512.
+        let uniform_bytes: [u8; 64] = hex::decode(
+            "7bde08d73da381b27bdb1e5117b635f4febcde1271292b8273c3acc631f7eae4\
+             61f289da85121ac0fd0e42d4405501c2488d03599be5eea74ea436efbaeac309",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            RistrettoPoint::hash_to_group::<Sha512>(b"abc", dst),
+            RistrettoPoint::from_uniform_bytes(&uniform_bytes)
+        );
+        assert_ne!(
+            RistrettoPoint::hash_to_group::<Sha512>(b"abc", b"another tag"),
+            RistrettoPoint::hash_to_group::<Sha512>(b"abc", dst)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "digest")]
+    fn double_scalar_mul_hashed_generator() {
+        use sha2::Sha512;
+
+        let A = RistrettoPoint::hash_from_bytes::<Sha512>(b"A");
+        let a = Scalar::from(1234u64);
+        let b = Scalar::from(5678u64);
+
+        for index in 0..3u64 {
+            let mut input = *b"commitments\0\0\0\0\0\0\0\0";
+            input[11..].copy_from_slice(&index.to_le_bytes());
+            let H = RistrettoPoint::hash_from_bytes::<Sha512>(&input);
+            assert_eq!(
+                RistrettoPoint::hashed_generator::<Sha512>(b"commitments", index),
+                H
+            );
+            assert_eq!(
+                RistrettoPoint::double_scalar_mul_hashed_generator::<Sha512>(
+                    &a,
+                    &A,
+                    &b,
+                    b"commitments",
+                    index
+                ),
+                a * A + b * H
+            );
+        }
+        assert_ne!(
+            RistrettoPoint::hashed_generator::<Sha512>(b"commitments", 0),
+            RistrettoPoint::hashed_generator::<Sha512>(b"commitments", 1)
+        );
+    }
+
+    #[test]
+    fn vartime_double_scalar_mul_basepoint_chunk() {
+        let mut rng = OsRng;
+        let A = RistrettoPoint::random(&mut rng);
+        let a = Scalar::random(&mut rng);
+        let b = Scalar::random(&mut rng);
+
+        let mut checkpoint = None;
+        let mut chunks = 0;
+        let point = loop {
+            chunks += 1;
+            match RistrettoPoint::vartime_double_scalar_mul_basepoint_chunk(&a, &A, &b, checkpoint)
+                .unwrap()
+            {
+                RistrettoStepResult::InProgress(next) => {
+                    // Store and reload the checkpoint between chunks.
+                    checkpoint = Some(RistrettoCheckpoint::from_bytes(&next.to_bytes()).unwrap());
+                }
+                RistrettoStepResult::Done(point) => break point,
+            }
+        };
+        assert!(chunks > 1);
+        assert_eq!(
+            point,
+            RistrettoPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b)
+        );
+
+        let mut bytes = [0u8; RistrettoCheckpoint::BYTE_LEN];
+        bytes[0] = 1;
+        assert_eq!(
+            RistrettoCheckpoint::from_bytes(&bytes),
+            Err(BackupError::Checksum)
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn serde_bincode_basepoint_roundtrip() {
@@ -1386,6 +1716,31 @@ mod test {
         assert!(bad_compressed.decompress().is_none());
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vartime_multiscalar_mul_compressed_matches_multiscalar_mul() {
+        let scalars: Vec<Scalar> = (0..40u64).map(|i| Scalar::from(i * 977 + 3)).collect();
+        let points: Vec<RistrettoPoint> = (0..40u64)
+            .map(|i| RistrettoPoint::mul_base(&Scalar::from(i + 11)))
+            .collect();
+        let mut terms: Vec<(Scalar, CompressedRistretto)> = scalars
+            .iter()
+            .zip(points.iter())
+            .map(|(s, P)| (*s, P.compress()))
+            .collect();
+
+        assert_eq!(
+            RistrettoPoint::vartime_multiscalar_mul_compressed(terms.clone()),
+            Ok(RistrettoPoint::vartime_multiscalar_mul(&scalars, &points))
+        );
+
+        terms[17].1 = CompressedRistretto([0xff; 32]);
+        assert_eq!(
+            RistrettoPoint::vartime_multiscalar_mul_compressed(terms),
+            Err(MultiscalarError::InvalidPoint { index: 17 })
+        );
+    }
+
     #[test]
     fn decompress_id() {
         let compressed_id = CompressedRistretto::identity();
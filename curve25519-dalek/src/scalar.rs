@@ -148,6 +148,7 @@ use zeroize::Zeroize;
 
 use crate::backend;
 use crate::constants;
+use crate::errors::NafError;
 
 cfg_if! {
     if #[cfg(curve25519_dalek_backend = "fiat")] {
@@ -234,6 +235,22 @@ pub struct Scalar {
 impl Scalar {
     /// Construct a `Scalar` by reducing a 256-bit little-endian integer
     /// modulo the group order \\( \ell \\).
+    ///
+    /// Every 32-byte array is accepted, and the result is always the
+    /// canonical representative in \\( [0, \ell) \\).  Use
+    /// [`Scalar::from_canonical_bytes`] instead to reject encodings which
+    /// are not already reduced, e.g. when parsing signatures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// // 2^256 - 1 is larger than the group order, so it is reduced.
+    /// let big = Scalar::from_bytes_mod_order([0xff; 32]);
+    /// assert_ne!(big.to_bytes(), [0xff; 32]);
+    /// assert_eq!(big, Scalar::from_bytes_mod_order(big.to_bytes()));
+    /// ```
     pub fn from_bytes_mod_order(bytes: [u8; 32]) -> Scalar {
         // Temporarily allow s_unreduced.bytes > 2^255 ...
         let s_unreduced = Scalar { bytes };
@@ -251,6 +268,36 @@ impl Scalar {
         UnpackedScalar::from_bytes_wide(input).pack()
     }
 
+    /// Construct a `Scalar` by reducing a little-endian integer of any
+    /// length modulo the group order \\( \ell \\).
+    ///
+    /// An input of up to 64 bytes gives the same scalar as
+    /// [`Scalar::from_bytes_mod_order_wide`] on the input padded with zeros,
+    /// and an empty input gives zero.  Longer inputs are reduced 32 bytes at
+    /// a time from the most significant end, each chunk being folded in
+    /// below the remainder so far with one 512-bit reduction, so the work
+    /// is linear in the length of the input and no copy of it is made.  The
+    /// time taken depends on the length of the input, but not on its
+    /// contents.
+    pub fn from_bytes_mod_order_arbitrary(input: &[u8]) -> Scalar {
+        let full_chunks = input.len() / 32;
+        let top = &input[32 * full_chunks..];
+
+        // Each reduction computes remainder * 2^256 + chunk
+        let mut wide = [0u8; 64];
+        wide[..top.len()].copy_from_slice(top);
+        let mut remainder = Scalar::from_bytes_mod_order_wide(&wide);
+        for chunk in input[..32 * full_chunks].chunks_exact(32).rev() {
+            wide[..32].copy_from_slice(chunk);
+            wide[32..].copy_from_slice(remainder.as_bytes());
+            remainder = Scalar::from_bytes_mod_order_wide(&wide);
+        }
+
+        #[cfg(feature = "zeroize")]
+        wide.zeroize();
+        remainder
+    }
+
     /// Attempt to construct a `Scalar` from a canonical byte representation.
     ///
     /// # Return
@@ -522,7 +569,9 @@ impl From<u64> for Scalar {
     ///
     /// # Returns
     ///
-    /// A `Scalar` corresponding to the input `u64`.
+    /// A `Scalar` corresponding to the input `u64`.  Since \\( 2\^{64} \\) is
+    /// smaller than the group order \\( \ell \\), every `u64` is already
+    /// reduced, so this cannot fail and never needs to reduce.
     ///
     /// # Example
     ///
@@ -677,6 +726,34 @@ impl Scalar {
         Scalar::from_bytes_mod_order_wide(&output)
     }
 
+    #[cfg(feature = "digest")]
+    /// Construct a scalar from an existing `Digest` instance with any
+    /// output size, reducing its output with
+    /// [`Scalar::from_bytes_mod_order_arbitrary`].
+    ///
+    /// For a 64-byte digest this is [`Scalar::from_hash`].  The result is
+    /// only close to uniformly distributed if the digest outputs well over
+    /// 253 bits: at least 48 bytes keeps the bias below \\(2^{-128}\\).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// use sha2::{Digest, Sha384, Sha512};
+    ///
+    /// let message = b"To really appreciate architecture, you may even need to commit a murder.";
+    /// let s = Scalar::from_hash_arbitrary(Sha384::new_with_prefix(message));
+    /// assert_eq!(s, Scalar::from_bytes_mod_order_arbitrary(&Sha384::digest(message)));
+    ///
+    /// assert_eq!(
+    ///     Scalar::from_hash_arbitrary(Sha512::new_with_prefix(message)),
+    ///     Scalar::from_hash(Sha512::new_with_prefix(message)),
+    /// );
+    /// ```
+    pub fn from_hash_arbitrary<D: Digest>(hash: D) -> Scalar {
+        Scalar::from_bytes_mod_order_arbitrary(hash.finalize().as_slice())
+    }
+
     /// Convert this `Scalar` to its underlying sequence of bytes.
     ///
     /// # Example
@@ -836,6 +913,74 @@ impl Scalar {
         ret
     }
 
+    /// Given a slice of nonzero (possibly secret) `Scalar`s, write their
+    /// inverses into `outputs`, without allocating.
+    ///
+    /// This is [`Scalar::batch_invert`] for `no_std` callers without
+    /// `alloc`: `outputs` doubles as the scratch space that function
+    /// allocates.  All the inputs are inverted with a single inversion.
+    ///
+    /// # Return
+    ///
+    /// Each element of `outputs` is set to the inverse of the element of
+    /// `inputs` at the same position.
+    ///
+    /// The product of all inverses is returned.
+    ///
+    /// # Warning
+    ///
+    /// All input `Scalars` **MUST** be nonzero.  If you cannot
+    /// *prove* that this is the case, you **SHOULD NOT USE THIS
+    /// FUNCTION**.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `outputs` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use curve25519_dalek::scalar::Scalar;
+    /// let scalars = [Scalar::from(3u64), Scalar::from(5u64), Scalar::from(7u64)];
+    /// let mut inverses = [Scalar::ZERO; 3];
+    ///
+    /// let allinv = Scalar::batch_invert_into(&scalars, &mut inverses);
+    ///
+    /// assert_eq!(allinv, Scalar::from(3 * 5 * 7u64).invert());
+    /// assert_eq!(inverses[0], Scalar::from(3u64).invert());
+    /// assert_eq!(inverses[2], Scalar::from(7u64).invert());
+    /// ```
+    pub fn batch_invert_into(inputs: &[Scalar], outputs: &mut [Scalar]) -> Scalar {
+        assert_eq!(
+            inputs.len(),
+            outputs.len(),
+            "batch_invert_into needs one output per input"
+        );
+
+        // As in `batch_invert`, with `outputs` holding the products of the
+        // previous inputs, in Montgomery form, until the second pass
+        // replaces them with the inverses.
+        let mut acc = Scalar::ONE.unpack().as_montgomery();
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            *output = acc.pack();
+            acc = UnpackedScalar::montgomery_mul(&acc, &input.unpack().as_montgomery());
+        }
+
+        // acc is nonzero iff all inputs are nonzero
+        debug_assert!(acc.pack() != Scalar::ZERO);
+
+        acc = acc.montgomery_invert().from_montgomery();
+        let ret = acc.pack();
+
+        for (input, output) in inputs.iter().rev().zip(outputs.iter_mut().rev()) {
+            let tmp = UnpackedScalar::montgomery_mul(&acc, &input.unpack().as_montgomery());
+            *output = UnpackedScalar::montgomery_mul(&acc, &output.unpack()).pack();
+            acc = tmp;
+        }
+
+        ret
+    }
+
     /// Get the bits of the scalar, in little-endian order
     pub(crate) fn bits_le(&self) -> impl DoubleEndedIterator<Item = bool> + '_ {
         (0..256).map(|i| {
@@ -846,6 +991,44 @@ impl Scalar {
         })
     }
 
+    /// Compute the width-\\(w\\) non-adjacent form of this scalar, for
+    /// \\(w\\) from [`NafDigits::MIN_WIDTH`] to [`NafDigits::MAX_WIDTH`].
+    ///
+    /// The digits \\(n\_i\\) satisfy
+    /// \\(\sum\_{i=0}\^{255} n\_i 2\^i = \mathtt{self}\\), every nonzero
+    /// digit is odd with \\(|n\_i| < 2\^{w-1}\\), and at most one of any
+    /// \\(w\\) consecutive digits is nonzero.  Together with a table of the
+    /// odd multiples \\(P, 3P, \ldots, (2\^{w-1} - 1)P\\), they let a caller
+    /// compute \\(\mathtt{self} \cdot P\\) with one doubling per digit and one
+    /// addition per nonzero digit, e.g. in a loop of their own which can
+    /// be suspended between digits.
+    ///
+    /// The positions of the nonzero digits depend on the scalar, so
+    /// algorithms built on them are variable-time: use this only with
+    /// public scalars.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NafError::WidthOutOfRange`] if `w` is not in `2..=8`.
+    ///
+    /// ```
+    /// use curve25519_dalek::scalar::Scalar;
+    ///
+    /// let s = Scalar::from(0xdead_beef_u64);
+    /// let naf = s.to_naf(5).unwrap();
+    /// assert!(naf.digits().iter().all(|d| d % 2 != 0 || *d == 0));
+    /// assert_eq!(naf.to_scalar(), s);
+    /// ```
+    pub fn to_naf(&self, w: usize) -> Result<NafDigits, NafError> {
+        if !(NafDigits::MIN_WIDTH..=NafDigits::MAX_WIDTH).contains(&w) {
+            return Err(NafError::WidthOutOfRange { width: w });
+        }
+        Ok(NafDigits {
+            width: w,
+            digits: self.non_adjacent_form(w),
+        })
+    }
+
     /// Compute a width-\\(w\\) "Non-Adjacent Form" of this scalar.
     ///
     /// A width-\\(w\\) NAF of a positive integer \\(k\\) is an expression
@@ -1136,6 +1319,84 @@ impl Scalar {
     }
 }
 
+/// The digits of a width-\\(w\\) non-adjacent form, as computed by
+/// [`Scalar::to_naf`].
+///
+/// A `NafDigits` always satisfies the non-adjacent form conditions for its
+/// width, whether computed from a scalar or checked by
+/// [`NafDigits::from_digits`], so code consuming the digits can index a
+/// table of \\(2\^{w-2}\\) odd multiples by \\(|n\_i| / 2\\) without
+/// further checks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NafDigits {
+    width: usize,
+    digits: [i8; 256],
+}
+
+impl NafDigits {
+    /// The narrowest width of a non-adjacent form.
+    pub const MIN_WIDTH: usize = 2;
+
+    /// The widest width of a non-adjacent form, whose digits still fit in
+    /// an `i8`.
+    pub const MAX_WIDTH: usize = 8;
+
+    /// Check that `digits`, least significant first, are a width-`w`
+    /// non-adjacent form, e.g. after loading them from storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NafError::WidthOutOfRange`] if `w` is not in `2..=8`, and
+    /// [`NafError::InvalidDigit`] with the index of the first digit which
+    /// is even and nonzero, at least \\(2\^{w-1}\\) in absolute value, or
+    /// within \\(w - 1\\) places of the previous nonzero digit.
+    pub fn from_digits(w: usize, digits: [i8; 256]) -> Result<NafDigits, NafError> {
+        if !(NafDigits::MIN_WIDTH..=NafDigits::MAX_WIDTH).contains(&w) {
+            return Err(NafError::WidthOutOfRange { width: w });
+        }
+        let bound = 1i16 << (w - 1);
+        let mut previous: Option<usize> = None;
+        for (index, digit) in digits.iter().enumerate() {
+            if *digit == 0 {
+                continue;
+            }
+            let too_close = previous.map_or(false, |p| index - p < w);
+            if digit % 2 == 0 || (*digit as i16).abs() >= bound || too_close {
+                return Err(NafError::InvalidDigit { index });
+            }
+            previous = Some(index);
+        }
+        Ok(NafDigits { width: w, digits })
+    }
+
+    /// The width \\(w\\) of this non-adjacent form.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The digits, least significant first.
+    pub fn digits(&self) -> &[i8; 256] {
+        &self.digits
+    }
+
+    /// Reconstruct the scalar \\(\sum\_i n\_i 2\^i \bmod \ell\\) these digits
+    /// represent.
+    pub fn to_scalar(&self) -> Scalar {
+        self.digits.iter().rev().fold(Scalar::ZERO, |acc, digit| {
+            let magnitude = Scalar::from(digit.unsigned_abs());
+            let digit = if *digit < 0 { -magnitude } else { magnitude };
+            acc + acc + digit
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for NafDigits {
+    fn zeroize(&mut self) {
+        self.digits.zeroize();
+    }
+}
+
 impl UnpackedScalar {
     /// Pack the limbs of this `UnpackedScalar` into a `Scalar`.
     fn pack(&self) -> Scalar {
@@ -1390,6 +1651,41 @@ pub const fn clamp_integer(mut bytes: [u8; 32]) -> [u8; 32] {
     bytes
 }
 
+/// Fetch the 32 little-endian bytes of a scalar from `chunks`, `N` bytes at
+/// a time, and pass each byte to `each`, most significant first.
+///
+/// Chunks are requested from the most significant down: `chunks` is given
+/// the range of byte positions to fill and a buffer of `N` bytes, which is
+/// wiped once its bytes have been consumed.
+///
+/// # Panics
+///
+/// Panics if `N` does not divide 32.
+pub(crate) fn stream_scalar_bytes<const N: usize, E, F, G>(
+    mut chunks: F,
+    mut each: G,
+) -> Result<(), E>
+where
+    F: FnMut(core::ops::Range<usize>, &mut [u8; N]) -> Result<(), E>,
+    G: FnMut(u8),
+{
+    assert!(N > 0 && 32 % N == 0, "chunk size {} does not divide 32", N);
+
+    let mut chunk = [0u8; N];
+    for end in (1..=32 / N).rev().map(|k| k * N) {
+        let result = chunks(end - N..end, &mut chunk);
+        if result.is_ok() {
+            for byte in chunk.iter().rev() {
+                each(*byte);
+            }
+        }
+        #[cfg(feature = "zeroize")]
+        chunk.zeroize();
+        result?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
@@ -1561,6 +1857,69 @@ pub(crate) mod test {
         }
     }
 
+    #[test]
+    fn to_naf_round_trips() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let x = Scalar::random(&mut rng);
+            for w in NafDigits::MIN_WIDTH..=NafDigits::MAX_WIDTH {
+                let naf = x.to_naf(w).unwrap();
+                assert_eq!(naf.width(), w);
+                assert_eq!(naf.to_scalar(), x);
+                assert_eq!(NafDigits::from_digits(w, *naf.digits()), Ok(naf));
+            }
+        }
+        assert_eq!(A_SCALAR.to_naf(5).unwrap().digits(), &A_NAF);
+        assert_eq!(Scalar::ZERO.to_naf(2).unwrap().digits(), &[0i8; 256]);
+
+        assert_eq!(
+            A_SCALAR.to_naf(1),
+            Err(NafError::WidthOutOfRange { width: 1 })
+        );
+        assert_eq!(
+            A_SCALAR.to_naf(9),
+            Err(NafError::WidthOutOfRange { width: 9 })
+        );
+    }
+
+    #[test]
+    fn naf_digits_are_validated() {
+        let mut digits = [0i8; 256];
+        digits[0] = 15;
+        digits[5] = -3;
+        digits[255] = 1;
+        let naf = NafDigits::from_digits(5, digits).unwrap();
+        let mut two_255 = Scalar::ONE;
+        for _ in 0..255 {
+            two_255 += two_255;
+        }
+        assert_eq!(
+            naf.to_scalar(),
+            Scalar::from(15u64) - Scalar::from(3u64 * 32) + two_255
+        );
+
+        // An even digit, a digit too large for the width, and two nonzero
+        // digits closer than the width are each rejected
+        let invalid = |index: usize, digit: i8| {
+            let mut d = digits;
+            d[index] = digit;
+            NafDigits::from_digits(5, d)
+        };
+        assert_eq!(invalid(100, 4), Err(NafError::InvalidDigit { index: 100 }));
+        assert_eq!(invalid(100, 17), Err(NafError::InvalidDigit { index: 100 }));
+        assert_eq!(
+            invalid(100, -17),
+            Err(NafError::InvalidDigit { index: 100 })
+        );
+        assert_eq!(invalid(4, 1), Err(NafError::InvalidDigit { index: 4 }));
+        assert_eq!(invalid(9, 1), Err(NafError::InvalidDigit { index: 9 }));
+        assert!(invalid(10, 1).is_ok());
+        assert_eq!(
+            NafDigits::from_digits(0, digits),
+            Err(NafError::WidthOutOfRange { width: 0 })
+        );
+    }
+
     #[test]
     fn from_u64() {
         let val: u64 = 0xdeadbeefdeadbeef;
@@ -1575,6 +1934,29 @@ pub(crate) mod test {
         assert_eq!(s[0], 0xef);
     }
 
+    #[test]
+    fn from_u64_edge_values() {
+        assert_eq!(Scalar::from(0u64), Scalar::ZERO);
+        assert_eq!(Scalar::from(1u64), Scalar::ONE);
+
+        let max = Scalar::from(u64::MAX);
+        assert_eq!(max.as_bytes()[..8], [0xff; 8]);
+        assert_eq!(max.as_bytes()[8..], [0u8; 24]);
+        assert_eq!(max, Scalar::from_bytes_mod_order(*max.as_bytes()));
+        assert_eq!(max + Scalar::ONE, Scalar::from(1u128 << 64));
+
+        let basepoint = constants::ED25519_BASEPOINT_POINT;
+        let point = basepoint * Scalar::from(3u64);
+        assert_eq!(
+            crate::edwards::EdwardsPoint::vartime_double_scalar_mul_basepoint(
+                &max,
+                &point,
+                &Scalar::from(7u64)
+            ),
+            basepoint * (max * Scalar::from(3u64) + Scalar::from(7u64))
+        );
+    }
+
     #[test]
     fn scalar_mul_by_one() {
         let test_scalar = X * Scalar::ONE;
@@ -1687,6 +2069,39 @@ pub(crate) mod test {
         assert_eq!(biggest, CANONICAL_2_256_MINUS_1);
     }
 
+    #[test]
+    fn from_bytes_mod_order_arbitrary() {
+        // Reduce byte by byte, from the most significant
+        fn reference(input: &[u8]) -> Scalar {
+            input.iter().rev().fold(Scalar::ZERO, |acc, byte| {
+                acc * Scalar::from(256u64) + Scalar::from(*byte as u64)
+            })
+        }
+
+        let mut input = [0u8; 200];
+        for (i, byte) in input.iter_mut().enumerate() {
+            *byte = X[i % 32] ^ (i as u8).wrapping_mul(151);
+        }
+        for len in [0, 1, 31, 32, 33, 63, 64, 65, 96, 127, 128, 200] {
+            let s = Scalar::from_bytes_mod_order_arbitrary(&input[..len]);
+            assert_eq!(s, reference(&input[..len]), "length {}", len);
+            assert!(s.is_canonical().unwrap_u8() == 1);
+            if len <= 64 {
+                let mut wide = [0u8; 64];
+                wide[..len].copy_from_slice(&input[..len]);
+                assert_eq!(s, Scalar::from_bytes_mod_order_wide(&wide));
+            }
+        }
+
+        // Leading zeros do not change the value
+        let mut padded = [0u8; 300];
+        padded[..200].copy_from_slice(&input);
+        assert_eq!(
+            Scalar::from_bytes_mod_order_arbitrary(&padded),
+            Scalar::from_bytes_mod_order_arbitrary(&input)
+        );
+    }
+
     #[test]
     fn from_bytes_mod_order_wide() {
         let mut bignum = [0u8; 64];
@@ -1855,6 +2270,32 @@ pub(crate) mod test {
         }
     }
 
+    #[test]
+    fn batch_invert_into_consistency() {
+        let mut inputs = [Scalar::ZERO; 16];
+        let mut x = Scalar::from(3u64);
+        for input in inputs.iter_mut() {
+            *input = x;
+            x = x * x + Scalar::ONE;
+        }
+        let mut outputs = [Scalar::ZERO; 16];
+
+        let expected: Scalar = inputs.iter().product();
+        let ret = Scalar::batch_invert_into(&inputs, &mut outputs);
+        assert_eq!(ret, expected.invert());
+        for (a, b) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(a * b, Scalar::ONE);
+        }
+
+        assert_eq!(Scalar::batch_invert_into(&[], &mut []), Scalar::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "one output per input")]
+    fn batch_invert_into_length_mismatch_panics() {
+        Scalar::batch_invert_into(&[Scalar::ONE; 2], &mut [Scalar::ZERO; 3]);
+    }
+
     #[cfg(feature = "precomputed-tables")]
     fn test_pippenger_radix_iter(scalar: Scalar, w: usize) {
         let digits_count = Scalar::to_radix_2w_size_hint(w);
@@ -1964,13 +2405,13 @@ pub(crate) mod test {
 
         // ROOT_OF_UNITY^{2^s} mod m == 1
         assert_eq!(
-            Scalar::ROOT_OF_UNITY.pow(&[1u64 << Scalar::S, 0, 0, 0]),
+            Scalar::ROOT_OF_UNITY.pow([1u64 << Scalar::S, 0, 0, 0]),
             Scalar::ONE,
         );
 
         // DELTA^{t} mod m == 1
         assert_eq!(
-            Scalar::DELTA.pow(&[
+            Scalar::DELTA.pow([
                 0x9604_98c6_973d_74fb,
                 0x0537_be77_a8bd_e735,
                 0x0000_0000_0000_0000,
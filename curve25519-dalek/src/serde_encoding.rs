@@ -0,0 +1,117 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Serde support for checkpoints, contexts and tables, which are encoded
+//! as a fixed number of fixed-size chunks.
+//!
+//! Like points and scalars, these serialize as a tuple of bytes: formats
+//! such as postcard and bincode write exactly the bytes of the encoding,
+//! with no length prefix.  Tables are decoded one entry at a time as the
+//! bytes arrive, so that loading one needs no buffer the size of the
+//! whole encoding.
+
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserializer, Serializer};
+
+/// Serialize the `count` chunks of `chunks` as a single tuple of bytes.
+pub(crate) fn serialize_chunks<S, I, const M: usize>(
+    serializer: S,
+    count: usize,
+    chunks: I,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: IntoIterator<Item = [u8; M]>,
+{
+    let mut tup = serializer.serialize_tuple(count * M)?;
+    for chunk in chunks {
+        for byte in chunk.iter() {
+            tup.serialize_element(byte)?;
+        }
+    }
+    tup.end()
+}
+
+/// Deserialize a tuple of `count` chunks of `M` bytes, passing each chunk
+/// and its index to `decode` as soon as it is complete.
+pub(crate) fn deserialize_chunks<'de, D, F, E, const M: usize>(
+    deserializer: D,
+    count: usize,
+    expecting: &'static str,
+    decode: F,
+) -> Result<(), D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnMut(usize, &[u8; M]) -> Result<(), E>,
+    E: Display,
+{
+    struct ChunkVisitor<F, E, const M: usize> {
+        count: usize,
+        expecting: &'static str,
+        decode: F,
+        error: PhantomData<E>,
+    }
+
+    impl<'de, F, E, const M: usize> Visitor<'de> for ChunkVisitor<F, E, M>
+    where
+        F: FnMut(usize, &[u8; M]) -> Result<(), E>,
+        E: Display,
+    {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str(self.expecting)
+        }
+
+        fn visit_seq<A>(mut self, mut seq: A) -> Result<(), A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut chunk = [0u8; M];
+            for index in 0..self.count {
+                for (i, byte) in chunk.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(index * M + i, &self))?;
+                }
+                (self.decode)(index, &chunk).map_err(A::Error::custom)?;
+            }
+            Ok(())
+        }
+    }
+
+    deserializer.deserialize_tuple(
+        count * M,
+        ChunkVisitor {
+            count,
+            expecting,
+            decode,
+            error: PhantomData,
+        },
+    )
+}
+
+/// Deserialize a tuple of `M` bytes and decode it with `decode`.
+pub(crate) fn deserialize_with<'de, D, T, F, E, const M: usize>(
+    deserializer: D,
+    expecting: &'static str,
+    decode: F,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(&[u8]) -> Result<T, E>,
+    E: Display,
+{
+    let mut bytes = [0u8; M];
+    deserialize_chunks(deserializer, 1, expecting, |_, chunk: &[u8; M]| {
+        bytes = *chunk;
+        Ok::<(), E>(())
+    })?;
+    decode(&bytes).map_err(D::Error::custom)
+}
@@ -0,0 +1,29 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! Checks shared between the test modules of the point types.
+
+use group::{Group, GroupEncoding};
+
+use crate::scalar::Scalar;
+
+/// Check a `group` implementation's encoding and arithmetic against
+/// each other.
+pub(crate) fn check_group_impl<G: Group<Scalar = Scalar> + GroupEncoding>() {
+    let p = G::generator() * Scalar::from(12345u64);
+    assert_eq!(G::from_bytes(&p.to_bytes()).unwrap(), p);
+    assert_eq!(G::from_bytes_unchecked(&p.to_bytes()).unwrap(), p);
+    assert_eq!(
+        G::from_bytes(&G::identity().to_bytes()).unwrap(),
+        G::identity()
+    );
+    assert!(bool::from(G::identity().is_identity()));
+    assert!(!bool::from(p.is_identity()));
+    assert_eq!(p.double(), p + p);
+    assert_eq!(p - p.double(), -p);
+
+    let r = G::random(&mut rand_core::OsRng);
+    assert!(!bool::from(r.is_identity()));
+}
@@ -14,7 +14,9 @@
 #![allow(non_snake_case)]
 
 use core::borrow::Borrow;
+use core::ops::Range;
 
+use crate::resumable::{WithProgress, WorkDone};
 use crate::scalar::{clamp_integer, Scalar};
 use subtle::ConstantTimeEq;
 
@@ -413,6 +415,123 @@ pub trait VartimePrecomputedMultiscalarMul: Sized {
         K: IntoIterator<Item = Option<Self::Point>>;
 }
 
+/// A trait for long-running computations which can be run in bounded
+/// chunks, suspended between them, and resumed from their state.
+///
+/// An implementor holds the inputs of one computation; everything that
+/// changes as the computation progresses lives in its
+/// [`State`](Interruptible::State), which is all a caller has to keep
+/// between chunks:
+///
+/// ```
+/// use curve25519_dalek::constants;
+/// use curve25519_dalek::resumable::VariableBaseMul;
+/// use curve25519_dalek::scalar::Scalar;
+/// use curve25519_dalek::traits::Interruptible;
+///
+/// let P = constants::ED25519_BASEPOINT_POINT;
+/// let s = Scalar::from(1234567u64);
+/// let job = VariableBaseMul::new(&P, &s);
+///
+/// let mut state = job.start();
+/// while !job.advance(&mut state, 10) {
+///     // `state` can be stored here and the loop resumed later.
+/// }
+/// assert_eq!(job.finish(&state), Some(s * P));
+/// ```
+pub trait Interruptible {
+    /// The state of the computation between chunks.
+    type State;
+
+    /// The result of the computation.
+    type Output;
+
+    /// Return the state of the computation before any work has been done.
+    fn start(&self) -> Self::State;
+
+    /// Perform at most `steps` iterations of the computation on `state`.
+    ///
+    /// What an iteration is depends on the computation, but each one costs
+    /// a bounded amount of work.  Returns `true` once the computation is
+    /// complete; further calls then leave `state` unchanged.
+    fn advance(&self, state: &mut Self::State, steps: usize) -> bool;
+
+    /// Return the result if `state` is complete, or `None` otherwise.
+    fn finish(&self, state: &Self::State) -> Option<Self::Output>;
+
+    /// Return the number of steps `state` has completed and the total
+    /// number of steps of the computation, or `None` if the computation
+    /// does not track them.
+    fn progress(&self, _state: &Self::State) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Wrap this computation so that `progress` is called with the steps
+    /// done and the total after every [`advance`](Interruptible::advance),
+    /// e.g. to display a completion percentage, or to detect a stalled
+    /// computation whose count stops moving.
+    fn with_progress<F: Fn(usize, usize)>(self, progress: F) -> WithProgress<Self, F>
+    where
+        Self: Sized,
+    {
+        WithProgress::new(self, progress)
+    }
+
+    /// Run the computation to completion in one call, and return
+    /// [`finish`](Interruptible::finish) of the final state.
+    fn run(&self) -> Option<Self::Output> {
+        let mut state = self.start();
+        while !self.advance(&mut state, usize::MAX) {}
+        self.finish(&state)
+    }
+}
+
+/// Trait for step-wise computations whose work can be predicted before they
+/// run, so that hosts which meter computation can set their limits
+/// deterministically.
+///
+/// A step is what the computation's resume calls count against their
+/// budget: an [`Interruptible::advance`] step, or a loop iteration of a
+/// step-wise multiplication.  The counts are exact, not estimates: they
+/// depend only on the computation's inputs.
+///
+/// ```
+/// use curve25519_dalek::constants;
+/// use curve25519_dalek::resumable::VariableBaseMul;
+/// use curve25519_dalek::scalar::Scalar;
+/// use curve25519_dalek::traits::CostModel;
+///
+/// let job = VariableBaseMul::new(&constants::ED25519_BASEPOINT_POINT, &Scalar::ONE);
+/// assert_eq!(job.calls(10), 7);
+/// assert_eq!(job.call_work(6, 10).doublings, 16);
+/// ```
+pub trait CostModel {
+    /// The number of steps the computation takes from start to finish.
+    fn total_steps(&self) -> usize;
+
+    /// The doublings and additions performed by `steps`, counted from the
+    /// start of the computation.  Steps past the end perform no work.
+    fn work(&self, steps: Range<usize>) -> WorkDone;
+
+    /// The number of calls needed to complete the computation when each
+    /// call performs up to `budget` steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    fn calls(&self, budget: usize) -> usize {
+        assert!(budget > 0, "budget must be nonzero");
+        (self.total_steps() + budget - 1) / budget
+    }
+
+    /// The doublings and additions performed by call number `call`,
+    /// counted from zero, when each call performs up to `budget` steps.
+    fn call_work(&self, call: usize, budget: usize) -> WorkDone {
+        let start = call.saturating_mul(budget);
+        self.work(start..start.saturating_add(budget))
+    }
+}
+
 // ------------------------------------------------------------------------
 // Private Traits
 // ------------------------------------------------------------------------
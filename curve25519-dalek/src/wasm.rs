@@ -0,0 +1,343 @@
+// -*- mode: rust; -*-
+//
+// This file is part of curve25519-dalek.
+// See LICENSE for licensing information.
+
+//! A byte-oriented wrapper around the step-wise multiplication of
+//! [`resumable`](crate::resumable), for hosts across a WebAssembly
+//! boundary.
+//!
+//! [`step_mul`](crate::resumable::step_mul) takes scalars, points and a
+//! [`Checkpoint`] holding `[u64; 15]`, none of which a JavaScript host can
+//! pass directly.  [`WasmStepMul`] takes and returns only byte slices,
+//! `Vec<u8>`, `String` and fieldless enums, and every type here is
+//! exported with `#[wasm_bindgen]`, so a host can call it from JavaScript
+//! without a wrapper crate of its own.
+//!
+//! Each call to [`WasmStepMul::resume`] returns a [`WasmStepOutcome`]
+//! tagged with a [`WasmStepStatus`] instead of a status byte, carrying
+//! either the next checkpoint, the result, or a [`WasmError`].
+//!
+//! ```
+//! use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+//! use curve25519_dalek::edwards::EdwardsPoint;
+//! use curve25519_dalek::scalar::Scalar;
+//! use curve25519_dalek::wasm::{WasmStepMul, WasmStepStatus};
+//!
+//! let a = Scalar::from(1234u64);
+//! let b = Scalar::from(5678u64);
+//! let A = ED25519_BASEPOINT_POINT.mul_by_cofactor();
+//!
+//! let job = WasmStepMul::new(&a.to_bytes(), A.compress().as_bytes(), &b.to_bytes()).unwrap();
+//! let mut outcome = job.start();
+//! while outcome.status() == WasmStepStatus::InProgress {
+//!     // the host may store `outcome.bytes()` and resume after a reload
+//!     outcome = job.resume(&outcome.bytes());
+//! }
+//!
+//! assert_eq!(outcome.status(), WasmStepStatus::Done);
+//! let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+//! assert_eq!(outcome.bytes(), expected.compress().as_bytes().to_vec());
+//! ```
+
+#![allow(non_snake_case)]
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::edwards::CompressedEdwardsY;
+use crate::resumable::{Checkpoint, PrecomputedStepMulContext, StepMulState, StepResult};
+use crate::scalar::Scalar;
+
+/// The kind of a [`WasmError`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[wasm_bindgen]
+pub enum WasmErrorKind {
+    /// A scalar is not the canonical 32-byte encoding of a scalar.
+    InvalidScalar,
+    /// The point is not the 32-byte encoding of a curve point.
+    InvalidPoint,
+    /// A checkpoint could not be decoded; see
+    /// [`Checkpoint::from_bytes`].
+    InvalidCheckpoint,
+    /// A serialized context could not be decoded; see
+    /// [`PrecomputedStepMulContext::from_bytes`].
+    InvalidContext,
+    /// A decoded checkpoint cannot be resumed from; see
+    /// [`step_mul`](crate::resumable::step_mul).
+    Step,
+}
+
+/// An error from [`WasmStepMul`], with a message suitable for showing to
+/// the host.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[wasm_bindgen]
+pub struct WasmError {
+    kind: WasmErrorKind,
+    message: String,
+}
+
+#[wasm_bindgen]
+impl WasmError {
+    fn new<E: Display>(kind: WasmErrorKind, error: E) -> WasmError {
+        WasmError {
+            kind,
+            message: error.to_string(),
+        }
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> WasmErrorKind {
+        self.kind
+    }
+
+    /// A description of what went wrong.
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl Display for WasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WasmError {}
+
+/// The tag of a [`WasmStepOutcome`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[wasm_bindgen]
+pub enum WasmStepStatus {
+    /// The call ran out of budget; its bytes are the checkpoint to resume
+    /// from.
+    InProgress,
+    /// The multiplication is complete; its bytes are the compressed result.
+    Done,
+    /// The call failed; see [`WasmStepOutcome::error`].
+    Failed,
+}
+
+/// The outcome of a call to [`WasmStepMul::start`] or
+/// [`WasmStepMul::resume`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[wasm_bindgen]
+pub struct WasmStepOutcome {
+    status: WasmStepStatus,
+    bytes: Vec<u8>,
+    error: Option<WasmError>,
+}
+
+#[wasm_bindgen]
+impl WasmStepOutcome {
+    fn failed(error: WasmError) -> WasmStepOutcome {
+        WasmStepOutcome {
+            status: WasmStepStatus::Failed,
+            bytes: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    /// Whether the multiplication is in progress, done, or failed.
+    pub fn status(&self) -> WasmStepStatus {
+        self.status
+    }
+
+    /// The checkpoint, as [`Checkpoint::to_bytes`], if the multiplication
+    /// is in progress; the compressed result if it is done; and empty if
+    /// the call failed.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    /// The error, if the call failed.
+    pub fn error(&self) -> Option<WasmError> {
+        self.error.clone()
+    }
+}
+
+/// A step-wise computation of \\(aA + bB\\) in variable time, where \\(B\\)
+/// is the Ed25519 basepoint, taking and returning bytes.
+///
+/// This holds a [`PrecomputedStepMulContext`], so each call spends its
+/// whole budget on the multiplication.
+#[derive(Clone, Debug)]
+#[wasm_bindgen]
+pub struct WasmStepMul {
+    context: PrecomputedStepMulContext,
+}
+
+#[wasm_bindgen]
+impl WasmStepMul {
+    /// Set up the computation of \\(aA + bB\\) from the canonical encodings
+    /// of \\(a\\) and \\(b\\) and the compressed encoding of \\(A\\).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WasmErrorKind::InvalidScalar`] error if `a` or `b` is
+    /// not the canonical encoding of a scalar, and a
+    /// [`WasmErrorKind::InvalidPoint`] error if `A` does not decompress.
+    pub fn new(a: &[u8], A: &[u8], b: &[u8]) -> Result<WasmStepMul, WasmError> {
+        let a = decode_scalar(a)?;
+        let b = decode_scalar(b)?;
+        let A = CompressedEdwardsY::from_slice(A)
+            .ok()
+            .and_then(|A| A.decompress())
+            .ok_or_else(|| {
+                WasmError::new(
+                    WasmErrorKind::InvalidPoint,
+                    "Point is not a valid compressed Edwards point",
+                )
+            })?;
+        Ok(WasmStepMul {
+            context: PrecomputedStepMulContext::new(&a, &A, &b),
+        })
+    }
+
+    /// Restore a computation from [`WasmStepMul::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`WasmErrorKind::InvalidContext`] error if `bytes` is not
+    /// a context this build can read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmStepMul, WasmError> {
+        PrecomputedStepMulContext::from_bytes(bytes)
+            .map(|context| WasmStepMul { context })
+            .map_err(|e| WasmError::new(WasmErrorKind::InvalidContext, e))
+    }
+
+    /// Serialize the computation, as [`PrecomputedStepMulContext::to_bytes`],
+    /// to store alongside its checkpoints.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.context.to_bytes().to_vec()
+    }
+
+    /// Start the multiplication, performing up to
+    /// [`DEFAULT_STEP_BUDGET`](crate::resumable::DEFAULT_STEP_BUDGET)
+    /// loop iterations.
+    pub fn start(&self) -> WasmStepOutcome {
+        self.step(StepMulState::Fresh)
+    }
+
+    /// Continue the multiplication from a checkpoint an earlier call
+    /// returned, performing up to
+    /// [`DEFAULT_STEP_BUDGET`](crate::resumable::DEFAULT_STEP_BUDGET)
+    /// loop iterations.
+    ///
+    /// The outcome is [`WasmStepStatus::Failed`], with a
+    /// [`WasmErrorKind::InvalidCheckpoint`] or [`WasmErrorKind::Step`]
+    /// error, if `checkpoint` cannot be resumed from.
+    pub fn resume(&self, checkpoint: &[u8]) -> WasmStepOutcome {
+        match Checkpoint::from_bytes(checkpoint) {
+            Ok(checkpoint) => self.step(StepMulState::Resumed(checkpoint)),
+            Err(e) => WasmStepOutcome::failed(WasmError::new(WasmErrorKind::InvalidCheckpoint, e)),
+        }
+    }
+
+    fn step(&self, state: StepMulState) -> WasmStepOutcome {
+        match self.context.step(state) {
+            Ok(StepResult::InProgress(checkpoint)) => WasmStepOutcome {
+                status: WasmStepStatus::InProgress,
+                bytes: checkpoint.to_bytes().to_vec(),
+                error: None,
+            },
+            Ok(StepResult::Done(point)) => WasmStepOutcome {
+                status: WasmStepStatus::Done,
+                bytes: point.compress().as_bytes().to_vec(),
+                error: None,
+            },
+            Err(e) => WasmStepOutcome::failed(WasmError::new(WasmErrorKind::Step, e)),
+        }
+    }
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar, WasmError> {
+    <[u8; 32]>::try_from(bytes)
+        .ok()
+        .and_then(|bytes| Option::from(Scalar::from_canonical_bytes(bytes)))
+        .ok_or_else(|| {
+            WasmError::new(
+                WasmErrorKind::InvalidScalar,
+                "Scalar is not a canonical 32-byte encoding",
+            )
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constants;
+    use crate::edwards::EdwardsPoint;
+
+    fn job() -> (WasmStepMul, Vec<u8>) {
+        let a = Scalar::from_bytes_mod_order([0x5a; 32]);
+        let b = -Scalar::from(987_654_321u64);
+        let A = constants::ED25519_BASEPOINT_POINT * Scalar::from(42u64);
+        let expected = EdwardsPoint::vartime_double_scalar_mul_basepoint(&a, &A, &b);
+        let job = WasmStepMul::new(&a.to_bytes(), A.compress().as_bytes(), &b.to_bytes()).unwrap();
+        (job, expected.compress().as_bytes().to_vec())
+    }
+
+    #[test]
+    fn round_trip_through_bytes() {
+        let (job, expected) = job();
+        let mut outcome = job.start();
+        let mut calls = 1;
+        while outcome.status() == WasmStepStatus::InProgress {
+            assert_eq!(outcome.bytes().len(), Checkpoint::BYTE_LEN);
+            assert_eq!(outcome.error(), None);
+            // Restore the job from bytes as a reloaded host would
+            let job = WasmStepMul::from_bytes(&job.to_bytes()).unwrap();
+            outcome = job.resume(&outcome.bytes());
+            calls += 1;
+        }
+        assert!(calls > 1);
+        assert_eq!(outcome.status(), WasmStepStatus::Done);
+        assert_eq!(outcome.bytes(), expected);
+    }
+
+    #[test]
+    fn errors_are_tagged() {
+        let one = Scalar::ONE.to_bytes();
+        let B = constants::ED25519_BASEPOINT_COMPRESSED;
+
+        let kind = |r: Result<WasmStepMul, WasmError>| r.unwrap_err().kind();
+        assert_eq!(
+            kind(WasmStepMul::new(&[0xff; 32], B.as_bytes(), &one)),
+            WasmErrorKind::InvalidScalar
+        );
+        assert_eq!(
+            kind(WasmStepMul::new(&one, B.as_bytes(), &one[..31])),
+            WasmErrorKind::InvalidScalar
+        );
+        // y = 2 is not the y-coordinate of a curve point
+        let mut not_a_point = [0u8; 32];
+        not_a_point[0] = 2;
+        assert_eq!(
+            kind(WasmStepMul::new(&one, &not_a_point, &one)),
+            WasmErrorKind::InvalidPoint
+        );
+        assert_eq!(
+            kind(WasmStepMul::from_bytes(&[0u8; 7])),
+            WasmErrorKind::InvalidContext
+        );
+
+        let (job, _) = job();
+        let outcome = job.resume(&[0u8; 3]);
+        assert_eq!(outcome.status(), WasmStepStatus::Failed);
+        assert!(outcome.bytes().is_empty());
+        let error = outcome.error().unwrap();
+        assert_eq!(error.kind(), WasmErrorKind::InvalidCheckpoint);
+        assert!(!error.message().is_empty());
+
+        // A checksummed checkpoint with an index the loop cannot resume from
+        let checkpoint = Checkpoint::from_bytes(&job.start().bytes()).unwrap();
+        let bad_index = Checkpoint::new(400, checkpoint.projective_point());
+        let outcome = job.resume(&bad_index.to_bytes());
+        assert_eq!(outcome.error().unwrap().kind(), WasmErrorKind::Step);
+    }
+}